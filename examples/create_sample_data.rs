@@ -277,7 +277,7 @@ struct SnapshotConfig {
     total_files: usize,
 }
 
-async fn run_manual(file_io: iceberg::io::FileIO, table_location: &str) -> Result<()> {
+pub async fn run_manual(file_io: iceberg::io::FileIO, table_location: &str) -> Result<()> {
     let schema_v0 = build_schema_v0()?;
     let schema_v1 = build_schema_v1()?;
     let schema_v0_ref = Arc::new(schema_v0.clone());
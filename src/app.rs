@@ -1,45 +1,69 @@
 use std::io;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result};
-use crossterm::event::{Event, KeyCode, KeyEvent};
+use anyhow::{bail, Context, Result};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use crossterm::ExecutableCommand;
+use futures::StreamExt;
 use ratatui::prelude::*;
-use ratatui::widgets::Tabs;
+use ratatui::widgets::{Paragraph, Tabs, Wrap};
 use tokio::sync::mpsc;
 
 use crate::cli::{self, Cli, Command};
+use crate::clipboard;
 use crate::components::column_selector::ColumnSelector;
 use crate::components::data_view::DataView;
 use crate::components::file_stats_panel::FileStatsPanel;
 use crate::components::filter_bar::FilterBar;
 use crate::components::help_popup::HelpPopup;
-use crate::components::manifest_panel::ManifestPanel;
+use crate::components::manifest_panel::{ManifestPanel, FILE_INVENTORY_HEADER};
 use crate::components::properties_panel::PropertiesPanel;
+use crate::components::recent_picker::RecentPicker;
+use crate::components::scan_plan_popup::ScanPlanPopup;
 use crate::components::schema_panel::SchemaPanel;
 use crate::components::snapshot_panel::SnapshotPanel;
+#[cfg(feature = "sql")]
+use crate::components::sql_prompt::SqlPrompt;
+use crate::components::stats_popup::{StatsPopup, HISTOGRAM_BUCKETS};
 use crate::components::status_bar::StatusBar;
 use crate::components::Component;
-use crate::event::{spawn_event_reader, to_key_event, Action, AppMessage};
-use crate::loader::arrow_convert::total_row_count;
-use crate::loader::catalog_loader::load_from_catalog;
-use crate::loader::direct_loader::load_direct;
-use crate::loader::scan::{execute_scan, ScanRequest};
-use crate::loader::TableHandle;
-use crate::model::filter;
+use crate::event::{spawn_event_reader, to_key_event, Action, AppMessage, LoadTask};
+use crate::export;
+use crate::loader::arrow_convert::{self, total_row_count};
+use crate::loader::catalog_loader::CatalogTable;
+use crate::loader::direct_loader::{
+    is_newer_metadata, load_direct, metadata_version_label, VersionHint,
+};
+use crate::loader::manifest_cache::{CachedManifests, ManifestCache, DEFAULT_MANIFEST_CACHE_SIZE};
+use crate::loader::projection::{self, Projection};
+use crate::loader::scan::{count_live_rows, execute_scan, plan_scan, ScanRequest, ScanResult};
+use crate::loader::{TableCounts, TableHandle};
+use crate::model::file_diagnostics::{diagnose, DiagnosticsRequest};
+use crate::model::filter::{self, ScanFilter};
 use crate::model::table_info::{DataFileInfo, ManifestInfo};
-use crate::ui::layout::{AppLayout, DataTabLayout};
+use crate::recents::{self, RecentTable};
+use crate::ui::layout::{self, AppLayout, DataTabLayout};
 use crate::ui::theme::Theme;
-use crate::ui::{Focus, Tab};
+use crate::ui::theme_preview;
+use crate::ui::{self, Focus, Tab};
+use crate::util::format::format_timestamp_ms;
+use iceberg::io::FileIO;
+use iceberg::spec::{ManifestFile, ManifestList};
 
 static TABLE_HANDLE: Mutex<Option<TableHandle>> = Mutex::new(None);
+/// Catalog client of a `catalog` table, kept so reloads don't reconnect. It is replaced
+/// when a load through it fails.
+static CATALOG_TABLE: Mutex<Option<CatalogTable>> = Mutex::new(None);
 
 struct App {
     data_view: DataView,
     filter_bar: FilterBar,
+    #[cfg(feature = "sql")]
+    sql_prompt: SqlPrompt,
     column_selector: ColumnSelector,
     schema_panel: SchemaPanel,
     snapshot_panel: SnapshotPanel,
@@ -48,6 +72,8 @@ struct App {
     properties_panel: PropertiesPanel,
     status_bar: StatusBar,
     help_popup: HelpPopup,
+    scan_plan_popup: ScanPlanPopup,
+    stats_popup: StatsPopup,
     active_tab: Tab,
     focus: Focus,
     initial_columns: Option<Vec<String>>,
@@ -56,6 +82,15 @@ struct App {
     has_more: bool,
     selected_snapshot_id: Option<i64>,
     current_snapshot_id: Option<i64>,
+    projection: Option<Projection>,
+    sql: Option<String>,
+    manifest_cache: ManifestCache,
+    /// Manifests loaded at once for the Files and Stats tabs and the row count.
+    max_concurrency: usize,
+    metadata_columns: bool,
+    group_numbers: bool,
+    /// Generation of the latest data scan; messages of earlier scans still running are dropped.
+    scan_generation: u64,
 }
 
 impl App {
@@ -63,6 +98,8 @@ impl App {
         Self {
             data_view: DataView::new(),
             filter_bar: FilterBar::new(),
+            #[cfg(feature = "sql")]
+            sql_prompt: SqlPrompt::new(),
             column_selector: ColumnSelector::new(),
             schema_panel: SchemaPanel::new(),
             snapshot_panel: SnapshotPanel::new(),
@@ -71,23 +108,80 @@ impl App {
             properties_panel: PropertiesPanel::new(),
             status_bar: StatusBar::new(),
             help_popup: HelpPopup::new(),
+            scan_plan_popup: ScanPlanPopup::new(),
+            stats_popup: StatsPopup::new(),
             active_tab: Tab::Data,
-            focus: Focus::Left,
+            focus: Focus::Content,
             initial_columns,
             limit,
             page_size,
             has_more: false,
             selected_snapshot_id: None,
             current_snapshot_id: None,
+            projection: None,
+            sql: None,
+            manifest_cache: ManifestCache::new(DEFAULT_MANIFEST_CACHE_SIZE),
+            max_concurrency: cli::DEFAULT_MAX_CONCURRENCY,
+            metadata_columns: false,
+            group_numbers: false,
+            scan_generation: 0,
+        }
+    }
+
+    /// Scan the table again, replacing any scan still running.
+    fn rescan(&mut self, msg_tx: &mpsc::UnboundedSender<AppMessage>, request: ScanRequest) {
+        self.scan_generation += 1;
+        spawn_rescan(msg_tx.clone(), request, self.scan_generation);
+    }
+
+    /// Build a scan request for the current snapshot, limit and post-scan transforms.
+    fn scan_request(&self, filter: Option<ScanFilter>, columns: Vec<String>) -> ScanRequest {
+        let filter = filter.unwrap_or_default();
+        ScanRequest {
+            columns: if columns.is_empty() {
+                None
+            } else {
+                Some(columns)
+            },
+            // `--columns` may name leaves of struct columns, e.g. `metrics.cpu.p99`.
+            nested_columns: self
+                .initial_columns
+                .iter()
+                .flatten()
+                .filter(|c| c.contains('.'))
+                .cloned()
+                .collect(),
+            filter: filter.predicate,
+            snapshot_id: self.selected_snapshot_id,
+            limit: self.limit,
+            projection: self.projection.clone(),
+            sql: self.sql.clone(),
+            metadata_columns: self.metadata_columns,
+            metadata_filter: filter.metadata,
+            offset: 0,
         }
     }
 
     fn draw(&mut self, frame: &mut Frame) {
+        if layout::is_too_small(frame.area()) {
+            let message = format!(
+                "Terminal too small — resize to at least {}×{}",
+                layout::MIN_TERMINAL_WIDTH,
+                layout::MIN_TERMINAL_HEIGHT
+            );
+            let hint = Paragraph::new(message)
+                .style(Theme::status_warning())
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+            frame.render_widget(hint, frame.area());
+            return;
+        }
+
         let snap_label = self.snapshot_panel.selected_snapshot().map(|s| {
             format!(
                 "Snap: {} ({})",
                 s.snapshot_id,
-                SnapshotPanel::format_timestamp(s.timestamp_ms)
+                format_timestamp_ms(s.timestamp_ms)
             )
         });
         self.status_bar.set_highlighted_snapshot(snap_label);
@@ -120,8 +214,12 @@ impl App {
                     data_layout.filter_bar,
                     self.focus == Focus::FilterBar,
                 );
+                #[cfg(feature = "sql")]
+                if self.sql_prompt.is_input_mode() {
+                    self.sql_prompt.render(frame, data_layout.filter_bar, true);
+                }
                 self.data_view
-                    .render(frame, data_layout.table, self.focus == Focus::Left);
+                    .render(frame, data_layout.table, self.focus == Focus::Content);
             }
             Tab::Schema => self.schema_panel.render(frame, layout.content, true),
             Tab::Snapshots => self.snapshot_panel.render(frame, layout.content, true),
@@ -130,14 +228,29 @@ impl App {
             Tab::Stats => self.file_stats_panel.render(frame, layout.content, true),
         }
 
+        self.status_bar.context_hints = match self.active_tab {
+            Tab::Data => self.data_view.context_hints(),
+            Tab::Schema => self.schema_panel.context_hints(),
+            Tab::Snapshots => self.snapshot_panel.context_hints(),
+            Tab::Files => self.manifest_panel.context_hints(),
+            Tab::Properties => self.properties_panel.context_hints(),
+            Tab::Stats => self.file_stats_panel.context_hints(),
+        };
         self.status_bar.render(frame, layout.status_bar, false);
 
         self.column_selector
             .render(frame, frame.area(), self.focus == Focus::ColumnSelector);
+        self.scan_plan_popup.render(frame, frame.area(), true);
+        self.stats_popup.render(frame, frame.area(), true);
         self.help_popup.render(frame, frame.area(), true);
     }
 
     fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        // Raw mode turns Ctrl-C into a key press instead of SIGINT; quit from anywhere.
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Some(Action::Quit);
+        }
+
         if self.help_popup.visible {
             return self.help_popup.handle_key(key);
         }
@@ -146,8 +259,42 @@ impl App {
             return self.column_selector.handle_key(key);
         }
 
+        if self.scan_plan_popup.visible {
+            return self.scan_plan_popup.handle_key(key);
+        }
+
+        if self.stats_popup.visible {
+            return self.stats_popup.handle_key(key);
+        }
+
         if self.filter_bar.is_input_mode() {
-            return self.filter_bar.handle_key(key);
+            let action = self.filter_bar.handle_key(key);
+            // Esc closes the bar without an action; give focus back to the table.
+            if !self.filter_bar.is_input_mode() {
+                self.focus = Focus::Content;
+            }
+            return action;
+        }
+
+        #[cfg(feature = "sql")]
+        if self.sql_prompt.is_input_mode() {
+            let action = self.sql_prompt.handle_key(key);
+            if !self.sql_prompt.is_input_mode() {
+                self.focus = Focus::Content;
+            }
+            return action;
+        }
+
+        if self.active_tab == Tab::Snapshots && self.snapshot_panel.is_input_mode() {
+            return self.snapshot_panel.handle_key(key);
+        }
+
+        if self.active_tab == Tab::Data && self.data_view.is_input_mode() {
+            return self.data_view.handle_key(key);
+        }
+
+        if key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Some(Action::ResetView);
         }
 
         match key.code {
@@ -161,8 +308,9 @@ impl App {
             KeyCode::Char('6') => return Some(Action::SwitchTab(5)),
             KeyCode::Char('r') => return Some(Action::Reload),
             KeyCode::Char('m') => return Some(Action::IncreaseLimit),
-            KeyCode::Tab => return Some(Action::FocusNext),
-            KeyCode::BackTab => return Some(Action::FocusPrev),
+            KeyCode::Char(',') => return Some(Action::ToggleNumberGrouping),
+            KeyCode::Char(']') => return Some(Action::SwitchTab(self.active_tab.next().index())),
+            KeyCode::Char('[') => return Some(Action::SwitchTab(self.active_tab.prev().index())),
             _ => {}
         }
 
@@ -188,28 +336,19 @@ impl App {
                     return Ok(false);
                 };
                 self.active_tab = tab;
-                self.focus = Focus::Left;
+                self.focus = Focus::Content;
 
                 let needs_manifest =
                     self.manifest_panel.needs_load() || self.file_stats_panel.needs_load();
                 if (tab == Tab::Files || tab == Tab::Stats) && needs_manifest {
-                    let msg_tx = msg_tx.clone();
-                    let snap_id = self.selected_snapshot_id;
-                    tokio::spawn(async move {
-                        let _ =
-                            msg_tx.send(AppMessage::LoadingStarted("Loading manifests...".into()));
-                        load_manifests(&msg_tx, snap_id).await;
-                        let _ = msg_tx.send(AppMessage::LoadingFinished);
-                    });
+                    spawn_load_manifests(
+                        msg_tx.clone(),
+                        self.manifest_cache.clone(),
+                        self.selected_snapshot_id,
+                        self.max_concurrency,
+                    );
                 }
             }
-            Action::FocusNext | Action::FocusPrev => {
-                self.focus = match self.focus {
-                    Focus::Left => Focus::Right,
-                    Focus::Right => Focus::Left,
-                    _ => Focus::Left,
-                };
-            }
             Action::ToggleHelp => {
                 self.help_popup.toggle();
             }
@@ -217,13 +356,30 @@ impl App {
                 self.focus = Focus::FilterBar;
                 self.filter_bar.start_editing();
             }
+            #[cfg(feature = "sql")]
+            Action::FocusSql => {
+                self.focus = Focus::SqlPrompt;
+                self.sql_prompt.start_editing();
+            }
+            #[cfg(feature = "sql")]
+            Action::SubmitSql(query) => {
+                self.focus = Focus::Content;
+                self.limit = Some(self.page_size);
+                self.sql = query;
+                let predicate = self
+                    .filter_bar
+                    .applied_filter()
+                    .and_then(|f| filter::parse_scan_filter(f).ok());
+                // The query picks its own columns; without one, go back to all of them.
+                self.rescan(msg_tx, self.scan_request(predicate, vec![]));
+            }
             Action::ToggleColumnSelector => {
                 if self.column_selector.visible {
                     self.column_selector.hide();
-                    self.focus = Focus::Left;
+                    self.focus = Focus::Content;
                     let enabled = self.column_selector.enabled_columns();
-                    self.data_view.set_visible_columns(enabled.clone());
-                    self.status_bar.visible_columns = enabled.len();
+                    self.data_view.set_visible_columns(enabled);
+                    self.update_column_counts();
                 } else {
                     self.column_selector.show();
                     self.focus = Focus::ColumnSelector;
@@ -231,26 +387,23 @@ impl App {
             }
             Action::ToggleColumn(_) => {
                 let enabled = self.column_selector.enabled_columns();
-                self.data_view.set_visible_columns(enabled.clone());
-                self.status_bar.visible_columns = enabled.len();
+                self.data_view.set_visible_columns(enabled);
+                self.update_column_counts();
             }
             Action::SubmitFilter(filter_text) => {
-                self.focus = Focus::Left;
+                self.focus = Focus::Content;
                 self.limit = Some(self.page_size);
 
                 if filter_text.is_empty() {
                     self.status_bar.filter_active = false;
-                    spawn_rescan(
-                        msg_tx.clone(),
-                        None,
-                        self.data_view.visible_columns().to_vec(),
-                        self.selected_snapshot_id,
-                        self.limit,
+                    self.rescan(
+                        msg_tx,
+                        self.scan_request(None, self.data_view.visible_columns().to_vec()),
                     );
                     return Ok(false);
                 }
 
-                let predicate = match filter::parse_filter(&filter_text) {
+                let predicate = match filter::parse_scan_filter(&filter_text) {
                     Ok(p) => p,
                     Err(e) => {
                         let _ = msg_tx.send(AppMessage::Error(format!("Filter error: {}", e)));
@@ -258,59 +411,110 @@ impl App {
                     }
                 };
                 self.status_bar.filter_active = true;
-                spawn_rescan(
-                    msg_tx.clone(),
-                    Some(predicate),
-                    self.data_view.visible_columns().to_vec(),
-                    self.selected_snapshot_id,
-                    self.limit,
+                self.rescan(
+                    msg_tx,
+                    self.scan_request(Some(predicate), self.data_view.visible_columns().to_vec()),
                 );
             }
-            Action::SelectSnapshot(snapshot_id) => {
-                let is_current = self.current_snapshot_id == Some(snapshot_id);
-                self.selected_snapshot_id = if is_current { None } else { Some(snapshot_id) };
+            Action::SelectSnapshot(snapshot_id) => self.select_snapshot(snapshot_id, msg_tx),
+            Action::ResetView => {
+                self.filter_bar.clear();
+                #[cfg(feature = "sql")]
+                {
+                    self.sql = None;
+                    self.sql_prompt.set_applied(None);
+                }
+                self.status_bar.filter_active = false;
+                self.initial_columns = None;
+                self.projection = None;
+                let all_cols = self.data_view.all_columns().to_vec();
+                self.column_selector
+                    .set_columns(all_cols.clone(), &all_cols);
+                self.data_view.set_visible_columns(all_cols);
+                self.update_column_counts();
                 self.limit = Some(self.page_size);
-
-                self.snapshot_panel
-                    .set_viewed_snapshot(self.selected_snapshot_id);
-                self.properties_panel
-                    .set_viewed_snapshot(self.selected_snapshot_id);
-                self.status_bar
-                    .set_snapshot_view(self.selected_snapshot_id, self.current_snapshot_id);
-
-                let schema_id = self
-                    .selected_snapshot_id
-                    .and_then(|sid| self.snapshot_panel.schema_id_for_snapshot(sid));
-                self.schema_panel.set_viewed_schema(schema_id);
-
+                match (self.selected_snapshot_id, self.current_snapshot_id) {
+                    (Some(_), Some(current)) => self.select_snapshot(current, msg_tx),
+                    _ => self.rescan(msg_tx, self.scan_request(None, vec![])),
+                }
+                self.status_bar.info_message = Some("View reset".into());
+            }
+            Action::RetryManifest(None) => {
                 self.manifest_panel.invalidate();
                 self.file_stats_panel.invalidate();
-                if self.active_tab == Tab::Files || self.active_tab == Tab::Stats {
-                    let msg_tx = msg_tx.clone();
-                    let snap_id = self.selected_snapshot_id;
-                    tokio::spawn(async move {
-                        let _ =
-                            msg_tx.send(AppMessage::LoadingStarted("Loading manifests...".into()));
-                        load_manifests(&msg_tx, snap_id).await;
-                        let _ = msg_tx.send(AppMessage::LoadingFinished);
-                    });
-                }
-
-                let predicate = self
-                    .filter_bar
-                    .applied_filter()
-                    .and_then(|f| filter::parse_filter(f).ok());
-                spawn_rescan(
+                spawn_load_manifests(
                     msg_tx.clone(),
-                    predicate,
-                    vec![],
+                    self.manifest_cache.clone(),
                     self.selected_snapshot_id,
-                    self.limit,
+                    self.max_concurrency,
                 );
-                if let Some(handle) = TABLE_HANDLE.lock().unwrap().clone() {
-                    spawn_count_rows(msg_tx.clone(), handle, self.selected_snapshot_id);
+            }
+            Action::RetryManifest(Some(index)) => {
+                let msg_tx = msg_tx.clone();
+                let cache = self.manifest_cache.clone();
+                let snap_id = self.selected_snapshot_id;
+                tokio::spawn(async move {
+                    let _ = msg_tx.send(AppMessage::LoadingStarted(
+                        LoadTask::Manifests,
+                        "Retrying manifest...".into(),
+                    ));
+                    retry_manifest(&msg_tx, &cache, snap_id, index).await;
+                    let _ = msg_tx.send(AppMessage::LoadingFinished(LoadTask::Manifests));
+                });
+            }
+            Action::ToggleNumberGrouping => {
+                self.group_numbers = !self.group_numbers;
+                self.data_view.set_group_numbers(self.group_numbers);
+                self.manifest_panel.set_group_numbers(self.group_numbers);
+                self.status_bar.group_numbers = self.group_numbers;
+            }
+            Action::ExplainScan => {
+                let predicate = self
+                    .filter_bar
+                    .applied_filter()
+                    .and_then(|f| filter::parse_scan_filter(f).ok());
+                spawn_explain(msg_tx.clone(), self.scan_request(predicate, vec![]));
+            }
+            Action::ShowColumnStats => {
+                if let Some(column) = self
+                    .data_view
+                    .focused_column()
+                    .filter(|c| self.data_view.is_redacted(c))
+                {
+                    self.status_bar.error_message =
+                        Some(format!("{} is redacted; press * to show it", column));
+                } else if let Some(column) = self.data_view.focused_column() {
+                    match arrow_convert::column_stats(
+                        self.data_view.batches(),
+                        column,
+                        HISTOGRAM_BUCKETS,
+                    ) {
+                        Ok(stats) => self.stats_popup.show(column.to_string(), stats),
+                        Err(e) => self.status_bar.error_message = Some(format!("{:#}", e)),
+                    }
                 }
             }
+            Action::CopyToClipboard { text, label } => match clipboard::copy(&text) {
+                Ok(()) => self.status_bar.info_message = Some(format!("Copied {}", label)),
+                Err(e) => self.status_bar.error_message = Some(format!("{:#}", e)),
+            },
+            Action::ExportFiles => {
+                let rows = self.manifest_panel.file_inventory();
+                let snapshot_id = self.selected_snapshot_id.or(self.current_snapshot_id);
+                let path = export::csv_path("files", snapshot_id);
+                match export::write_csv(&path, &FILE_INVENTORY_HEADER, &rows) {
+                    Ok(()) => {
+                        self.status_bar.info_message = Some(format!(
+                            "Exported {} files to {}",
+                            rows.len(),
+                            path.display()
+                        ))
+                    }
+                    Err(e) => self.status_bar.error_message = Some(format!("{:#}", e)),
+                }
+            }
+            // Only produced by the recent tables picker, which runs before the app.
+            Action::OpenRecent(_) | Action::RemoveRecent(_) => {}
             Action::IncreaseLimit => {
                 if !self.has_more {
                     return Ok(false);
@@ -320,33 +524,71 @@ impl App {
                 let predicate = self
                     .filter_bar
                     .applied_filter()
-                    .and_then(|f| filter::parse_filter(f).ok());
-                spawn_rescan(
-                    msg_tx.clone(),
-                    predicate,
-                    self.data_view.visible_columns().to_vec(),
-                    self.selected_snapshot_id,
-                    self.limit,
+                    .and_then(|f| filter::parse_scan_filter(f).ok());
+                self.rescan(
+                    msg_tx,
+                    self.scan_request(predicate, self.data_view.visible_columns().to_vec()),
                 );
             }
             Action::Reload => {
+                self.manifest_cache.clear();
+                self.manifest_panel.invalidate();
+                self.file_stats_panel.invalidate();
+                if self.active_tab == Tab::Files || self.active_tab == Tab::Stats {
+                    spawn_load_manifests(
+                        msg_tx.clone(),
+                        self.manifest_cache.clone(),
+                        self.selected_snapshot_id,
+                        self.max_concurrency,
+                    );
+                }
                 let predicate = self
                     .filter_bar
                     .applied_filter()
-                    .and_then(|f| filter::parse_filter(f).ok());
-                spawn_rescan(
-                    msg_tx.clone(),
-                    predicate,
-                    self.data_view.visible_columns().to_vec(),
-                    self.selected_snapshot_id,
-                    self.limit,
+                    .and_then(|f| filter::parse_scan_filter(f).ok());
+                self.rescan(
+                    msg_tx,
+                    self.scan_request(predicate, self.data_view.visible_columns().to_vec()),
                 );
             }
         }
         Ok(false)
     }
 
+    /// Pick up a table version found by `--watch`: rescan and recount the viewed snapshot.
+    async fn reload_table(&mut self, msg_tx: &mpsc::UnboundedSender<AppMessage>) -> Result<()> {
+        self.handle_action(Action::Reload, msg_tx).await?;
+        if let Some(handle) = TABLE_HANDLE.lock().unwrap().clone() {
+            spawn_count_rows(
+                msg_tx.clone(),
+                handle,
+                self.selected_snapshot_id,
+                self.max_concurrency,
+            );
+        }
+        Ok(())
+    }
+
+    /// Name the snapshot shown in the titles of the tabs that follow time travel.
+    fn update_snapshot_labels(&mut self) {
+        let label = ui::snapshot_label(self.selected_snapshot_id, self.current_snapshot_id);
+        self.data_view.set_snapshot_label(label.clone());
+        self.schema_panel.set_snapshot_label(label.clone());
+        self.manifest_panel.set_snapshot_label(label.clone());
+        self.file_stats_panel.set_snapshot_label(label);
+    }
+
     fn handle_message(&mut self, msg: &AppMessage) {
+        if let AppMessage::Scan {
+            generation,
+            message,
+        } = msg
+        {
+            if *generation == self.scan_generation {
+                self.handle_message(message);
+            }
+            return;
+        }
         self.data_view.handle_message(msg);
         self.schema_panel.handle_message(msg);
         self.snapshot_panel.handle_message(msg);
@@ -354,9 +596,16 @@ impl App {
         self.file_stats_panel.handle_message(msg);
         self.properties_panel.handle_message(msg);
         self.status_bar.handle_message(msg);
+        self.scan_plan_popup.handle_message(msg);
 
         if let AppMessage::MetadataReady(metadata) = msg {
             self.current_snapshot_id = metadata.current_snapshot_id;
+            self.update_snapshot_labels();
+        }
+
+        if let AppMessage::RefResolved(snapshot_id) = msg {
+            self.snapshot_panel.select_snapshot_id(*snapshot_id);
+            self.view_snapshot(*snapshot_id);
         }
 
         if let AppMessage::DataReady {
@@ -376,19 +625,96 @@ impl App {
             };
             self.column_selector
                 .set_columns(all_cols.clone(), &vis_cols);
-            self.status_bar.visible_columns = vis_cols.len();
+            self.filter_bar.set_columns(all_cols.clone());
             self.status_bar.total_columns = all_cols.len();
 
             if self.initial_columns.is_some() {
                 self.data_view.set_visible_columns(vis_cols);
             }
+            self.update_column_counts();
         }
     }
+
+    /// View the data and metadata as of `snapshot_id`, or the table's current state when it
+    /// is the current snapshot, reloading what depends on the snapshot.
+    fn select_snapshot(&mut self, snapshot_id: i64, msg_tx: &mpsc::UnboundedSender<AppMessage>) {
+        self.view_snapshot(snapshot_id);
+        self.limit = Some(self.page_size);
+
+        self.manifest_panel.invalidate();
+        self.file_stats_panel.invalidate();
+        if self.active_tab == Tab::Files || self.active_tab == Tab::Stats {
+            spawn_load_manifests(
+                msg_tx.clone(),
+                self.manifest_cache.clone(),
+                self.selected_snapshot_id,
+                self.max_concurrency,
+            );
+        }
+
+        let predicate = self
+            .filter_bar
+            .applied_filter()
+            .and_then(|f| filter::parse_scan_filter(f).ok());
+        self.rescan(msg_tx, self.scan_request(predicate, vec![]));
+        if let Some(handle) = TABLE_HANDLE.lock().unwrap().clone() {
+            spawn_count_rows(
+                msg_tx.clone(),
+                handle,
+                self.selected_snapshot_id,
+                self.max_concurrency,
+            );
+        }
+    }
+
+    /// Point the panels and labels at `snapshot_id`, or back at the current state when it is
+    /// the current snapshot, without reloading anything.
+    fn view_snapshot(&mut self, snapshot_id: i64) {
+        let is_current = self.current_snapshot_id == Some(snapshot_id);
+        self.selected_snapshot_id = if is_current { None } else { Some(snapshot_id) };
+
+        self.snapshot_panel
+            .set_viewed_snapshot(self.selected_snapshot_id);
+        self.properties_panel
+            .set_viewed_snapshot(self.selected_snapshot_id);
+        self.update_snapshot_labels();
+        let selected_ts = self
+            .selected_snapshot_id
+            .and_then(|sid| self.snapshot_panel.snapshot(sid))
+            .map(|s| s.timestamp_ms);
+        self.status_bar.set_snapshot_view(
+            self.selected_snapshot_id,
+            self.current_snapshot_id,
+            selected_ts,
+        );
+
+        let schema_id = self
+            .selected_snapshot_id
+            .and_then(|sid| self.snapshot_panel.schema_id_for_snapshot(sid));
+        self.schema_panel.set_viewed_schema(schema_id);
+    }
+
+    /// Shown and selected-but-absent column counts, e.g. after time traveling to a snapshot
+    /// from before a selected column was added.
+    fn update_column_counts(&mut self) {
+        let missing = self.data_view.missing_columns().len();
+        self.status_bar.visible_columns = self.data_view.visible_columns().len() - missing;
+        self.status_bar.missing_columns = missing;
+    }
 }
 
 // --- Terminal setup ---
 
 pub async fn run(cli: Cli) -> Result<()> {
+    if let Some(command) = &cli.command {
+        if let Some(filter) = &command.view().explain_json {
+            return explain_json(command, filter).await;
+        }
+        if command.view().count_only {
+            return count_only(command).await;
+        }
+    }
+
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         let _ = disable_raw_mode();
@@ -403,7 +729,17 @@ pub async fn run(cli: Cli) -> Result<()> {
     let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run_app(&mut terminal, cli).await;
+    let result = match cli.command {
+        Some(command) => run_app(&mut terminal, command, cli.profile, cli.verbose).await,
+        None if cli.theme_preview => run_theme_preview(&mut terminal).await,
+        None => match run_recent_picker(&mut terminal).await {
+            Ok(Some(recent)) => {
+                reopen_recent(&mut terminal, &recent, cli.profile, cli.verbose).await
+            }
+            Ok(None) => Ok(()),
+            Err(e) => Err(e),
+        },
+    };
 
     disable_raw_mode()?;
     io::stdout().execute(LeaveAlternateScreen)?;
@@ -415,11 +751,19 @@ pub async fn run(cli: Cli) -> Result<()> {
 
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
-    cli: Cli,
+    command: Command,
+    profile: Option<String>,
+    verbose: bool,
 ) -> Result<()> {
     let (msg_tx, mut msg_rx) = mpsc::unbounded_channel::<AppMessage>();
-
-    let (initial_columns, limit, no_limit) = match &cli.command {
+    // Remembered once the table has loaded, so typos don't end up in the picker.
+    let mut recent = Some(RecentTable::from_command(
+        &command,
+        profile,
+        chrono::Utc::now().timestamp_millis(),
+    ));
+
+    let (initial_columns, limit, no_limit) = match &command {
         Command::Open {
             columns,
             limit,
@@ -433,20 +777,99 @@ async fn run_app(
             ..
         } => (columns.clone(), *limit, *no_limit),
     };
+    let view = command.view();
+    let tail = view.tail;
+    let limit = view.head.or(tail).or(limit);
+    // `--limit 0` is shorthand for `--metadata-only`; a later reload loads a default page.
+    let metadata_only = view.metadata_only || limit == Some(0);
+    let limit = limit.filter(|&n| n > 0);
+    let initial_columns = match &command.view().columns_file {
+        Some(path) => Some(cli::read_columns_file(path)?),
+        None => initial_columns,
+    };
+
+    let projection = command
+        .view()
+        .select
+        .as_deref()
+        .map(projection::parse_projection)
+        .transpose()
+        .context("invalid --select")?;
+
+    let initial_filter = command
+        .view()
+        .filter
+        .as_deref()
+        .map(filter::parse_scan_filter)
+        .transpose()
+        .context("invalid --filter")?;
 
     let effective = cli::effective_limit(limit, no_limit);
     let page_size = limit.unwrap_or(cli::DEFAULT_PAGE_SIZE);
     let mut app = App::new(initial_columns, effective, page_size);
+    app.projection = projection;
+    app.manifest_cache = ManifestCache::new(command.view().manifest_cache_size);
+    app.max_concurrency = command.view().max_concurrency;
+    app.metadata_columns = command.view().metadata_columns;
+    app.status_bar.show_timings = verbose;
+    app.data_view
+        .set_null_string(command.view().null_string.clone());
+    app.data_view
+        .set_truncation_indicator(command.view().truncation_indicator.clone());
+    app.data_view
+        .set_stripe_color(command.view().stripe_color());
+    app.data_view.set_binary_format(command.view().binary_as);
+    app.data_view.set_bool_style(command.view().bool_style);
+    app.data_view
+        .set_redacted_columns(command.view().redact.clone());
+    app.data_view
+        .set_column_widths(command.view().column_width.clone());
+    app.properties_panel
+        .set_hide_empty(command.view().hide_empty);
+    app.properties_panel
+        .set_stale_after_hours(command.view().stale_after);
+    app.snapshot_panel
+        .set_time_range(command.view().since, command.view().until);
+    app.data_view.set_metadata_only(metadata_only);
+    #[cfg(feature = "sql")]
+    {
+        app.sql = command.view().sql.clone();
+        app.sql_prompt.set_applied(app.sql.clone());
+    }
 
-    spawn_initial_load(msg_tx.clone(), cli.command, effective);
+    app.active_tab = command.view().tab;
+    let manifest_cache =
+        matches!(app.active_tab, Tab::Files | Tab::Stats).then(|| app.manifest_cache.clone());
+
+    if let Some(secs) = command.watch() {
+        spawn_version_watch(msg_tx.clone(), command.clone(), Duration::from_secs(secs));
+    }
+
+    if let Some(text) = &command.view().filter {
+        app.filter_bar.set_applied_filter(text.trim().to_string());
+        app.status_bar.filter_active = true;
+    }
+    let initial_request = app.scan_request(initial_filter, vec![]);
+    spawn_initial_load(
+        msg_tx.clone(),
+        command,
+        initial_request,
+        manifest_cache,
+        app.max_concurrency,
+        tail,
+        metadata_only,
+    );
 
     let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Event>();
     spawn_event_reader(event_tx);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
 
     loop {
         terminal.draw(|frame| app.draw(frame))?;
 
         tokio::select! {
+            _ = &mut shutdown => return Ok(()),
             Some(event) = event_rx.recv() => {
                 let Some(key) = to_key_event(&event) else { continue };
                 let Some(action) = app.handle_key(key) else { continue };
@@ -455,41 +878,253 @@ async fn run_app(
                 }
             }
             Some(msg) = msg_rx.recv() => {
+                if matches!(msg, AppMessage::MetadataReady(_)) {
+                    if let Some(table) = recent.take() {
+                        if let Err(e) = recents::record(table) {
+                            tracing::warn!("cannot save recent tables: {:#}", e);
+                        }
+                    }
+                }
+                if let AppMessage::StartupFailed(err) = &msg {
+                    bail!("{}", err);
+                }
                 app.handle_message(&msg);
+                if let Some(request) = app.manifest_panel.take_diagnostics_request() {
+                    spawn_file_diagnostics(msg_tx.clone(), request);
+                }
+                if matches!(msg, AppMessage::TableAdvanced { .. }) {
+                    app.reload_table(&msg_tx).await?;
+                }
+            }
+        }
+    }
+}
+
+/// Let the user pick a recently opened table; `None` when they quit instead.
+async fn run_recent_picker(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<Option<RecentTable>> {
+    let store = recents::store_path();
+    let entries = match &store {
+        Some(path) => recents::load(path)?,
+        None => vec![],
+    };
+    let mut picker = RecentPicker::new(entries, chrono::Utc::now().timestamp_millis());
+
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Event>();
+    spawn_event_reader(event_tx);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        terminal.draw(|frame| picker.render(frame, frame.area(), true))?;
+        tokio::select! {
+            _ = &mut shutdown => return Ok(None),
+            Some(event) = event_rx.recv() => {
+                let Some(key) = to_key_event(&event) else { continue };
+                if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                    return Ok(None);
+                }
+                match picker.handle_key(key) {
+                    Some(Action::Quit) => return Ok(None),
+                    Some(Action::OpenRecent(index)) => return Ok(picker.entry(index).cloned()),
+                    Some(Action::RemoveRecent(index)) => {
+                        picker.remove(index);
+                        if let Some(path) = &store {
+                            recents::save(path, picker.entries())?;
+                        }
+                    }
+                    _ => {}
+                }
             }
         }
     }
 }
 
+/// Open a table picked from the recent list, with the current config file settings. A
+/// profile given on the command line wins over the one the table was last opened with.
+async fn reopen_recent(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    recent: &RecentTable,
+    profile: Option<String>,
+    verbose: bool,
+) -> Result<()> {
+    let cli = Cli::parse_configured(recent.args(profile.as_deref()))
+        .with_context(|| format!("cannot reopen {}", recent.label()))?;
+    let command = cli.command.context("recent table has no table source")?;
+    run_app(terminal, command, cli.profile, verbose).await
+}
+
+/// Show the theme sample until a key is pressed.
+async fn run_theme_preview(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<()> {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel::<Event>();
+    spawn_event_reader(event_tx);
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        terminal.draw(theme_preview::render)?;
+        tokio::select! {
+            _ = &mut shutdown => return Ok(()),
+            event = event_rx.recv() => match event {
+                Some(event) if to_key_event(&event).is_some() => return Ok(()),
+                Some(_) => continue,
+                None => return Ok(()),
+            },
+        }
+    }
+}
+
+/// Resolves on SIGINT or SIGTERM. The event loops return on it so `run` can leave raw mode
+/// and the alternate screen instead of the process dying with the terminal still in them.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match (
+            signal(SignalKind::interrupt()),
+            signal(SignalKind::terminate()),
+        ) {
+            (Ok(mut interrupt), Ok(mut terminate)) => {
+                tokio::select! {
+                    _ = interrupt.recv() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            _ => std::future::pending().await,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/// Plan a scan for `filter` and print the plan as JSON, for scripted checks of file pruning.
+async fn explain_json(command: &Command, filter: &str) -> Result<()> {
+    let handle = load_table(command).await?;
+    let snapshot_id = match &command.view().ref_name {
+        Some(name) => Some(handle.ref_snapshot_id(name)?),
+        None => None,
+    };
+    let predicate = match command.view().filter.as_deref() {
+        Some(initial) if filter.trim().is_empty() => {
+            Some(filter::parse_scan_filter(initial).context("invalid --filter")?)
+        }
+        _ if filter.trim().is_empty() => None,
+        _ => Some(filter::parse_scan_filter(filter).context("invalid --explain-json filter")?),
+    };
+    let predicate = predicate.unwrap_or_default();
+    let request = ScanRequest {
+        filter: predicate.predicate,
+        metadata_filter: predicate.metadata,
+        snapshot_id,
+        ..ScanRequest::default()
+    };
+    let plan = plan_scan(&handle, &request).await?;
+    println!("{}", serde_json::to_string_pretty(&plan)?);
+    Ok(())
+}
+
+/// Print the row and data file counts of the requested snapshot as one line, for scripts.
+async fn count_only(command: &Command) -> Result<()> {
+    let view = command.view();
+    let handle = load_table(command).await?;
+    let snapshot_id = match (view.snapshot, view.as_of, &view.ref_name) {
+        (Some(id), _, _) => id,
+        (None, Some(ts), _) => handle.snapshot_as_of(ts)?,
+        (None, None, Some(name)) => handle.ref_snapshot_id(name)?,
+        (None, None, None) => handle
+            .table
+            .metadata()
+            .current_snapshot_id()
+            .context("table has no snapshot")?,
+    };
+    let counts = handle
+        .count(Some(snapshot_id), view.max_concurrency)
+        .await?;
+    println!("{}", count_line(&counts, snapshot_id));
+    Ok(())
+}
+
+/// The `--count-only` output. With delete files the row count is an upper bound, and says so.
+fn count_line(counts: &TableCounts, snapshot_id: i64) -> String {
+    let rows = if counts.delete_files > 0 {
+        format!(
+            "at most {} rows ({} delete files not applied)",
+            counts.rows, counts.delete_files
+        )
+    } else {
+        format!("{} rows", counts.rows)
+    };
+    format!(
+        "{}, {} files at snapshot {}",
+        rows, counts.files, snapshot_id
+    )
+}
+
 // --- Background tasks ---
 
+async fn load_table(command: &Command) -> Result<TableHandle> {
+    match command {
+        Command::Open { path, storage, .. } => load_direct(path, storage).await,
+        Command::Catalog {
+            uri,
+            table,
+            storage,
+            catalog,
+            ..
+        } => {
+            let connected = CATALOG_TABLE.lock().unwrap().clone();
+            if let Some(catalog_table) = connected {
+                match catalog_table.load().await {
+                    Ok(handle) => return Ok(handle),
+                    // The connection may have gone stale, e.g. an expired token; connect again.
+                    Err(e) => {
+                        tracing::warn!("catalog load failed, reconnecting: {:#}", e);
+                        CATALOG_TABLE.lock().unwrap().take();
+                    }
+                }
+            }
+            let catalog_table = CatalogTable::connect(uri, table, storage, catalog).await?;
+            CATALOG_TABLE.lock().unwrap().replace(catalog_table.clone());
+            catalog_table.load().await
+        }
+    }
+}
+
+/// Load the table, then scan and count it. With `manifest_cache` set, manifests are loaded
+/// too, for a startup tab that shows them. With `tail`, the table is counted first and the
+/// scan starts that many rows before the end. With `metadata_only`, the scan is skipped and
+/// left to an explicit reload. With `--ref`, all of it reads the branch or tag's snapshot.
 fn spawn_initial_load(
     msg_tx: mpsc::UnboundedSender<AppMessage>,
     command: Command,
-    limit: Option<usize>,
+    mut scan_request: ScanRequest,
+    manifest_cache: Option<ManifestCache>,
+    max_concurrency: usize,
+    tail: Option<usize>,
+    metadata_only: bool,
 ) {
     tokio::spawn(async move {
-        let _ = msg_tx.send(AppMessage::LoadingStarted("Loading table...".into()));
-
-        let result = match command {
-            Command::Open {
-                ref path,
-                ref storage,
-                ..
-            } => load_direct(path, storage).await,
-            Command::Catalog {
-                ref uri,
-                ref table,
-                ref storage,
-                ..
-            } => load_from_catalog(uri, table, storage).await,
-        };
+        let _ = msg_tx.send(AppMessage::LoadingStarted(
+            LoadTask::Table,
+            "Loading table...".into(),
+        ));
+
+        let started = Instant::now();
+        let result = load_table(&command).await;
 
         let handle = match result {
             Ok(h) => h,
             Err(e) => {
-                let _ = msg_tx.send(AppMessage::Error(format!("Load error: {}", e)));
-                let _ = msg_tx.send(AppMessage::LoadingFinished);
+                tracing::error!("table load failed: {:#}", e);
+                let _ = msg_tx.send(AppMessage::Error(format!("Load error: {:#}", e)));
+                let _ = msg_tx.send(AppMessage::LoadingFinished(LoadTask::Table));
                 return;
             }
         };
@@ -497,80 +1132,280 @@ fn spawn_initial_load(
         match handle.extract_metadata() {
             Ok(metadata) => {
                 let _ = msg_tx.send(AppMessage::MetadataReady(Box::new(metadata)));
+                send_arrow_types(&msg_tx, &handle);
+                let _ = msg_tx.send(AppMessage::PhaseTimed(LoadTask::Table, started.elapsed()));
             }
             Err(e) => {
-                let _ = msg_tx.send(AppMessage::Error(format!("Metadata error: {}", e)));
+                let _ = msg_tx.send(AppMessage::Error(format!("Metadata error: {:#}", e)));
             }
         }
 
-        let _ = msg_tx.send(AppMessage::LoadingStarted("Scanning data...".into()));
-        let scan_request = ScanRequest {
-            limit,
-            ..Default::default()
-        };
-        match execute_scan(&handle, &scan_request).await {
-            Ok(result) => {
-                let total_rows = total_row_count(&result.batches);
-                let _ = msg_tx.send(AppMessage::DataReady {
-                    batches: result.batches,
-                    total_rows,
-                    has_more: result.has_more,
-                });
+        if let Some(name) = &command.view().ref_name {
+            match handle.ref_snapshot_id(name) {
+                Ok(snapshot_id) => {
+                    scan_request.snapshot_id = Some(snapshot_id);
+                    let _ = msg_tx.send(AppMessage::RefResolved(snapshot_id));
+                }
+                Err(e) => {
+                    let _ = msg_tx.send(AppMessage::StartupFailed(format!("--ref: {:#}", e)));
+                    return;
+                }
             }
-            Err(e) => {
-                let _ = msg_tx.send(AppMessage::Error(format!("Scan error: {}", e)));
+        }
+
+        let _ = msg_tx.send(AppMessage::LoadingFinished(LoadTask::Table));
+
+        let mut counted = false;
+        if !metadata_only {
+            if let Some(n) = tail {
+                match count_rows_for_tail(&handle, scan_request.snapshot_id, max_concurrency).await
+                {
+                    Ok(total) => {
+                        scan_request.offset = total.saturating_sub(n);
+                        let _ = msg_tx.send(AppMessage::TotalRowCount(total));
+                        counted = true;
+                    }
+                    Err(e) => {
+                        let _ = msg_tx.send(AppMessage::Warning(format!(
+                            "cannot locate the last rows, showing the first: {:#}",
+                            e
+                        )));
+                    }
+                }
             }
+
+            // The app's first scan, unless the user started another one meanwhile.
+            let scan = ScanSender {
+                msg_tx: msg_tx.clone(),
+                generation: 0,
+            };
+            scan.send(AppMessage::LoadingStarted(
+                LoadTask::Data,
+                "Scanning data...".into(),
+            ));
+            let started = Instant::now();
+            send_scan_result(&scan, execute_scan(&handle, &scan_request).await, started);
+            scan.send(AppMessage::LoadingFinished(LoadTask::Data));
         }
-        let _ = msg_tx.send(AppMessage::LoadingFinished);
 
         TABLE_HANDLE.lock().unwrap().replace(handle.clone());
 
-        spawn_count_rows(msg_tx.clone(), handle, None);
+        let snapshot_id = scan_request.snapshot_id;
+        if let Some(cache) = manifest_cache {
+            spawn_load_manifests(msg_tx.clone(), cache, snapshot_id, max_concurrency);
+        }
+        if !counted {
+            spawn_count_rows(msg_tx.clone(), handle, snapshot_id, max_concurrency);
+        }
     });
 }
 
-fn spawn_rescan(
-    msg_tx: mpsc::UnboundedSender<AppMessage>,
-    predicate: Option<iceberg::expr::Predicate>,
-    columns: Vec<String>,
+/// Rows in the snapshot, for locating the last rows with `--tail`. Manifest row counts include
+/// rows removed by deletes, so when the snapshot has delete files the live rows are counted.
+async fn count_rows_for_tail(
+    handle: &TableHandle,
     snapshot_id: Option<i64>,
-    limit: Option<usize>,
+    max_concurrency: usize,
+) -> Result<usize> {
+    let counts = handle.count(snapshot_id, max_concurrency).await?;
+    if counts.delete_files == 0 {
+        return Ok(counts.rows);
+    }
+    count_live_rows(handle, snapshot_id).await
+}
+
+/// Check for new commits every `interval` and, when the table has moved past the loaded
+/// metadata file, load it again and report the new version. Tables opened by path poll
+/// their `version-hint.text` first; catalog tables ask the catalog, through the connection
+/// made at startup.
+fn spawn_version_watch(
+    msg_tx: mpsc::UnboundedSender<AppMessage>,
+    command: Command,
+    interval: Duration,
 ) {
     tokio::spawn(async move {
-        let _ = msg_tx.send(AppMessage::LoadingStarted("Scanning...".into()));
+        let hint = match &command {
+            Command::Open { path, storage, .. } => match VersionHint::new(path, storage) {
+                Ok(hint) => Some(hint),
+                Err(e) => {
+                    let _ = msg_tx.send(AppMessage::Warning(format!("--watch disabled: {:#}", e)));
+                    return;
+                }
+            },
+            Command::Catalog { .. } => None,
+        };
+
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let loaded = TABLE_HANDLE
+                .lock()
+                .unwrap()
+                .as_ref()
+                .and_then(|h| h.table.metadata_location().map(str::to_string));
+            let Some(loaded) = loaded else {
+                continue;
+            };
+
+            if let Some(hint) = &hint {
+                let latest = match hint.read().await {
+                    Ok(Some(latest)) => latest,
+                    Ok(None) => {
+                        let _ = msg_tx.send(AppMessage::Warning(
+                            "--watch disabled: the table has no metadata/version-hint.text".into(),
+                        ));
+                        return;
+                    }
+                    Err(e) => {
+                        tracing::warn!("cannot read version hint: {:#}", e);
+                        continue;
+                    }
+                };
+                if !is_newer_metadata(&latest, &loaded) {
+                    continue;
+                }
+                tracing::info!(%loaded, %latest, "version hint advanced, reloading table");
+            }
+
+            // A writer may update the hint before its metadata file is readable; the next
+            // tick retries.
+            let handle = match load_table(&command).await {
+                Ok(handle) => handle,
+                Err(e) => {
+                    tracing::warn!("reload after table update failed: {:#}", e);
+                    continue;
+                }
+            };
+            let Some(to) = handle.table.metadata_location().map(str::to_string) else {
+                continue;
+            };
+            if !is_newer_metadata(&to, &loaded) {
+                continue;
+            }
+            let metadata = match handle.extract_metadata() {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    let _ = msg_tx.send(AppMessage::Error(format!("Metadata error: {:#}", e)));
+                    continue;
+                }
+            };
+            let _ = msg_tx.send(AppMessage::MetadataReady(Box::new(metadata)));
+            send_arrow_types(&msg_tx, &handle);
+            TABLE_HANDLE.lock().unwrap().replace(handle);
+            let _ = msg_tx.send(AppMessage::TableAdvanced {
+                from: metadata_version_label(&loaded),
+                to: metadata_version_label(&to),
+            });
+        }
+    });
+}
+
+/// Sends the messages of one data scan, tagged with its generation.
+struct ScanSender {
+    msg_tx: mpsc::UnboundedSender<AppMessage>,
+    generation: u64,
+}
+
+impl ScanSender {
+    fn send(&self, message: AppMessage) {
+        let _ = self.msg_tx.send(AppMessage::Scan {
+            generation: self.generation,
+            message: Box::new(message),
+        });
+    }
+}
+
+fn spawn_rescan(msg_tx: mpsc::UnboundedSender<AppMessage>, request: ScanRequest, generation: u64) {
+    tokio::spawn(async move {
+        let scan = ScanSender { msg_tx, generation };
+        scan.send(AppMessage::LoadingStarted(
+            LoadTask::Data,
+            "Scanning...".into(),
+        ));
 
         let Some(handle) = TABLE_HANDLE.lock().unwrap().clone() else {
-            let _ = msg_tx.send(AppMessage::Error("No table loaded".into()));
-            let _ = msg_tx.send(AppMessage::LoadingFinished);
+            scan.send(AppMessage::Error("No table loaded".into()));
+            scan.send(AppMessage::LoadingFinished(LoadTask::Data));
             return;
         };
 
-        let request = ScanRequest {
-            columns: if columns.is_empty() {
-                None
-            } else {
-                Some(columns)
-            },
-            filter: predicate,
-            snapshot_id,
-            limit,
+        let started = Instant::now();
+        send_scan_result(&scan, execute_scan(&handle, &request).await, started);
+
+        scan.send(AppMessage::LoadingFinished(LoadTask::Data));
+    });
+}
+
+/// Arrow types for the Schema tab. A schema Arrow can't represent only costs that view, so
+/// the failure is logged rather than shown.
+fn send_arrow_types(msg_tx: &mpsc::UnboundedSender<AppMessage>, handle: &TableHandle) {
+    match handle.arrow_types() {
+        Ok(types) => {
+            let _ = msg_tx.send(AppMessage::ArrowTypesReady(types));
+        }
+        Err(e) => tracing::warn!("cannot map the schema to Arrow: {:#}", e),
+    }
+}
+
+fn send_scan_result(scan: &ScanSender, result: Result<ScanResult>, started: Instant) {
+    match result {
+        Ok(result) => {
+            scan.send(AppMessage::PhaseTimed(LoadTask::Data, started.elapsed()));
+            if !result.dropped_columns.is_empty() {
+                let fallback = if result.read_all_columns {
+                    "; showing all columns"
+                } else {
+                    ""
+                };
+                scan.send(AppMessage::Warning(format!(
+                    "columns not in snapshot schema: {}{}",
+                    result.dropped_columns.join(", "),
+                    fallback
+                )));
+            }
+            let total_rows = total_row_count(&result.batches);
+            scan.send(AppMessage::RowOffset(result.offset));
+            scan.send(AppMessage::DataReady {
+                batches: result.batches,
+                total_rows,
+                has_more: result.has_more,
+            });
+            if let Some(err) = result.error {
+                scan.send(AppMessage::Error(format!(
+                    "Scan stopped after {} rows: {}",
+                    total_rows, err
+                )));
+            }
+        }
+        Err(e) => {
+            tracing::error!("scan failed: {:#}", e);
+            scan.send(AppMessage::Error(format!("Scan error: {:#}", e)));
+        }
+    }
+}
+
+fn spawn_explain(msg_tx: mpsc::UnboundedSender<AppMessage>, request: ScanRequest) {
+    tokio::spawn(async move {
+        let Some(handle) = TABLE_HANDLE.lock().unwrap().clone() else {
+            let _ = msg_tx.send(AppMessage::Error("No table loaded".into()));
+            return;
         };
 
-        match execute_scan(&handle, &request).await {
-            Ok(result) => {
-                let total_rows = total_row_count(&result.batches);
-                let _ = msg_tx.send(AppMessage::DataReady {
-                    batches: result.batches,
-                    total_rows,
-                    has_more: result.has_more,
-                });
+        let _ = msg_tx.send(AppMessage::LoadingStarted(
+            LoadTask::Data,
+            "Planning scan...".into(),
+        ));
+        match plan_scan(&handle, &request).await {
+            Ok(plan) => {
+                let _ = msg_tx.send(AppMessage::ScanPlanReady(plan));
             }
             Err(e) => {
-                let _ = msg_tx.send(AppMessage::Error(format!("Scan error: {}", e)));
+                let _ = msg_tx.send(AppMessage::Error(format!("Explain error: {:#}", e)));
             }
         }
-
-        let _ = msg_tx.send(AppMessage::LoadingFinished);
+        let _ = msg_tx.send(AppMessage::LoadingFinished(LoadTask::Data));
     });
 }
 
@@ -578,105 +1413,280 @@ fn spawn_count_rows(
     msg_tx: mpsc::UnboundedSender<AppMessage>,
     handle: TableHandle,
     snapshot_id: Option<i64>,
+    max_concurrency: usize,
 ) {
     tokio::spawn(async move {
-        if let Ok(total) = handle.count_total_rows(snapshot_id).await {
-            let _ = msg_tx.send(AppMessage::TotalRowCount(total));
+        let _ = msg_tx.send(AppMessage::LoadingStarted(
+            LoadTask::Count,
+            "Counting rows...".into(),
+        ));
+        let started = Instant::now();
+        match handle.count_total_rows(snapshot_id, max_concurrency).await {
+            Ok(total) => {
+                let _ = msg_tx.send(AppMessage::TotalRowCount(total));
+                let _ = msg_tx.send(AppMessage::PhaseTimed(LoadTask::Count, started.elapsed()));
+            }
+            Err(e) => {
+                let _ = msg_tx.send(AppMessage::Error(format!("Row count error: {:#}", e)));
+            }
         }
+        let _ = msg_tx.send(AppMessage::LoadingFinished(LoadTask::Count));
     });
 }
 
-async fn load_manifests(msg_tx: &mpsc::UnboundedSender<AppMessage>, snapshot_id: Option<i64>) {
-    let handle = TABLE_HANDLE.lock().unwrap().clone();
-    let Some(handle) = handle else {
-        let _ = msg_tx.send(AppMessage::Error("No table loaded".into()));
-        return;
-    };
+fn spawn_load_manifests(
+    msg_tx: mpsc::UnboundedSender<AppMessage>,
+    cache: ManifestCache,
+    snapshot_id: Option<i64>,
+    max_concurrency: usize,
+) {
+    tokio::spawn(async move {
+        let _ = msg_tx.send(AppMessage::LoadingStarted(
+            LoadTask::Manifests,
+            "Loading manifests...".into(),
+        ));
+        load_manifests(&msg_tx, &cache, snapshot_id, max_concurrency).await;
+        let _ = msg_tx.send(AppMessage::LoadingFinished(LoadTask::Manifests));
+    });
+}
+
+/// Check the Files tab's data files for overlaps and duplicates on a blocking thread.
+fn spawn_file_diagnostics(msg_tx: mpsc::UnboundedSender<AppMessage>, request: DiagnosticsRequest) {
+    tokio::task::spawn_blocking(move || {
+        let diagnostics = diagnose(&request.groups, request.key_field);
+        let _ = msg_tx.send(AppMessage::FileDiagnosticsReady {
+            generation: request.generation,
+            diagnostics,
+        });
+    });
+}
 
+/// Load the manifest list for a snapshot, returning `Ok(None)` when the table has no snapshot.
+async fn load_manifest_list(
+    handle: &TableHandle,
+    snapshot_id: Option<i64>,
+) -> Result<Option<ManifestList>> {
     let metadata = handle.table.metadata();
     let snapshot = match snapshot_id {
         Some(id) => metadata.snapshot_by_id(id),
         None => metadata.current_snapshot(),
     };
     let Some(snapshot) = snapshot else {
-        let _ = msg_tx.send(AppMessage::ManifestsReady(vec![]));
-        let _ = msg_tx.send(AppMessage::DataFileStatsReady(vec![]));
+        return Ok(None);
+    };
+
+    let list = snapshot
+        .load_manifest_list(handle.table.file_io(), metadata)
+        .await
+        .with_context(|| format!("failed to load manifest list {}", snapshot.manifest_list()))?;
+    Ok(Some(list))
+}
+
+#[tracing::instrument(skip(msg_tx, cache))]
+async fn load_manifests(
+    msg_tx: &mpsc::UnboundedSender<AppMessage>,
+    cache: &ManifestCache,
+    snapshot_id: Option<i64>,
+    max_concurrency: usize,
+) {
+    let handle = TABLE_HANDLE.lock().unwrap().clone();
+    let Some(handle) = handle else {
+        let _ = msg_tx.send(AppMessage::Error("No table loaded".into()));
         return;
     };
 
-    let file_io = handle.table.file_io().clone();
+    let cache_key = snapshot_id.or_else(|| handle.table.metadata().current_snapshot_id());
+    if let Some(cached) = cache_key.and_then(|id| cache.get(id)) {
+        tracing::debug!("manifests served from cache");
+        let _ = msg_tx.send(AppMessage::ManifestsReady(cached.manifests));
+        let _ = msg_tx.send(AppMessage::DataFileStatsReady(cached.files));
+        return;
+    }
 
-    let manifest_list = match snapshot.load_manifest_list(&file_io, metadata).await {
-        Ok(list) => list,
+    let manifest_list = match load_manifest_list(&handle, snapshot_id).await {
+        Ok(Some(list)) => list,
+        Ok(None) => {
+            let _ = msg_tx.send(AppMessage::ManifestsReady(vec![]));
+            let _ = msg_tx.send(AppMessage::DataFileStatsReady(vec![]));
+            return;
+        }
         Err(e) => {
-            let _ = msg_tx.send(AppMessage::Error(format!(
-                "Failed to load manifest list: {}",
-                e
-            )));
+            let _ = msg_tx.send(AppMessage::ManifestListFailed(format!("{:#}", e)));
+            let _ = msg_tx.send(AppMessage::Error(format!("{:#}", e)));
             return;
         }
     };
 
+    let loaded = load_all_data_files(
+        manifest_list.entries().to_vec(),
+        handle.table.file_io().clone(),
+        max_concurrency,
+    )
+    .await;
     let mut manifest_infos = Vec::new();
     let mut grouped_files: Vec<Vec<DataFileInfo>> = Vec::new();
+    let mut failed = 0;
 
-    for mf in manifest_list.entries() {
-        manifest_infos.push(ManifestInfo {
-            path: mf.manifest_path.clone(),
-            content_type: mf.content.to_string(),
-            added_data_files_count: mf.added_files_count.map(|v| v as i32),
-            added_rows_count: mf.added_rows_count.map(|v| v as i64),
-            existing_data_files_count: mf.existing_files_count.map(|v| v as i32),
-            existing_rows_count: mf.existing_rows_count.map(|v| v as i64),
-            deleted_data_files_count: mf.deleted_files_count.map(|v| v as i32),
-            deleted_rows_count: mf.deleted_rows_count.map(|v| v as i64),
-            sequence_number: mf.sequence_number,
-            partition_spec_id: mf.partition_spec_id,
-        });
-
-        let manifest = match mf.load_manifest(&file_io).await {
-            Ok(m) => m,
+    for (mf, result) in loaded {
+        let mut info = manifest_file_to_info(&mf);
+        match result {
+            Ok(files) => grouped_files.push(files),
             Err(e) => {
-                let _ = msg_tx.send(AppMessage::Error(format!("Failed to load manifest: {}", e)));
+                tracing::warn!(path = %mf.manifest_path, "manifest failed to load: {:#}", e);
+                info.load_error = Some(format!("{:#}", e));
                 grouped_files.push(vec![]);
-                continue;
+                failed += 1;
             }
-        };
-
-        let mut files_for_manifest = Vec::new();
-        for entry in manifest.entries() {
-            if !entry.is_alive() {
-                continue;
-            }
-            let df = entry.data_file();
-            files_for_manifest.push(DataFileInfo {
-                file_path: df.file_path().to_string(),
-                file_format: format!("{:?}", df.file_format()),
-                record_count: df.record_count() as i64,
-                file_size_bytes: df.file_size_in_bytes() as i64,
-                null_value_counts: df
-                    .null_value_counts()
-                    .iter()
-                    .map(|(&k, &v)| (k, v as i64))
-                    .collect(),
-                lower_bounds: df
-                    .lower_bounds()
-                    .iter()
-                    .map(|(&k, v)| (k, v.to_string()))
-                    .collect(),
-                upper_bounds: df
-                    .upper_bounds()
-                    .iter()
-                    .map(|(&k, v)| (k, v.to_string()))
-                    .collect(),
-                partition_data: std::collections::HashMap::new(),
-            });
         }
-        grouped_files.push(files_for_manifest);
+        manifest_infos.push(info);
+    }
+
+    // Partial results are kept for retries to complete, but aren't served from the cache.
+    if let Some(id) = cache_key {
+        cache.insert(
+            id,
+            CachedManifests {
+                manifests: manifest_infos.clone(),
+                files: grouped_files.clone(),
+            },
+        );
     }
 
     let _ = msg_tx.send(AppMessage::ManifestsReady(manifest_infos));
     let _ = msg_tx.send(AppMessage::DataFileStatsReady(grouped_files));
+    if failed > 0 {
+        let _ = msg_tx.send(AppMessage::Error(format!(
+            "{} manifest(s) failed to load; select one and press R to retry",
+            failed
+        )));
+    }
+}
+
+/// Load the data files of `manifests`, up to `max_concurrency` at a time. Results come back in
+/// manifest-list order whatever order the loads finish in, so each manifest keeps its index.
+async fn load_all_data_files(
+    manifests: Vec<ManifestFile>,
+    file_io: FileIO,
+    max_concurrency: usize,
+) -> Vec<(ManifestFile, Result<Vec<DataFileInfo>>)> {
+    futures::stream::iter(manifests)
+        .map(move |mf| {
+            let file_io = file_io.clone();
+            async move {
+                let result = load_data_files(&mf, &file_io).await;
+                (mf, result)
+            }
+        })
+        .buffered(max_concurrency.max(1))
+        .collect()
+        .await
+}
+
+/// Reload a single manifest by its position in the snapshot's manifest list, updating the
+/// cached manifests of the snapshot.
+async fn retry_manifest(
+    msg_tx: &mpsc::UnboundedSender<AppMessage>,
+    cache: &ManifestCache,
+    snapshot_id: Option<i64>,
+    index: usize,
+) {
+    let handle = TABLE_HANDLE.lock().unwrap().clone();
+    let Some(handle) = handle else {
+        let _ = msg_tx.send(AppMessage::Error("No table loaded".into()));
+        return;
+    };
+
+    let manifest_list = match load_manifest_list(&handle, snapshot_id).await {
+        Ok(Some(list)) => list,
+        Ok(None) => return,
+        Err(e) => {
+            let _ = msg_tx.send(AppMessage::Error(format!("{:#}", e)));
+            return;
+        }
+    };
+    let Some(mf) = manifest_list.entries().get(index) else {
+        return;
+    };
+
+    let mut manifest = manifest_file_to_info(mf);
+    let files = match load_data_files(mf, handle.table.file_io()).await {
+        Ok(files) => files,
+        Err(e) => {
+            manifest.load_error = Some(format!("{:#}", e));
+            let _ = msg_tx.send(AppMessage::Error(format!("{:#}", e)));
+            vec![]
+        }
+    };
+    if let Some(id) = snapshot_id.or_else(|| handle.table.metadata().current_snapshot_id()) {
+        cache.update(id, index, manifest.clone(), files.clone());
+    }
+    let _ = msg_tx.send(AppMessage::ManifestReloaded {
+        index,
+        manifest,
+        files,
+    });
+}
+
+fn manifest_file_to_info(mf: &ManifestFile) -> ManifestInfo {
+    ManifestInfo {
+        path: mf.manifest_path.clone(),
+        content_type: mf.content.to_string(),
+        added_data_files_count: mf.added_files_count.map(|v| v as i32),
+        added_rows_count: mf.added_rows_count.map(|v| v as i64),
+        existing_data_files_count: mf.existing_files_count.map(|v| v as i32),
+        existing_rows_count: mf.existing_rows_count.map(|v| v as i64),
+        deleted_data_files_count: mf.deleted_files_count.map(|v| v as i32),
+        deleted_rows_count: mf.deleted_rows_count.map(|v| v as i64),
+        sequence_number: mf.sequence_number,
+        partition_spec_id: mf.partition_spec_id,
+        load_error: None,
+    }
+}
+
+/// Load the live data file entries of one manifest.
+async fn load_data_files(mf: &ManifestFile, file_io: &FileIO) -> Result<Vec<DataFileInfo>> {
+    tracing::debug!(path = %mf.manifest_path, "loading manifest");
+    let manifest = mf
+        .load_manifest(file_io)
+        .await
+        .with_context(|| format!("failed to load manifest {}", mf.manifest_path))?;
+
+    let mut files = Vec::new();
+    for entry in manifest.entries() {
+        if !entry.is_alive() {
+            continue;
+        }
+        let df = entry.data_file();
+        files.push(DataFileInfo {
+            file_path: df.file_path().to_string(),
+            file_format: format!("{:?}", df.file_format()),
+            content: format!("{:?}", df.content_type()),
+            equality_ids: df.equality_ids().unwrap_or_default(),
+            record_count: df.record_count() as i64,
+            file_size_bytes: df.file_size_in_bytes() as i64,
+            null_value_counts: df
+                .null_value_counts()
+                .iter()
+                .map(|(&k, &v)| (k, v as i64))
+                .collect(),
+            lower_bounds: df
+                .lower_bounds()
+                .iter()
+                .map(|(&k, v)| (k, v.to_string()))
+                .collect(),
+            upper_bounds: df
+                .upper_bounds()
+                .iter()
+                .map(|(&k, v)| (k, v.to_string()))
+                .collect(),
+            partition_data: std::collections::HashMap::new(),
+            entry_status: format!("{:?}", entry.status),
+            entry_snapshot_id: entry.snapshot_id,
+            sequence_number: entry.sequence_number,
+            file_sequence_number: entry.file_sequence_number,
+        });
+    }
+    Ok(files)
 }
 
 #[cfg(test)]
@@ -688,7 +1698,7 @@ mod tests {
     fn app_new_default_state() {
         let app = App::new(None, None, DEFAULT_PAGE_SIZE);
         assert_eq!(app.active_tab, Tab::Data);
-        assert_eq!(app.focus, Focus::Left);
+        assert_eq!(app.focus, Focus::Content);
         assert!(app.initial_columns.is_none());
         assert!(app.limit.is_none());
         assert_eq!(app.page_size, DEFAULT_PAGE_SIZE);
@@ -710,6 +1720,140 @@ mod tests {
         assert_eq!(app.limit, Some(500));
     }
 
+    fn screen_text(app: &mut App, width: u16, height: u16) -> String {
+        let mut terminal =
+            Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn draw_small_terminal_shows_resize_hint() {
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        let text = screen_text(&mut app, 30, 6);
+        assert!(text.contains("Terminal too small"));
+        assert!(!text.contains("Schema"));
+    }
+
+    #[tokio::test]
+    async fn properties_scroll_survives_tab_switches() {
+        use crate::model::table_info::{SchemaInfo, TableMetadata};
+        use arrow_array::{ArrayRef, Int32Array, RecordBatch};
+        use arrow_schema::{DataType, Field, Schema};
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        let (msg_tx, _msg_rx) = mpsc::unbounded_channel();
+        let metadata = TableMetadata {
+            location: "/tmp/test".into(),
+            metadata_location: None,
+            current_schema: SchemaInfo {
+                schema_id: 0,
+                fields: vec![],
+            },
+            schemas: vec![],
+            snapshots: vec![],
+            refs: vec![],
+            partition_specs: vec![],
+            default_spec_id: 0,
+            sort_orders: vec![],
+            default_sort_order_id: 0,
+            statistics: vec![],
+            partition_statistics: vec![],
+            properties: (0..40)
+                .map(|i| (format!("property.{:02}", i), "value".to_string()))
+                .collect::<HashMap<_, _>>(),
+            current_snapshot_id: None,
+            format_version: 2,
+            table_uuid: "test-uuid".into(),
+            last_updated_ms: 1700000000000,
+            last_column_id: 0,
+            last_partition_id: 999,
+        };
+        app.handle_message(&AppMessage::MetadataReady(Box::new(metadata)));
+        let fields: Vec<Field> = (0..30)
+            .map(|i| Field::new(format!("column_{}", i), DataType::Int32, false))
+            .collect();
+        let columns: Vec<ArrayRef> = (0..30)
+            .map(|i| Arc::new(Int32Array::from(vec![i, i + 1])) as ArrayRef)
+            .collect();
+        let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap();
+        app.handle_message(&AppMessage::DataReady {
+            batches: vec![batch],
+            total_rows: 2,
+            has_more: false,
+        });
+        let switch = |tab: Tab| Action::SwitchTab(tab.index());
+
+        // Data: scroll a few columns to the right.
+        let data_top = screen_text(&mut app, 240, 24);
+        for _ in 0..3 {
+            app.handle_key(KeyEvent::from(KeyCode::Char('l')));
+        }
+        let data_scrolled = screen_text(&mut app, 240, 24);
+        assert_ne!(data_scrolled, data_top);
+
+        // Properties: scroll three lines down.
+        app.handle_action(switch(Tab::Properties), &msg_tx)
+            .await
+            .unwrap();
+        let top = screen_text(&mut app, 240, 24);
+        for _ in 0..3 {
+            app.handle_key(KeyEvent::from(KeyCode::Char('j')));
+        }
+        let scrolled = screen_text(&mut app, 240, 24);
+        assert_ne!(scrolled, top);
+
+        // Data keeps its column offset.
+        app.handle_action(switch(Tab::Data), &msg_tx).await.unwrap();
+        assert_eq!(app.active_tab, Tab::Data);
+        assert_eq!(screen_text(&mut app, 240, 24), data_scrolled);
+
+        // Properties keeps its line offset.
+        app.handle_action(switch(Tab::Properties), &msg_tx)
+            .await
+            .unwrap();
+        assert_eq!(app.active_tab, Tab::Properties);
+        assert_eq!(screen_text(&mut app, 240, 24), scrolled);
+    }
+
+    #[tokio::test]
+    async fn status_bar_hints_follow_the_active_tab() {
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        let (msg_tx, _msg_rx) = mpsc::unbounded_channel();
+        let text = screen_text(&mut app, 120, 24);
+        assert!(text.contains("/:filter c:columns f:find |:jump s:stats m:more │ q:quit ?:help"));
+
+        app.handle_action(Action::SwitchTab(Tab::Properties.index()), &msg_tx)
+            .await
+            .unwrap();
+        let text = screen_text(&mut app, 120, 24);
+        assert!(text.contains("Y:copy location │ q:quit"));
+        assert!(!text.contains("/:filter"));
+
+        // Too narrow for the tab's keys: only the global ones are left.
+        let text = screen_text(&mut app, 48, 24);
+        assert!(!text.contains("Y:copy"));
+        assert!(text.contains("q:quit ?:help"));
+    }
+
+    #[test]
+    fn cancelling_filter_returns_focus_to_table() {
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        app.focus = Focus::FilterBar;
+        app.filter_bar.start_editing();
+
+        assert_eq!(app.handle_key(KeyEvent::from(KeyCode::Esc)), None);
+        assert_eq!(app.focus, Focus::Content);
+    }
+
     #[test]
     fn handle_key_quit() {
         let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
@@ -733,6 +1877,58 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn comma_toggles_number_grouping_everywhere() {
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        let (msg_tx, _msg_rx) = mpsc::unbounded_channel();
+        let action = app.handle_key(KeyEvent::from(KeyCode::Char(','))).unwrap();
+        assert_eq!(action, Action::ToggleNumberGrouping);
+
+        app.handle_action(action, &msg_tx).await.unwrap();
+        assert!(app.group_numbers);
+        assert!(app.status_bar.group_numbers);
+
+        app.handle_action(Action::ToggleNumberGrouping, &msg_tx)
+            .await
+            .unwrap();
+        assert!(!app.status_bar.group_numbers);
+    }
+
+    #[tokio::test]
+    async fn s_opens_stats_for_the_focused_column() {
+        use arrow_array::{Int32Array, RecordBatch};
+        use arrow_schema::{DataType, Field, Schema};
+        use std::sync::Arc;
+
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        let (msg_tx, _msg_rx) = mpsc::unbounded_channel();
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let batch =
+            RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(vec![1, 2, 3]))]).unwrap();
+        app.handle_message(&AppMessage::DataReady {
+            batches: vec![batch],
+            total_rows: 3,
+            has_more: false,
+        });
+
+        let action = app.handle_key(KeyEvent::from(KeyCode::Char('s'))).unwrap();
+        assert_eq!(action, Action::ShowColumnStats);
+        app.handle_action(action, &msg_tx).await.unwrap();
+        assert!(app.stats_popup.visible);
+
+        // The popup takes keys until it's closed.
+        assert_eq!(app.handle_key(KeyEvent::from(KeyCode::Char('q'))), None);
+        assert!(!app.stats_popup.visible);
+    }
+
+    #[test]
+    fn ctrl_c_quits_even_with_a_popup_open() {
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        app.help_popup.visible = true;
+        let key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(app.handle_key(key), Some(Action::Quit));
+    }
+
     #[test]
     fn handle_key_reload() {
         let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
@@ -748,18 +1944,55 @@ mod tests {
     }
 
     #[test]
-    fn handle_key_focus_next_prev() {
+    fn handle_key_brackets_cycle_tabs() {
         let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
         assert_eq!(
-            app.handle_key(KeyEvent::from(KeyCode::Tab)),
-            Some(Action::FocusNext)
+            app.handle_key(KeyEvent::from(KeyCode::Char(']'))),
+            Some(Action::SwitchTab(1))
         );
         assert_eq!(
-            app.handle_key(KeyEvent::from(KeyCode::BackTab)),
-            Some(Action::FocusPrev)
+            app.handle_key(KeyEvent::from(KeyCode::Char('['))),
+            Some(Action::SwitchTab(5))
         );
     }
 
+    #[test]
+    fn handle_key_tab_is_delegated_to_component() {
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        app.active_tab = Tab::Schema;
+        assert_eq!(app.handle_key(KeyEvent::from(KeyCode::Tab)), None);
+        assert_eq!(app.focus, Focus::Content);
+    }
+
+    #[test]
+    fn handle_key_goto_prompt_captures_global_keys() {
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        app.active_tab = Tab::Snapshots;
+        assert_eq!(app.handle_key(KeyEvent::from(KeyCode::Char(':'))), None);
+        assert_eq!(app.handle_key(KeyEvent::from(KeyCode::Char('q'))), None);
+        assert_eq!(app.handle_key(KeyEvent::from(KeyCode::Char('1'))), None);
+    }
+
+    #[cfg(feature = "sql")]
+    #[tokio::test]
+    async fn sql_prompt_captures_global_keys_and_submits() {
+        let (msg_tx, _msg_rx) = mpsc::unbounded_channel();
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        let open = app.handle_key(KeyEvent::from(KeyCode::Char('S'))).unwrap();
+        assert_eq!(open, Action::FocusSql);
+        app.handle_action(open, &msg_tx).await.unwrap();
+        assert_eq!(app.focus, Focus::SqlPrompt);
+
+        for c in "SELECT 1 FROM t".chars() {
+            assert_eq!(app.handle_key(KeyEvent::from(KeyCode::Char(c))), None);
+        }
+        let submit = app.handle_key(KeyEvent::from(KeyCode::Enter)).unwrap();
+        app.handle_action(submit, &msg_tx).await.unwrap();
+        assert_eq!(app.focus, Focus::Content);
+        assert_eq!(app.sql.as_deref(), Some("SELECT 1 FROM t"));
+        assert_eq!(app.limit, Some(DEFAULT_PAGE_SIZE));
+    }
+
     #[test]
     fn handle_message_data_ready_updates_has_more() {
         let mut app = App::new(None, Some(500), 500);
@@ -780,8 +2013,210 @@ mod tests {
         assert_eq!(app.limit, Some(300));
     }
 
+    #[test]
+    fn messages_of_replaced_scans_are_dropped() {
+        let mut app = App::new(None, Some(500), 500);
+        let scan = |generation: u64, message: AppMessage| AppMessage::Scan {
+            generation,
+            message: Box::new(message),
+        };
+        app.handle_message(&scan(
+            0,
+            AppMessage::LoadingStarted(LoadTask::Data, "Scanning...".into()),
+        ));
+        app.scan_generation = 1;
+        app.handle_message(&scan(
+            1,
+            AppMessage::LoadingStarted(LoadTask::Data, "Scanning...".into()),
+        ));
+
+        // The first scan finishing neither ends the loading state nor replaces the rows.
+        app.handle_message(&scan(
+            0,
+            AppMessage::DataReady {
+                batches: vec![],
+                total_rows: 42,
+                has_more: true,
+            },
+        ));
+        app.handle_message(&scan(0, AppMessage::LoadingFinished(LoadTask::Data)));
+        assert!(app.status_bar.is_loading(LoadTask::Data));
+        assert_eq!(app.limit, Some(500));
+
+        app.handle_message(&scan(1, AppMessage::LoadingFinished(LoadTask::Data)));
+        assert!(!app.status_bar.is_loading(LoadTask::Data));
+    }
+
+    #[test]
+    fn ref_resolved_views_the_branch_snapshot() {
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        app.current_snapshot_id = Some(1);
+
+        app.handle_message(&AppMessage::RefResolved(2));
+        assert_eq!(app.selected_snapshot_id, Some(2));
+
+        app.handle_message(&AppMessage::RefResolved(1));
+        assert_eq!(app.selected_snapshot_id, None);
+    }
+
+    #[test]
+    fn columns_missing_from_the_snapshot_are_counted() {
+        use arrow_array::{Int32Array, RecordBatch};
+        use arrow_schema::{DataType, Field, Schema};
+        use std::sync::Arc;
+
+        let cols = vec!["id".to_string(), "title".to_string(), "email".to_string()];
+        let mut app = App::new(Some(cols), None, DEFAULT_PAGE_SIZE);
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let batch =
+            RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(vec![1, 2, 3]))]).unwrap();
+        app.handle_message(&AppMessage::DataReady {
+            batches: vec![batch],
+            total_rows: 3,
+            has_more: false,
+        });
+
+        assert_eq!(app.status_bar.visible_columns, 1);
+        assert_eq!(app.status_bar.total_columns, 1);
+        assert_eq!(app.status_bar.missing_columns, 2);
+        assert!(screen_text(&mut app, 160, 10)
+            .contains("2 selected columns not present in this snapshot"));
+    }
+
+    #[tokio::test]
+    async fn ctrl_r_clears_filter_and_restores_columns() {
+        use arrow_array::{Int32Array, RecordBatch, StringArray};
+        use arrow_schema::{DataType, Field, Schema};
+        use std::sync::Arc;
+
+        let mut app = App::new(None, None, DEFAULT_PAGE_SIZE);
+        let (msg_tx, _msg_rx) = mpsc::unbounded_channel();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+            ],
+        )
+        .unwrap();
+        app.handle_message(&AppMessage::DataReady {
+            batches: vec![batch],
+            total_rows: 2,
+            has_more: false,
+        });
+        let all_cols = vec!["id".to_string(), "name".to_string()];
+        app.projection =
+            Some(crate::loader::projection::parse_projection("id * 2 AS twice").unwrap());
+
+        app.column_selector
+            .set_columns(all_cols.clone(), &["id".to_string()]);
+        app.handle_action(Action::ToggleColumn("name".into()), &msg_tx)
+            .await
+            .unwrap();
+        assert_eq!(app.data_view.visible_columns(), ["id"]);
+
+        app.filter_bar.start_editing();
+        for c in "id = 1".chars() {
+            app.filter_bar.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        let submit = app
+            .filter_bar
+            .handle_key(KeyEvent::from(KeyCode::Enter))
+            .unwrap();
+        app.handle_action(submit, &msg_tx).await.unwrap();
+        assert_eq!(app.filter_bar.applied_filter(), Some("id = 1"));
+        assert!(app.status_bar.filter_active);
+
+        let reset = app
+            .handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert_eq!(reset, Action::ResetView);
+        app.handle_action(reset, &msg_tx).await.unwrap();
+        assert_eq!(app.filter_bar.applied_filter(), None);
+        assert!(!app.status_bar.filter_active);
+        assert_eq!(app.data_view.visible_columns(), all_cols.as_slice());
+        assert_eq!(app.column_selector.enabled_columns(), all_cols);
+        assert!(app.projection.is_none());
+        assert!(app.selected_snapshot_id.is_none());
+    }
+
+    #[test]
+    fn count_line_flags_unapplied_deletes() {
+        let mut counts = TableCounts {
+            rows: 1200,
+            files: 4,
+            delete_files: 0,
+        };
+        assert_eq!(count_line(&counts, 7), "1200 rows, 4 files at snapshot 7");
+        counts.delete_files = 2;
+        assert_eq!(
+            count_line(&counts, 7),
+            "at most 1200 rows (2 delete files not applied), 4 files at snapshot 7"
+        );
+    }
+
     #[test]
     fn table_handle_static_starts_none() {
         let _handle = TABLE_HANDLE.lock().unwrap();
     }
+
+    /// The sample table's manifests repeated up to `count`, standing in for a wide table.
+    async fn many_manifests(count: usize) -> (Vec<ManifestFile>, FileIO) {
+        let handle = crate::loader::test_support::sample_table().await;
+        let list = load_manifest_list(&handle, None).await.unwrap().unwrap();
+        let manifests = list.entries().iter().cycle().take(count).cloned().collect();
+        (manifests, handle.table.file_io().clone())
+    }
+
+    #[tokio::test]
+    async fn concurrent_manifest_loads_keep_list_order() {
+        let (manifests, file_io) = many_manifests(24).await;
+        let summary = |loaded: Vec<(ManifestFile, Result<Vec<DataFileInfo>>)>| {
+            loaded
+                .into_iter()
+                .map(|(mf, files)| {
+                    let files: Vec<_> = files.unwrap().into_iter().map(|f| f.file_path).collect();
+                    (mf.manifest_path, files)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let sequential = summary(load_all_data_files(manifests.clone(), file_io.clone(), 1).await);
+        let concurrent = summary(load_all_data_files(manifests.clone(), file_io, 8).await);
+        assert_eq!(sequential, concurrent);
+        let paths: Vec<_> = manifests
+            .iter()
+            .map(|mf| mf.manifest_path.clone())
+            .collect();
+        let loaded: Vec<_> = concurrent.into_iter().map(|(path, _)| path).collect();
+        assert_eq!(loaded, paths);
+    }
+
+    /// Times loading many manifests one at a time against the default concurrency. Run with
+    /// `cargo test manifest_loads_sequential_vs_concurrent -- --ignored --nocapture`.
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore = "benchmark"]
+    async fn manifest_loads_sequential_vs_concurrent() {
+        use std::time::Instant;
+
+        let (manifests, file_io) = many_manifests(400).await;
+        let started = Instant::now();
+        load_all_data_files(manifests.clone(), file_io.clone(), 1).await;
+        let sequential = started.elapsed();
+
+        let started = Instant::now();
+        load_all_data_files(manifests, file_io, crate::cli::DEFAULT_MAX_CONCURRENCY).await;
+        let concurrent = started.elapsed();
+
+        eprintln!(
+            "400 manifests sequentially: {:?}, {} at a time: {:?}",
+            sequential,
+            crate::cli::DEFAULT_MAX_CONCURRENCY,
+            concurrent
+        );
+    }
 }
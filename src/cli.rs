@@ -1,8 +1,26 @@
-use clap::{Parser, Subcommand};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 
+use anyhow::{bail, Context, Result};
+use clap::builder::BoolishValueParser;
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap::{ArgAction, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+
+use crate::components::data_view::{BoolStyle, ColumnWidth};
+use crate::loader::arrow_convert::BinaryFormat;
+use crate::loader::catalog_loader::CatalogOptions;
 use crate::loader::file_io::StorageConfig;
+use crate::ui::Tab;
+use crate::util::format::{parse_timestamp, parse_timestamp_until};
 
 pub const DEFAULT_PAGE_SIZE: usize = 500;
+pub const DEFAULT_MAX_CONCURRENCY: usize = 8;
+/// Seconds between `version-hint.text` checks when `--watch` is given without a value.
+pub const DEFAULT_WATCH_SECS: &str = "5";
+/// Config file profile used when `--profile` isn't given.
+pub const DEFAULT_PROFILE: &str = "default";
 
 pub fn effective_limit(limit: Option<usize>, no_limit: bool) -> Option<usize> {
     if no_limit {
@@ -16,7 +34,27 @@ pub fn effective_limit(limit: Option<usize>, no_limit: bool) -> Option<usize> {
 #[command(name = "icepeek", about = "Terminal-based Apache Iceberg table viewer")]
 pub struct Cli {
     #[command(subcommand)]
-    pub command: Command,
+    pub command: Option<Command>,
+
+    /// Show a sample of every themed element and exit on a keypress, without loading a table
+    #[arg(long)]
+    pub theme_preview: bool,
+
+    /// Pick from recently opened tables (also what happens when no table source is given)
+    #[arg(long, conflicts_with = "theme_preview")]
+    pub recent: bool,
+
+    /// Write diagnostic logs to this file (level set by RUST_LOG)
+    #[arg(long, global = true)]
+    pub log_file: Option<PathBuf>,
+
+    /// Log at debug level, including iceberg internals (with --log-file)
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+
+    /// Config file profile whose settings to use (default: the "default" profile, if any)
+    #[arg(long, global = true, env = "ICEPEEK_PROFILE")]
+    pub profile: Option<String>,
 }
 
 #[derive(Subcommand, Clone)]
@@ -34,8 +72,22 @@ pub enum Command {
         #[arg(long)]
         no_limit: bool,
 
+        /// Re-read metadata/version-hint.text every SECS seconds and reload when another writer
+        /// commits
+        #[arg(
+            long,
+            value_name = "SECS",
+            num_args = 0..=1,
+            default_missing_value = DEFAULT_WATCH_SECS,
+            value_parser = parse_watch_secs
+        )]
+        watch: Option<u64>,
+
         #[command(flatten)]
         storage: StorageConfig,
+
+        #[command(flatten)]
+        view: ViewOptions,
     },
 
     /// Open a table from a REST catalog
@@ -55,19 +107,919 @@ pub enum Command {
         #[arg(long)]
         no_limit: bool,
 
+        /// Ask the catalog for the table every SECS seconds and reload when another writer
+        /// commits
+        #[arg(
+            long,
+            value_name = "SECS",
+            num_args = 0..=1,
+            default_missing_value = DEFAULT_WATCH_SECS,
+            value_parser = parse_watch_secs
+        )]
+        watch: Option<u64>,
+
         #[command(flatten)]
         storage: StorageConfig,
+
+        #[command(flatten)]
+        catalog: CatalogOptions,
+
+        #[command(flatten)]
+        view: ViewOptions,
     },
 }
 
+/// Options that shape what is loaded and displayed, shared by every table source.
+#[derive(Args, Clone, Debug, Default)]
+pub struct ViewOptions {
+    /// Tab to show at startup
+    #[arg(long, value_enum, default_value_t = Tab::Data)]
+    pub tab: Tab,
+
+    /// Show only the first N rows (same as --limit N)
+    #[arg(long, value_name = "N", conflicts_with_all = ["limit", "no_limit"])]
+    pub head: Option<usize>,
+
+    /// Show the last N rows; counts the table first to find where they start
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with_all = ["limit", "no_limit", "head"]
+    )]
+    pub tail: Option<usize>,
+
+    /// Load metadata only and skip the initial data scan; press r to load data later
+    #[arg(long, conflicts_with = "tail")]
+    pub metadata_only: bool,
+
+    /// Filter the initial scan, e.g. "price > 100 AND dept = 'HR'"; it starts out in the filter
+    /// bar, where it can be edited. Not with --tail, whose row count ignores filters
+    #[arg(long, value_name = "FILTER", conflicts_with = "tail")]
+    pub filter: Option<String>,
+
+    /// Print the scan plan for FILTER (or --filter, or an unfiltered scan) as JSON and exit,
+    /// without the TUI
+    #[arg(
+        long,
+        value_name = "FILTER",
+        num_args = 0..=1,
+        default_missing_value = "",
+        conflicts_with = "metadata_only"
+    )]
+    pub explain_json: Option<String>,
+
+    /// Print the row and data file counts as one line and exit, without scanning or the TUI.
+    /// Counts the whole snapshot, so not with --filter, --head or --tail
+    #[arg(
+        long,
+        conflicts_with_all = ["metadata_only", "explain_json", "filter", "head", "tail"]
+    )]
+    pub count_only: bool,
+
+    /// Snapshot to count with --count-only (defaults to the current one)
+    #[arg(long, value_name = "ID", requires = "count_only")]
+    pub snapshot: Option<i64>,
+
+    /// Count the snapshot that was current at TIMESTAMP: RFC 3339, "YYYY-MM-DD[ HH:MM:SS]" (UTC)
+    /// or epoch milliseconds
+    #[arg(
+        long,
+        value_name = "TIMESTAMP",
+        value_parser = parse_timestamp,
+        requires = "count_only",
+        conflicts_with = "snapshot"
+    )]
+    pub as_of: Option<i64>,
+
+    /// Read the snapshot a branch or tag points at, e.g. a write-audit-publish staging branch,
+    /// instead of the current one
+    #[arg(long = "ref", value_name = "NAME", conflicts_with_all = ["snapshot", "as_of"])]
+    pub ref_name: Option<String>,
+
+    /// List only snapshots committed at or after TIMESTAMP on the Snapshots tab (same formats
+    /// as --as-of); press t to change the range
+    #[arg(long, value_name = "TIMESTAMP", value_parser = parse_timestamp)]
+    pub since: Option<i64>,
+
+    /// List only snapshots committed at or before TIMESTAMP on the Snapshots tab; a date alone
+    /// includes that whole day
+    #[arg(long, value_name = "TIMESTAMP", value_parser = parse_timestamp_until)]
+    pub until: Option<i64>,
+
+    /// Leave out Properties sections with nothing to show; press e to toggle
+    #[arg(long)]
+    pub hide_empty: bool,
+
+    /// Hours after which the current snapshot's age is shown as stale on the Properties tab
+    #[arg(
+        long,
+        value_name = "HOURS",
+        default_value_t = crate::components::properties_panel::DEFAULT_STALE_AFTER_HOURS,
+        value_parser = parse_stale_hours
+    )]
+    pub stale_after: u64,
+
+    /// File listing columns to show, one per line (alternative to --columns)
+    #[arg(long, conflicts_with = "columns")]
+    pub columns_file: Option<PathBuf>,
+
+    /// Comma-separated projection, e.g. "name, salary * 12 AS annual"
+    #[arg(long = "select", visible_alias = "select-expr")]
+    pub select: Option<String>,
+
+    /// Placeholder shown for NULL cells in the data grid
+    #[arg(long, default_value = crate::components::data_view::DEFAULT_NULL_STRING)]
+    pub null_string: String,
+
+    /// Marker ending data grid cells cut off at the column width; pass "" to disable it
+    #[arg(long, default_value = crate::components::data_view::DEFAULT_TRUNCATION_INDICATOR)]
+    pub truncation_indicator: String,
+
+    /// Data grid column widths as NAME=WIDTH, where WIDTH is a number of characters, a
+    /// percentage such as 30% or "fill" for the space left over, e.g. "notes=fill,id=6"
+    #[arg(
+        long,
+        value_name = "NAME=WIDTH",
+        value_delimiter = ',',
+        value_parser = parse_column_width
+    )]
+    pub column_width: Vec<(String, ColumnWidth)>,
+
+    /// Columns whose values are masked in the data grid, e.g. "email,name"; `*` on the Data
+    /// tab shows them again
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    pub redact: Vec<String>,
+
+    /// How binary columns are shown in the data grid
+    #[arg(long, value_enum, default_value_t = BinaryFormat::Hex)]
+    pub binary_as: BinaryFormat,
+
+    /// How boolean columns are shown in the data grid
+    #[arg(long, value_enum, default_value_t = BoolStyle::Text)]
+    pub bool_style: BoolStyle,
+
+    /// Render every data row with the same background instead of alternating stripes
+    #[arg(long)]
+    pub no_zebra: bool,
+
+    /// Background of alternate data rows: a color name, "#rrggbb" or a 256-color index
+    #[arg(long, conflicts_with = "no_zebra")]
+    pub stripe_color: Option<ratatui::style::Color>,
+
+    /// Add `_file` and `_pos` columns showing where each row was read from
+    #[arg(long)]
+    pub metadata_columns: bool,
+
+    /// Number of snapshots whose manifests are kept in memory (0 disables caching)
+    #[arg(long, default_value_t = crate::loader::manifest_cache::DEFAULT_MANIFEST_CACHE_SIZE)]
+    pub manifest_cache_size: usize,
+
+    /// Number of manifests loaded at once for the Files and Stats tabs and the row count
+    #[arg(long, default_value_t = DEFAULT_MAX_CONCURRENCY, value_parser = parse_concurrency)]
+    pub max_concurrency: usize,
+
+    /// SQL query over the scanned table, registered as `t`
+    #[cfg(feature = "sql")]
+    #[arg(long)]
+    pub sql: Option<String>,
+}
+
+impl ViewOptions {
+    /// Alternate-row background, or `None` when zebra striping is disabled.
+    pub fn stripe_color(&self) -> Option<ratatui::style::Color> {
+        if self.no_zebra {
+            None
+        } else {
+            Some(
+                self.stripe_color
+                    .unwrap_or(crate::ui::theme::Theme::STRIPE_COLOR),
+            )
+        }
+    }
+}
+
+impl Cli {
+    /// Parse the command line, filling unset flags from the config file. Without a table source
+    /// the recent tables picker is shown, unless `--theme-preview` is given.
+    pub fn parse_args() -> Self {
+        let cli =
+            Self::parse_configured(std::env::args_os().collect()).unwrap_or_else(|e| e.exit());
+        if let Err(e) = cli.validate() {
+            e.exit();
+        }
+        cli
+    }
+
+    /// Parse `args` with the config file's settings, as `parse_args` does for the process
+    /// arguments.
+    pub fn parse_configured(args: Vec<OsString>) -> Result<Self, clap::Error> {
+        let (command, args) = Config::load()
+            .and_then(|config| config.apply(Self::command(), &args))
+            .map_err(|e| Self::command().error(ErrorKind::InvalidValue, format!("{:#}", e)))?;
+        Self::from_arg_matches(&command.try_get_matches_from(args)?)
+    }
+
+    fn validate(&self) -> Result<(), clap::Error> {
+        match (&self.command, self.theme_preview) {
+            (Some(_), true) => Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "--theme-preview cannot be used with a table source",
+            )),
+            (Some(_), false) if self.recent => Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "--recent cannot be used with a table source",
+            )),
+            (Some(command), false)
+                if command
+                    .view()
+                    .since
+                    .zip(command.view().until)
+                    .is_some_and(|(since, until)| since > until) =>
+            {
+                Err(Self::command()
+                    .error(ErrorKind::ArgumentConflict, "--since is later than --until"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Command {
+    pub fn view(&self) -> &ViewOptions {
+        match self {
+            Command::Open { view, .. } | Command::Catalog { view, .. } => view,
+        }
+    }
+
+    /// Seconds between checks for new commits, when `--watch` is given.
+    pub fn watch(&self) -> Option<u64> {
+        match self {
+            Command::Open { watch, .. } | Command::Catalog { watch, .. } => *watch,
+        }
+    }
+}
+
+/// Settings for command-line flags read from the config file, keyed by long flag name,
+/// e.g. `limit = 1000` or `s3-endpoint = "http://localhost:9000"`.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct Config {
+    /// Named `[profiles.NAME]` tables of settings, chosen with `--profile NAME`.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, BTreeMap<String, toml::Value>>,
+    #[serde(flatten)]
+    pub defaults: BTreeMap<String, toml::Value>,
+}
+
+impl Config {
+    /// Read the config file; a missing file is an empty config.
+    pub fn load() -> Result<Self> {
+        let Some(path) = config_path() else {
+            return Ok(Self::default());
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("invalid config file {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("cannot read config file {}", path.display())),
+        }
+    }
+
+    /// Merge the config values into `args` for the flags the user left unset, so clap checks
+    /// them like typed flags and `--help` never shows them. The precedence is flags, then
+    /// environment, then file, and a file value is dropped when the user set a flag it
+    /// conflicts with. The profile selected by `args` is layered on top and also wins over
+    /// the environment, so that choosing a profile can't silently pick up credentials meant
+    /// for another.
+    fn apply(
+        &self,
+        command: clap::Command,
+        args: &[OsString],
+    ) -> Result<(clap::Command, Vec<OsString>)> {
+        let command = command
+            .mut_args(negatable)
+            .mut_subcommands(|sub| sub.mut_args(negatable));
+        // Parse leniently to learn what the user set and which subcommand runs.
+        let matches = command
+            .clone()
+            .ignore_errors(true)
+            .try_get_matches_from(args)
+            .ok();
+        let selected = matches
+            .as_ref()
+            .filter(|m| is_user_set(m, "profile"))
+            .and_then(|m| m.get_one::<String>("profile").cloned())
+            .or_else(|| match self.defaults.get("profile") {
+                Some(toml::Value::String(name)) => Some(name.clone()),
+                _ => None,
+            });
+        let profile = match selected {
+            Some(name) => Some(
+                self.profiles
+                    .get(&name)
+                    .with_context(|| format!("unknown profile `{}` in config file", name))?,
+            ),
+            None => self.profiles.get(DEFAULT_PROFILE),
+        };
+
+        let mut settings = BTreeMap::new();
+        for (key, value) in &self.defaults {
+            settings.insert(key, (setting_value(key, value)?, false));
+        }
+        for (key, value) in profile.into_iter().flatten() {
+            settings.insert(key, (setting_value(key, value)?, true));
+        }
+
+        let running = matches.as_ref().and_then(|m| m.subcommand());
+        let mut top_flags = Vec::new();
+        let mut sub_flags = Vec::new();
+        for (key, (value, from_profile)) in settings {
+            let flag = OsString::from(format!("--{}={}", key, value));
+            if let Some(id) = long_flag_id(&command, key) {
+                if config_fills(&command, matches.as_ref(), &id, from_profile) {
+                    top_flags.push(flag);
+                }
+                continue;
+            }
+            if let Some((name, sub_matches)) = running {
+                let sub = command
+                    .find_subcommand(name)
+                    .expect("parsed subcommand exists");
+                if let Some(id) = long_flag_id(sub, key) {
+                    if config_fills(sub, Some(sub_matches), &id, from_profile) {
+                        sub_flags.push(flag);
+                    }
+                    continue;
+                }
+            }
+            // Flags of a subcommand that isn't running are fine; anything else is a typo.
+            if !command
+                .get_subcommands()
+                .any(|sub| long_flag_id(sub, key).is_some())
+            {
+                bail!("unknown setting `{}` in config file", key);
+            }
+        }
+
+        let mut merged: Vec<OsString> = args.iter().take(1).cloned().collect();
+        merged.extend(top_flags);
+        merged.extend(args.iter().skip(1).cloned());
+        merged.extend(sub_flags);
+        Ok((command, merged))
+    }
+}
+
+/// Id of the argument of `command` whose long flag is `--{key}`.
+fn long_flag_id(command: &clap::Command, key: &str) -> Option<String> {
+    command
+        .get_arguments()
+        .find(|arg| arg.get_long() == Some(key))
+        .map(|arg| arg.get_id().to_string())
+}
+
+/// Whether the user gave the argument `id` as a flag or through its environment variable.
+fn is_user_set(matches: &clap::ArgMatches, id: &str) -> bool {
+    matches!(
+        matches.value_source(id),
+        Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+    )
+}
+
+/// Whether a config value should be passed for the argument `id` of `command`: the user set
+/// neither it nor an argument it conflicts with. Profile values replace environment variables.
+fn config_fills(
+    command: &clap::Command,
+    matches: Option<&clap::ArgMatches>,
+    id: &str,
+    from_profile: bool,
+) -> bool {
+    let Some(matches) = matches else {
+        return true;
+    };
+    match matches.value_source(id) {
+        Some(ValueSource::CommandLine) => return false,
+        Some(ValueSource::EnvVariable) if !from_profile => return false,
+        _ => {}
+    }
+    let Some(arg) = command.get_arguments().find(|arg| arg.get_id() == id) else {
+        return true;
+    };
+    let conflicts = command.get_arg_conflicts_with(arg);
+    !command.get_arguments().any(|other| {
+        other.get_id() != id
+            && is_user_set(matches, other.get_id().as_str())
+            && (conflicts.iter().any(|c| c.get_id() == other.get_id())
+                || command
+                    .get_arg_conflicts_with(other)
+                    .iter()
+                    .any(|c| c.get_id() == id))
+    })
+}
+
+/// Let an on/off flag take a value, as in `--hide-empty=false`, so that one switched on in the
+/// config file can be switched off on the command line.
+fn negatable(arg: clap::Arg) -> clap::Arg {
+    if !matches!(arg.get_action(), ArgAction::SetTrue) {
+        return arg;
+    }
+    arg.action(ArgAction::Set)
+        .num_args(0..=1)
+        .require_equals(true)
+        .default_missing_value("true")
+        .value_name("BOOL")
+        .default_value("false")
+        .hide_default_value(true)
+        .value_parser(BoolishValueParser::new())
+}
+
+/// `$ICEPEEK_CONFIG`, else `icepeek/config.toml` under `$XDG_CONFIG_HOME` or `~/.config`.
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("ICEPEEK_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("icepeek").join("config.toml"))
+}
+
+/// A config value as it would be typed on the command line; lists become comma-separated.
+fn setting_value(key: &str, value: &toml::Value) -> Result<String> {
+    match value {
+        toml::Value::String(s) => Ok(s.clone()),
+        toml::Value::Integer(n) => Ok(n.to_string()),
+        toml::Value::Float(f) => Ok(f.to_string()),
+        toml::Value::Boolean(b) => Ok(b.to_string()),
+        toml::Value::Array(items) => Ok(items
+            .iter()
+            .map(|item| setting_value(key, item))
+            .collect::<Result<Vec<_>>>()?
+            .join(",")),
+        _ => bail!(
+            "setting `{}` must be a string, number, boolean or list",
+            key
+        ),
+    }
+}
+
+fn parse_concurrency(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("must be at least 1".into()),
+        Ok(n) => Ok(n),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse_watch_secs(s: &str) -> Result<u64, String> {
+    match s.parse::<u64>() {
+        Ok(0) => Err("must be at least 1".into()),
+        Ok(n) => Ok(n),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse_column_width(s: &str) -> Result<(String, ColumnWidth), String> {
+    let (name, width) = s
+        .rsplit_once('=')
+        .ok_or_else(|| format!("invalid column width `{}`: expected NAME=WIDTH", s))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("invalid column width `{}`: missing column name", s));
+    }
+    Ok((name.to_string(), width.parse()?))
+}
+
+fn parse_stale_hours(s: &str) -> Result<u64, String> {
+    match s.parse::<u64>() {
+        Ok(0) => Err("must be at least 1".into()),
+        Ok(n) => Ok(n),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Read a column list with one name per line, skipping blank lines and `#` comments.
+pub fn read_columns_file(path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("cannot read columns file {}", path.display()))?;
+    Ok(parse_columns_list(&content))
+}
+
+fn parse_columns_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn try_parse_with_config(config: &str, args: &[&str]) -> Result<Cli, clap::Error> {
+        let config: Config = toml::from_str(config).unwrap();
+        let args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        let (command, args) = config.apply(Cli::command(), &args).unwrap();
+        Cli::from_arg_matches(&command.try_get_matches_from(args)?)
+    }
+
+    fn parse_with_config(config: &str, args: &[&str]) -> Cli {
+        try_parse_with_config(config, args).unwrap()
+    }
+
+    const PROFILES: &str = r#"
+limit = 1000
+
+[profiles.default]
+s3-endpoint = "http://localhost:9000"
+s3-access-key-id = "minio"
+
+[profiles.prod]
+s3-endpoint = "https://s3.eu-west-1.amazonaws.com"
+s3-region = "eu-west-1"
+limit = 50
+"#;
+
+    fn open_args(cli: Cli) -> (StorageConfig, Option<usize>) {
+        let Some(Command::Open { storage, limit, .. }) = cli.command else {
+            panic!("expected Open command");
+        };
+        (storage, limit)
+    }
+
+    #[test]
+    fn profile_flag_selects_profile_settings() {
+        let cli = parse_with_config(PROFILES, &["icepeek", "open", "/t", "--profile", "prod"]);
+        assert_eq!(cli.profile.as_deref(), Some("prod"));
+        let (storage, limit) = open_args(cli);
+        assert_eq!(
+            storage.s3_endpoint.as_deref(),
+            Some("https://s3.eu-west-1.amazonaws.com")
+        );
+        assert_eq!(storage.s3_region, "eu-west-1");
+        assert_eq!(limit, Some(50));
+
+        let cli = parse_with_config(
+            PROFILES,
+            &["icepeek", "--profile", "prod", "open", "/t", "--limit", "7"],
+        );
+        assert_eq!(open_args(cli).1, Some(7));
+    }
+
+    #[test]
+    fn default_profile_applies_without_profile_flag() {
+        let (storage, limit) = open_args(parse_with_config(PROFILES, &["icepeek", "open", "/t"]));
+        assert_eq!(
+            storage.s3_endpoint.as_deref(),
+            Some("http://localhost:9000")
+        );
+        assert_eq!(storage.s3_access_key_id.as_deref(), Some("minio"));
+        assert_eq!(limit, Some(1000));
+
+        let config = format!("profile = \"prod\"\n{}", PROFILES);
+        let (storage, _) = open_args(parse_with_config(&config, &["icepeek", "open", "/t"]));
+        assert_eq!(storage.s3_region, "eu-west-1");
+    }
+
+    #[test]
+    fn unknown_profile_is_an_error() {
+        let config: Config = toml::from_str(PROFILES).unwrap();
+        let args: Vec<OsString> = ["icepeek", "--profile", "staging", "open", "/t"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        let err = config.apply(Cli::command(), &args).unwrap_err();
+        assert!(err.to_string().contains("staging"));
+    }
+
+    #[test]
+    fn config_file_overrides_built_in_defaults() {
+        let cli = parse_with_config(
+            "limit = 1000\nnull-string = \"∅\"\ntab = \"schema\"\nhide-empty = true",
+            &["icepeek", "open", "/tmp/table"],
+        );
+        let command = cli.command.unwrap();
+        let Command::Open { limit, .. } = &command else {
+            panic!("expected Open command");
+        };
+        assert_eq!(*limit, Some(1000));
+        assert_eq!(command.view().null_string, "∅");
+        assert_eq!(command.view().tab, Tab::Schema);
+        assert!(command.view().hide_empty);
+    }
+
+    #[test]
+    fn flags_override_config_file() {
+        let cli = parse_with_config(
+            "limit = 1000\nuri = \"http://catalog:8181\"\ncolumns = [\"id\", \"name\"]",
+            &[
+                "icepeek", "catalog", "--table", "db.t", "--limit", "5", "-c", "email",
+            ],
+        );
+        let Some(Command::Catalog {
+            uri,
+            limit,
+            columns,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected Catalog command");
+        };
+        assert_eq!(uri, "http://catalog:8181");
+        assert_eq!(limit, Some(5));
+        assert_eq!(columns, Some(vec!["email".to_string()]));
+
+        let cli = parse_with_config("columns = [\"id\", \"name\"]", &["icepeek", "open", "/t"]);
+        let Some(Command::Open { columns, .. }) = cli.command else {
+            panic!("expected Open command");
+        };
+        assert_eq!(columns, Some(vec!["id".to_string(), "name".to_string()]));
+    }
+
+    #[test]
+    fn config_values_stay_out_of_help() {
+        let config: Config =
+            toml::from_str("s3-secret-access-key = \"hunter2\"\n[profiles.default]\nlimit = 1000")
+                .unwrap();
+        let args: Vec<OsString> = ["icepeek", "open", "/t"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        let (mut command, _) = config.apply(Cli::command(), &args).unwrap();
+        let help = command
+            .find_subcommand_mut("open")
+            .unwrap()
+            .render_long_help()
+            .to_string();
+        assert!(!help.contains("hunter2"));
+        assert!(!help.contains("1000"));
+    }
+
+    #[test]
+    fn config_bool_can_be_switched_off() {
+        let cli = parse_with_config("hide-empty = true", &["icepeek", "open", "/t"]);
+        assert!(cli.command.unwrap().view().hide_empty);
+
+        let cli = parse_with_config(
+            "hide-empty = true",
+            &["icepeek", "open", "/t", "--hide-empty=false"],
+        );
+        assert!(!cli.command.unwrap().view().hide_empty);
+
+        let cli = parse_with_config(
+            "hide-empty = true",
+            &["icepeek", "open", "/t", "--hide-empty"],
+        );
+        assert!(cli.command.unwrap().view().hide_empty);
+    }
+
+    #[test]
+    fn config_values_conflicting_with_flags_are_dropped() {
+        let cli = parse_with_config("tail = 20", &["icepeek", "open", "/t", "--head", "5"]);
+        let view = cli.command.unwrap().view().clone();
+        assert_eq!(view.head, Some(5));
+        assert_eq!(view.tail, None);
+
+        let cli = parse_with_config(
+            "no-zebra = true",
+            &["icepeek", "open", "/t", "--stripe-color", "red"],
+        );
+        assert!(!cli.command.unwrap().view().no_zebra);
+    }
+
+    #[test]
+    fn conflicting_config_values_are_rejected() {
+        assert!(try_parse_with_config("tail = 20\nhead = 5", &["icepeek", "open", "/t"]).is_err());
+        assert!(try_parse_with_config("snapshot = 5", &["icepeek", "open", "/t"]).is_err());
+        assert!(try_parse_with_config(
+            "tail = 20\nfilter = \"id > 1\"",
+            &["icepeek", "open", "/t"]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn config_file_rejects_unknown_settings() {
+        let config: Config = toml::from_str("no-such-flag = 1").unwrap();
+        let err = config.apply(Cli::command(), &[]).unwrap_err();
+        assert!(err.to_string().contains("no-such-flag"));
+
+        let config: Config = toml::from_str("limit = { a = 1 }").unwrap();
+        assert!(config.apply(Cli::command(), &[]).is_err());
+    }
+
+    #[test]
+    fn theme_preview_needs_no_table_source() {
+        let cli = Cli::parse_from(["icepeek", "--theme-preview"]);
+        assert!(cli.theme_preview);
+        assert!(cli.command.is_none());
+        assert!(cli.validate().is_ok());
+
+        let cli = Cli::parse_from(["icepeek", "--theme-preview", "open", "/tmp/table"]);
+        assert!(cli.validate().is_err());
+    }
+
+    #[test]
+    fn recent_picker_replaces_table_source() {
+        let cli = Cli::parse_from(["icepeek"]);
+        assert!(cli.command.is_none());
+        assert!(cli.validate().is_ok());
+
+        let cli = Cli::parse_from(["icepeek", "--recent"]);
+        assert!(cli.recent);
+        assert!(cli.validate().is_ok());
+
+        let cli = Cli::parse_from(["icepeek", "--recent", "open", "/tmp/table"]);
+        assert!(cli.validate().is_err());
+        assert!(Cli::try_parse_from(["icepeek", "--recent", "--theme-preview"]).is_err());
+    }
+
+    #[test]
+    fn parse_filter_for_open_and_catalog() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--filter", "price > 100"]);
+        assert_eq!(
+            cli.command.unwrap().view().filter.as_deref(),
+            Some("price > 100")
+        );
+
+        let cli = Cli::parse_from([
+            "icepeek",
+            "catalog",
+            "--uri",
+            "http://localhost:8181",
+            "--table",
+            "db.t",
+            "--filter",
+            "dept = 'HR'",
+        ]);
+        assert_eq!(
+            cli.command.unwrap().view().filter.as_deref(),
+            Some("dept = 'HR'")
+        );
+
+        // The tail offset comes from the unfiltered row count.
+        assert!(Cli::try_parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--filter",
+            "id > 1",
+            "--tail",
+            "5"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_explain_json_with_and_without_filter() {
+        let cli = Cli::parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--explain-json",
+            "age > 30",
+        ]);
+        assert_eq!(
+            cli.command.unwrap().view().explain_json.as_deref(),
+            Some("age > 30")
+        );
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--explain-json"]);
+        assert_eq!(
+            cli.command.unwrap().view().explain_json.as_deref(),
+            Some("")
+        );
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table"]);
+        assert!(cli.command.unwrap().view().explain_json.is_none());
+    }
+
+    #[test]
+    fn parse_watch_with_and_without_interval() {
+        let watch = |args: &[&str]| {
+            let mut full = vec!["icepeek", "open", "/tmp/table"];
+            full.extend(args);
+            match Cli::try_parse_from(full)?.command {
+                Some(Command::Open { watch, .. }) => Ok::<_, clap::Error>(watch),
+                _ => panic!("expected Open"),
+            }
+        };
+        assert_eq!(watch(&[]).unwrap(), None);
+        assert_eq!(watch(&["--watch"]).unwrap(), Some(5));
+        assert_eq!(watch(&["--watch", "30"]).unwrap(), Some(30));
+        assert!(watch(&["--watch", "0"]).is_err());
+        let cli = Cli::parse_from([
+            "icepeek",
+            "catalog",
+            "--uri",
+            "http://localhost:8181",
+            "--table",
+            "db.t",
+            "--watch",
+        ]);
+        assert_eq!(cli.command.unwrap().watch(), Some(5));
+    }
+
+    #[test]
+    fn parse_count_only_at_a_point_in_time() {
+        let view = |args: &[&str]| {
+            let mut full = vec!["icepeek", "open", "/tmp/table", "--count-only"];
+            full.extend(args);
+            Cli::try_parse_from(full).map(|cli| cli.command.unwrap().view().clone())
+        };
+        assert!(view(&[]).unwrap().count_only);
+        assert_eq!(view(&["--snapshot", "42"]).unwrap().snapshot, Some(42));
+        assert_eq!(
+            view(&["--as-of", "2024-01-02"]).unwrap().as_of,
+            Some(1_704_153_600_000)
+        );
+        assert_eq!(
+            view(&["--as-of", "2024-01-02 00:00:01"]).unwrap().as_of,
+            Some(1_704_153_601_000)
+        );
+        assert_eq!(
+            view(&["--as-of", "2024-01-02T01:00:00+01:00"])
+                .unwrap()
+                .as_of,
+            Some(1_704_153_600_000)
+        );
+        assert_eq!(view(&["--as-of", "1000"]).unwrap().as_of, Some(1000));
+        assert!(view(&["--as-of", "yesterday"]).is_err());
+        assert!(view(&["--snapshot", "1", "--as-of", "1000"]).is_err());
+        assert!(view(&["--metadata-only"]).is_err());
+        assert!(view(&["--filter", "id > 1"]).is_err());
+        assert!(view(&["--head", "5"]).is_err());
+        assert!(view(&["--tail", "5"]).is_err());
+
+        assert!(Cli::try_parse_from(["icepeek", "open", "/tmp/table", "--snapshot", "1"]).is_err());
+    }
+
+    #[test]
+    fn parse_snapshot_time_range() {
+        let cli = Cli::parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--since",
+            "2025-01-01",
+            "--until",
+            "2025-01-02 12:00:00",
+        ]);
+        let view = cli.command.unwrap().view().clone();
+        assert_eq!(view.since, Some(1_735_689_600_000));
+        assert_eq!(view.until, Some(1_735_819_200_000));
+
+        let range = |since: &str, until: &str| {
+            Cli::parse_from(["icepeek", "open", "/t", "--since", since, "--until", until])
+        };
+        // A date alone runs to the end of the day, so a one-day range works.
+        let cli = range("2025-01-01", "2025-01-01");
+        assert_eq!(
+            cli.command.as_ref().unwrap().view().until,
+            Some(1_735_775_999_999)
+        );
+        assert!(cli.validate().is_ok());
+        assert!(range("2025-01-02", "2025-01-01").validate().is_err());
+    }
+
+    #[test]
+    fn parse_ref_in_both_modes() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--ref", "staging"]);
+        assert_eq!(
+            cli.command.unwrap().view().ref_name.as_deref(),
+            Some("staging")
+        );
+        let cli = Cli::parse_from([
+            "icepeek",
+            "catalog",
+            "--uri",
+            "http://localhost:8181",
+            "--table",
+            "db.events",
+            "--ref",
+            "audit-tag",
+        ]);
+        assert_eq!(
+            cli.command.unwrap().view().ref_name.as_deref(),
+            Some("audit-tag")
+        );
+        assert!(Cli::try_parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--count-only",
+            "--snapshot",
+            "1",
+            "--ref",
+            "main",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn parse_open_with_limit() {
         let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--limit", "100"]);
-        let Command::Open { limit, .. } = cli.command else {
+        let Some(Command::Open { limit, .. }) = cli.command else {
             panic!("expected Open");
         };
         assert_eq!(limit, Some(100));
@@ -76,7 +1028,7 @@ mod tests {
     #[test]
     fn parse_open_without_limit() {
         let cli = Cli::parse_from(["icepeek", "open", "/tmp/table"]);
-        let Command::Open { limit, .. } = cli.command else {
+        let Some(Command::Open { limit, .. }) = cli.command else {
             panic!("expected Open");
         };
         assert_eq!(limit, None);
@@ -85,7 +1037,7 @@ mod tests {
     #[test]
     fn parse_open_limit_short_flag() {
         let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "-l", "50"]);
-        let Command::Open { limit, .. } = cli.command else {
+        let Some(Command::Open { limit, .. }) = cli.command else {
             panic!("expected Open");
         };
         assert_eq!(limit, Some(50));
@@ -94,9 +1046,9 @@ mod tests {
     #[test]
     fn parse_open_no_limit_flag() {
         let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--no-limit"]);
-        let Command::Open {
+        let Some(Command::Open {
             limit, no_limit, ..
-        } = cli.command
+        }) = cli.command
         else {
             panic!("expected Open");
         };
@@ -116,7 +1068,7 @@ mod tests {
             "--limit",
             "200",
         ]);
-        let Command::Catalog { limit, .. } = cli.command else {
+        let Some(Command::Catalog { limit, .. }) = cli.command else {
             panic!("expected Catalog");
         };
         assert_eq!(limit, Some(200));
@@ -132,7 +1084,7 @@ mod tests {
             "--table",
             "db.t",
         ]);
-        let Command::Catalog { limit, .. } = cli.command else {
+        let Some(Command::Catalog { limit, .. }) = cli.command else {
             panic!("expected Catalog");
         };
         assert_eq!(limit, None);
@@ -149,9 +1101,9 @@ mod tests {
             "db.t",
             "--no-limit",
         ]);
-        let Command::Catalog {
+        let Some(Command::Catalog {
             limit, no_limit, ..
-        } = cli.command
+        }) = cli.command
         else {
             panic!("expected Catalog");
         };
@@ -168,7 +1120,7 @@ mod tests {
             "--s3-endpoint",
             "http://localhost:9000",
         ]);
-        let Command::Open { storage, .. } = cli.command else {
+        let Some(Command::Open { storage, .. }) = cli.command else {
             panic!("expected Open");
         };
         assert_eq!(
@@ -189,12 +1141,287 @@ mod tests {
             "--s3-region",
             "eu-west-1",
         ]);
-        let Command::Catalog { storage, .. } = cli.command else {
+        let Some(Command::Catalog { storage, .. }) = cli.command else {
             panic!("expected Catalog");
         };
         assert_eq!(storage.s3_region, "eu-west-1");
     }
 
+    #[test]
+    fn parse_catalog_with_auth() {
+        let cli = Cli::parse_from([
+            "icepeek",
+            "catalog",
+            "--uri",
+            "http://localhost:8181",
+            "--table",
+            "db.t",
+            "--credential",
+            "client:secret",
+            "--oauth2-server-uri",
+            "https://auth.example.com/token",
+        ]);
+        let Some(Command::Catalog { catalog, .. }) = cli.command else {
+            panic!("expected Catalog");
+        };
+        assert_eq!(catalog.credential.as_deref(), Some("client:secret"));
+        assert_eq!(
+            catalog.oauth2_server_uri.as_deref(),
+            Some("https://auth.example.com/token")
+        );
+    }
+
+    #[test]
+    fn parse_catalog_with_warehouse_and_prefix() {
+        let cli = Cli::parse_from([
+            "icepeek",
+            "catalog",
+            "--uri",
+            "http://localhost:8181",
+            "--table",
+            "db.t",
+            "--warehouse",
+            "analytics",
+            "--prefix",
+            "/prod/",
+        ]);
+        let Some(Command::Catalog { catalog, .. }) = cli.command else {
+            panic!("expected Catalog");
+        };
+        assert_eq!(catalog.warehouse.as_deref(), Some("analytics"));
+        assert_eq!(catalog.prefix.as_deref(), Some("prod"));
+    }
+
+    #[test]
+    fn empty_warehouse_is_rejected() {
+        let result = Cli::try_parse_from([
+            "icepeek",
+            "catalog",
+            "--uri",
+            "http://localhost:8181",
+            "--table",
+            "db.t",
+            "--warehouse",
+            "",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_select_expression() {
+        let cli = Cli::parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--select",
+            "salary, salary * 12 AS annual",
+        ]);
+        assert_eq!(
+            cli.command.unwrap().view().select.as_deref(),
+            Some("salary, salary * 12 AS annual")
+        );
+    }
+
+    #[test]
+    fn parse_log_file_after_subcommand() {
+        let cli = Cli::parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--log-file",
+            "icepeek.log",
+            "-v",
+        ]);
+        assert_eq!(cli.log_file.as_deref(), Some(Path::new("icepeek.log")));
+        assert!(cli.verbose);
+    }
+
+    #[test]
+    fn parse_columns_file_flag() {
+        let cli = Cli::parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--columns-file",
+            "cols.txt",
+        ]);
+        assert_eq!(
+            cli.command.unwrap().view().columns_file.as_deref(),
+            Some(Path::new("cols.txt"))
+        );
+    }
+
+    #[test]
+    fn columns_file_conflicts_with_columns() {
+        let result = Cli::try_parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--columns",
+            "id",
+            "--columns-file",
+            "cols.txt",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_redact() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table"]);
+        assert!(cli.command.unwrap().view().redact.is_empty());
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--redact", "email,name"]);
+        assert_eq!(cli.command.unwrap().view().redact, ["email", "name"]);
+    }
+
+    #[test]
+    fn parse_bool_style() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table"]);
+        assert_eq!(cli.command.unwrap().view().bool_style, BoolStyle::Text);
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--bool-style", "check"]);
+        assert_eq!(cli.command.unwrap().view().bool_style, BoolStyle::Check);
+
+        let result = Cli::try_parse_from(["icepeek", "open", "/tmp/table", "--bool-style", "x"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_binary_as() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table"]);
+        assert_eq!(cli.command.unwrap().view().binary_as, BinaryFormat::Hex);
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--binary-as", "utf8-lossy"]);
+        assert_eq!(
+            cli.command.unwrap().view().binary_as,
+            BinaryFormat::Utf8Lossy
+        );
+
+        let result = Cli::try_parse_from(["icepeek", "open", "/tmp/table", "--binary-as", "raw"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_start_tab() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table"]);
+        assert_eq!(cli.command.unwrap().view().tab, Tab::Data);
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--tab", "snapshots"]);
+        assert_eq!(cli.command.unwrap().view().tab, Tab::Snapshots);
+
+        assert!(Cli::try_parse_from(["icepeek", "open", "/tmp/table", "--tab", "nope"]).is_err());
+    }
+
+    #[test]
+    fn parse_max_concurrency() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table"]);
+        assert_eq!(
+            cli.command.unwrap().view().max_concurrency,
+            DEFAULT_MAX_CONCURRENCY
+        );
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--max-concurrency", "32"]);
+        assert_eq!(cli.command.unwrap().view().max_concurrency, 32);
+
+        let args = ["icepeek", "open", "/tmp/table", "--max-concurrency", "0"];
+        assert!(Cli::try_parse_from(args).is_err());
+    }
+
+    #[test]
+    fn parse_head_and_tail() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--head", "5"]);
+        assert_eq!(cli.command.unwrap().view().head, Some(5));
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--tail", "20"]);
+        assert_eq!(cli.command.unwrap().view().tail, Some(20));
+
+        for conflict in [
+            &["--head", "5", "--tail", "5"][..],
+            &["--tail", "5", "--limit", "10"],
+            &["--head", "5", "--no-limit"],
+        ] {
+            let args = ["icepeek", "open", "/tmp/table"]
+                .into_iter()
+                .chain(conflict.iter().copied());
+            assert!(Cli::try_parse_from(args).is_err(), "{:?}", conflict);
+        }
+    }
+
+    #[test]
+    fn metadata_only_conflicts_with_tail() {
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--metadata-only"]);
+        assert!(cli.command.unwrap().view().metadata_only);
+        assert!(Cli::try_parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--metadata-only",
+            "--tail",
+            "5"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn column_width_flags() {
+        let cli = Cli::parse_from([
+            "icepeek",
+            "open",
+            "/tmp/table",
+            "--column-width",
+            "notes=fill,id=6",
+            "--column-width",
+            "name=30%",
+        ]);
+        assert_eq!(
+            cli.command.unwrap().view().column_width,
+            vec![
+                ("notes".to_string(), ColumnWidth::Fill),
+                ("id".to_string(), ColumnWidth::Fixed(6)),
+                ("name".to_string(), ColumnWidth::Percent(30)),
+            ]
+        );
+
+        for bad in ["notes", "=6", "id=wide"] {
+            assert!(
+                Cli::try_parse_from(["icepeek", "open", "/tmp/table", "--column-width", bad])
+                    .is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn stripe_color_flags() {
+        use ratatui::style::Color;
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table"]);
+        assert_eq!(
+            cli.command.unwrap().view().stripe_color(),
+            Some(crate::ui::theme::Theme::STRIPE_COLOR)
+        );
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--no-zebra"]);
+        assert_eq!(cli.command.unwrap().view().stripe_color(), None);
+
+        let cli = Cli::parse_from(["icepeek", "open", "/tmp/table", "--stripe-color", "#202830"]);
+        assert_eq!(
+            cli.command.unwrap().view().stripe_color(),
+            Some(Color::Rgb(0x20, 0x28, 0x30))
+        );
+    }
+
+    #[test]
+    fn columns_list_skips_blanks_and_comments() {
+        let cols = parse_columns_list("# wanted columns\nid\n\n  name  \n# salary\nemail\n");
+        assert_eq!(cols, vec!["id", "name", "email"]);
+    }
+
+    #[test]
+    fn missing_columns_file_errors() {
+        let err = read_columns_file(Path::new("/nonexistent/cols.txt")).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/cols.txt"));
+    }
+
     #[test]
     fn effective_limit_default() {
         assert_eq!(effective_limit(None, false), Some(DEFAULT_PAGE_SIZE));
@@ -0,0 +1,30 @@
+use std::io::Write;
+
+use anyhow::{Context, Result};
+
+use crate::util::encoding::base64_encode;
+
+/// Copy text to the system clipboard using the OSC 52 terminal escape sequence.
+/// This works over SSH and inside tmux (with `set-clipboard on`), but terminals
+/// that don't support OSC 52 silently ignore it.
+pub fn copy(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(osc52_sequence(text).as_bytes())
+        .and_then(|_| stdout.flush())
+        .context("failed to write to the terminal clipboard")
+}
+
+fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn osc52_wraps_encoded_text() {
+        assert_eq!(osc52_sequence("foo"), "\x1b]52;c;Zm9v\x07");
+    }
+}
@@ -1,35 +1,161 @@
+use std::collections::{HashMap, HashSet};
+
 use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::layout::Rect;
-use ratatui::text::Text;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Cell, Row, Table, TableState};
 use ratatui::Frame;
 
-use crate::event::{Action, AppMessage};
-use crate::loader::arrow_convert;
+use crate::event::{Action, AppMessage, LoadTask};
+use crate::loader::arrow_convert::{self, BinaryFormat, StringRow};
+use crate::model::table_info::{FieldInfo, TableMetadata};
 use crate::ui::theme::Theme;
+use crate::util::format;
 
 use super::Component;
 use arrow_array::RecordBatch;
+use arrow_schema::DataType;
 
 const DEFAULT_MAX_VISIBLE_COLS: usize = 20;
 const PAGE_SCROLL_SIZE: usize = 20;
 const WIDTH_SAMPLE_ROWS: usize = 100;
 const MIN_COLUMN_WIDTH: usize = 4;
 const MAX_COLUMN_WIDTH: usize = 40;
-const ROW_NUMBER_WIDTH: u16 = 5;
+const MIN_ROW_NUMBER_DIGITS: usize = 4;
 const COLUMN_PADDING: u16 = 2;
+/// Gap ratatui leaves between table columns.
+const COLUMN_SPACING: u16 = 1;
+pub const DEFAULT_NULL_STRING: &str = "NULL";
+pub const DEFAULT_TRUNCATION_INDICATOR: &str = "…";
+/// Shown instead of the values of redacted columns.
+const REDACTION_MASK: &str = "••••";
+/// Appended to the header of columns the schema marks optional, as in the Schema tab.
+const OPTIONAL_MARKER: &str = "?";
+
+/// Width override for one data column, set with `--column-width`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// Exactly this many characters.
+    Fixed(u16),
+    /// This share of the table's width.
+    Percent(u16),
+    /// Whatever space the other columns leave.
+    Fill,
+}
+
+impl std::str::FromStr for ColumnWidth {
+    type Err = String;
+
+    /// `30` for 30 characters, `30%` for 30% of the table, or `fill`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("fill") {
+            return Ok(Self::Fill);
+        }
+        let (number, percent) = match s.strip_suffix('%') {
+            Some(number) => (number, true),
+            None => (s, false),
+        };
+        let n: u16 = number
+            .trim()
+            .parse()
+            .map_err(|_| format!("invalid width `{}`: expected N, N% or fill", s))?;
+        match (n, percent) {
+            (0, _) => Err("width must be at least 1".into()),
+            (101.., true) => Err("percentage must be at most 100".into()),
+            (n, true) => Ok(Self::Percent(n)),
+            (n, false) => Ok(Self::Fixed(n)),
+        }
+    }
+}
+
+/// How boolean cells are drawn in the grid. Only the display changes; copied and exported
+/// values stay `true`/`false`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BoolStyle {
+    /// true / false
+    #[default]
+    Text,
+    /// ✓ / ✗
+    Check,
+    /// ● / ○
+    Dot,
+}
+
+impl BoolStyle {
+    /// Symbol standing in for a formatted boolean, or `None` to show the text as is.
+    fn symbol(self, value: &str) -> Option<&'static str> {
+        match (self, value) {
+            (BoolStyle::Check, "true") => Some("✓"),
+            (BoolStyle::Check, "false") => Some("✗"),
+            (BoolStyle::Dot, "true") => Some("●"),
+            (BoolStyle::Dot, "false") => Some("○"),
+            _ => None,
+        }
+    }
+}
+
+/// Incremental find prompt opened with `f`.
+struct Find {
+    query: String,
+    /// Selected row when the prompt opened; Esc goes back to it.
+    origin: Option<usize>,
+    /// Rows with a cell containing the query, in display order.
+    matches: Vec<usize>,
+}
 
 pub struct DataView {
     batches: Vec<RecordBatch>,
     all_columns: Vec<String>,
     visible_columns: Vec<String>,
-    display_rows: Vec<Vec<String>>,
+    display_rows: Vec<StringRow>,
     display_columns: Vec<String>,
+    /// Arrow type of each entry in `display_columns`, used for alignment.
+    display_types: Vec<DataType>,
     table_state: TableState,
     pub total_rows: usize,
     h_scroll: usize,
     max_visible_cols: usize,
     has_more: bool,
+    /// Placeholder rendered (dimmed) in place of null cells.
+    null_string: String,
+    /// Appended to cells clipped to their column width; empty to clip silently.
+    truncation_indicator: String,
+    binary_format: BinaryFormat,
+    bool_style: BoolStyle,
+    /// Set while a scan for this view is in flight.
+    scanning: bool,
+    /// Absolute position of the first loaded row, so numbering stays table-relative.
+    row_offset: usize,
+    show_row_numbers: bool,
+    /// Background of every other row, or `None` for flat rows.
+    stripe_color: Option<Color>,
+    /// Stripe background `z` turns back on: the configured one, or the theme's.
+    stripe_on_color: Color,
+    /// Title annotation describing the table's partitioning.
+    partition_note: Option<String>,
+    /// Field docs from the current schema by column name, nested fields by dotted path.
+    column_docs: HashMap<String, String>,
+    /// Columns the current schema marks optional, nested fields by dotted path.
+    optional_columns: HashSet<String>,
+    /// Mark optional columns in the header row.
+    show_optional: bool,
+    /// Set when the initial scan was skipped, until data is loaded on demand.
+    metadata_only: bool,
+    /// Show numeric cells with thousands separators.
+    group_numbers: bool,
+    /// Which snapshot the rows come from, for the block title.
+    snapshot_label: String,
+    /// Column name being typed into the jump prompt, if the prompt is open.
+    jump_input: Option<String>,
+    jump_error: Option<String>,
+    find: Option<Find>,
+    /// Columns whose values are masked while `redact` is on.
+    redacted_columns: HashSet<String>,
+    redact: bool,
+    /// Per-column width overrides by column name; other columns size to their content.
+    column_widths: HashMap<String, ColumnWidth>,
 }
 
 impl DataView {
@@ -40,11 +166,34 @@ impl DataView {
             visible_columns: vec![],
             display_rows: vec![],
             display_columns: vec![],
+            display_types: vec![],
             table_state: TableState::default(),
             total_rows: 0,
             h_scroll: 0,
             max_visible_cols: DEFAULT_MAX_VISIBLE_COLS,
             has_more: false,
+            null_string: DEFAULT_NULL_STRING.to_string(),
+            truncation_indicator: DEFAULT_TRUNCATION_INDICATOR.to_string(),
+            binary_format: BinaryFormat::default(),
+            bool_style: BoolStyle::default(),
+            scanning: false,
+            row_offset: 0,
+            show_row_numbers: true,
+            stripe_color: Some(Theme::STRIPE_COLOR),
+            stripe_on_color: Theme::STRIPE_COLOR,
+            partition_note: None,
+            column_docs: HashMap::new(),
+            optional_columns: HashSet::new(),
+            show_optional: true,
+            metadata_only: false,
+            group_numbers: false,
+            snapshot_label: String::new(),
+            jump_input: None,
+            jump_error: None,
+            find: None,
+            redacted_columns: HashSet::new(),
+            redact: true,
+            column_widths: HashMap::new(),
         }
     }
 
@@ -56,6 +205,106 @@ impl DataView {
         &self.visible_columns
     }
 
+    /// Selected columns the loaded batches don't have, such as columns added after the
+    /// snapshot being viewed. Empty until batches with a schema have been loaded.
+    pub fn missing_columns(&self) -> Vec<&str> {
+        if self.all_columns.is_empty() {
+            return vec![];
+        }
+        self.visible_columns
+            .iter()
+            .filter(|c| !self.display_columns.contains(c))
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn batches(&self) -> &[RecordBatch] {
+        &self.batches
+    }
+
+    /// The leftmost column on screen, which horizontal scrolling moves through.
+    pub fn focused_column(&self) -> Option<&str> {
+        self.display_columns.get(self.h_scroll).map(String::as_str)
+    }
+
+    pub fn set_null_string(&mut self, null_string: String) {
+        self.null_string = null_string;
+    }
+
+    pub fn set_truncation_indicator(&mut self, indicator: String) {
+        self.truncation_indicator = indicator;
+    }
+
+    pub fn set_column_widths(&mut self, widths: Vec<(String, ColumnWidth)>) {
+        self.column_widths = widths.into_iter().collect();
+    }
+
+    pub fn set_binary_format(&mut self, binary_format: BinaryFormat) {
+        self.binary_format = binary_format;
+    }
+
+    /// Set how boolean cells are drawn.
+    pub fn set_bool_style(&mut self, bool_style: BoolStyle) {
+        self.bool_style = bool_style;
+    }
+
+    pub fn set_metadata_only(&mut self, metadata_only: bool) {
+        self.metadata_only = metadata_only;
+    }
+
+    pub fn set_snapshot_label(&mut self, label: String) {
+        self.snapshot_label = label;
+    }
+
+    /// Set the alternate-row background; `None` disables zebra striping.
+    pub fn set_stripe_color(&mut self, color: Option<Color>) {
+        self.stripe_color = color;
+        if let Some(color) = color {
+            self.stripe_on_color = color;
+        }
+    }
+
+    pub fn set_group_numbers(&mut self, group_numbers: bool) {
+        self.group_numbers = group_numbers;
+    }
+
+    pub fn set_redacted_columns(&mut self, columns: Vec<String>) {
+        self.redacted_columns = columns.into_iter().collect();
+    }
+
+    /// Whether `column`'s values are currently masked.
+    pub fn is_redacted(&self, column: &str) -> bool {
+        self.redact && self.redacted_columns.contains(column)
+    }
+
+    fn is_redacted_at(&self, col_idx: usize) -> bool {
+        self.display_columns
+            .get(col_idx)
+            .is_some_and(|c| self.is_redacted(c))
+    }
+
+    /// Text shown for a non-null cell: a mask in redacted columns, grouped when it's numeric
+    /// and grouping is on, and a symbol for booleans when `--bool-style` asks for one.
+    fn cell_text<'a>(&self, value: &'a str, col_idx: usize) -> std::borrow::Cow<'a, str> {
+        let is_bool = self.display_types.get(col_idx) == Some(&DataType::Boolean);
+        if self.is_redacted_at(col_idx) {
+            REDACTION_MASK.into()
+        } else if let Some(symbol) = is_bool.then(|| self.bool_style.symbol(value)).flatten() {
+            symbol.into()
+        } else if self.group_numbers && self.is_right_aligned(col_idx) {
+            format::group_digits(value).into()
+        } else {
+            value.into()
+        }
+    }
+
+    fn row_style(&self, i: usize) -> Style {
+        match self.stripe_color {
+            Some(color) if i % 2 == 1 => Theme::table_row_alt().bg(color),
+            _ => Theme::table_row_normal(),
+        }
+    }
+
     pub fn set_visible_columns(&mut self, columns: Vec<String>) {
         self.visible_columns = columns;
         self.refresh_display();
@@ -68,14 +317,19 @@ impl DataView {
             &self.visible_columns
         };
 
-        let Ok((display_cols, rows)) =
-            arrow_convert::batches_to_string_rows(&self.batches, 0, self.total_rows.max(1))
-        else {
+        let Ok((display_cols, rows)) = arrow_convert::batches_to_string_rows(
+            &self.batches,
+            0,
+            self.total_rows.max(1),
+            self.binary_format,
+        ) else {
             return;
         };
+        let types = arrow_convert::column_types(&self.batches);
 
         if self.visible_columns.is_empty() {
             self.display_columns = display_cols;
+            self.display_types = types;
             self.display_rows = rows;
             return;
         }
@@ -89,12 +343,202 @@ impl DataView {
             .iter()
             .map(|&i| display_cols[i].clone())
             .collect();
+        self.display_types = col_indices.iter().map(|&i| types[i].clone()).collect();
         self.display_rows = rows
             .into_iter()
             .map(|row| col_indices.iter().map(|&i| row[i].clone()).collect())
             .collect();
     }
 
+    /// Numeric columns are right-aligned; everything else is left-aligned.
+    fn is_right_aligned(&self, col_idx: usize) -> bool {
+        self.display_types
+            .get(col_idx)
+            .is_some_and(|t| t.is_numeric())
+    }
+
+    /// Cut `text` down to `width` characters, ending it with the truncation indicator when
+    /// anything was dropped.
+    fn clip_cell<'a>(&self, text: &'a str, width: usize) -> std::borrow::Cow<'a, str> {
+        if self.truncation_indicator.is_empty() || text.chars().count() <= width {
+            return text.into();
+        }
+        let keep = width.saturating_sub(self.truncation_indicator.chars().count());
+        let mut clipped: String = text.chars().take(keep).collect();
+        clipped.push_str(&self.truncation_indicator);
+        clipped.into()
+    }
+
+    /// Pad `text` to `width` according to the column's alignment.
+    fn align_cell(&self, text: &str, col_idx: usize, width: usize) -> String {
+        if self.is_right_aligned(col_idx) {
+            format!("{:>width$}", text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Constraint and text width of each column in `cols`, whose content-based widths are
+    /// `auto`. Overridden columns get their fixed width, their share of `available` (the
+    /// width left for data columns) or an equal part of what the other columns leave over.
+    fn column_layout(
+        &self,
+        cols: std::ops::Range<usize>,
+        auto: &[u16],
+        available: u16,
+    ) -> Vec<(Constraint, u16)> {
+        let overrides: Vec<Option<ColumnWidth>> = cols
+            .map(|i| self.column_widths.get(&self.display_columns[i]).copied())
+            .collect();
+        let percent_width = |p: u16| (available as u32 * p as u32 / 100) as u16;
+
+        let mut taken = COLUMN_SPACING as usize * overrides.len().saturating_sub(1);
+        let mut fills = 0;
+        for (&width, column) in auto.iter().zip(&overrides) {
+            taken += match column {
+                None => width.saturating_add(COLUMN_PADDING) as usize,
+                Some(ColumnWidth::Fixed(n)) => n.saturating_add(COLUMN_PADDING) as usize,
+                Some(ColumnWidth::Percent(p)) => percent_width(*p) as usize,
+                Some(ColumnWidth::Fill) => {
+                    fills += 1;
+                    0
+                }
+            };
+        }
+        let fill_width = (available as usize).saturating_sub(taken) / fills.max(1);
+
+        auto.iter()
+            .zip(&overrides)
+            .map(|(&width, column)| match column {
+                None => (
+                    Constraint::Length(width.saturating_add(COLUMN_PADDING)),
+                    width,
+                ),
+                Some(ColumnWidth::Fixed(n)) => {
+                    (Constraint::Length(n.saturating_add(COLUMN_PADDING)), *n)
+                }
+                Some(ColumnWidth::Percent(p)) => (
+                    Constraint::Percentage(*p),
+                    percent_width(*p).saturating_sub(COLUMN_PADDING),
+                ),
+                Some(ColumnWidth::Fill) => (
+                    Constraint::Fill(1),
+                    (fill_width as u16)
+                        .saturating_sub(COLUMN_PADDING)
+                        .max(MIN_COLUMN_WIDTH as u16),
+                ),
+            })
+            .collect()
+    }
+
+    /// Absolute, 1-based row number of the `i`-th loaded row.
+    fn row_number(&self, i: usize) -> usize {
+        self.row_offset + i + 1
+    }
+
+    /// Digits needed for the largest row number on screen.
+    fn row_number_digits(&self) -> usize {
+        let largest = self.row_number(self.display_rows.len().saturating_sub(1));
+        largest.to_string().len().max(MIN_ROW_NUMBER_DIGITS)
+    }
+
+    /// Describe the default partition spec, e.g. "partitioned by department, day(ts)".
+    fn partition_summary(metadata: &TableMetadata) -> Option<String> {
+        let spec = metadata.default_partition_spec()?;
+        if spec.fields.is_empty() {
+            return None;
+        }
+        let parts: Vec<String> = spec
+            .fields
+            .iter()
+            .map(|f| {
+                let source = metadata
+                    .current_schema
+                    .find_field(f.source_id)
+                    .map_or(f.name.as_str(), |field| field.name.as_str());
+                if f.transform == "identity" {
+                    source.to_string()
+                } else {
+                    format!("{}({})", f.transform, source)
+                }
+            })
+            .collect();
+        Some(format!("partitioned by {}", parts.join(", ")))
+    }
+
+    /// Docs of `fields` and their children, keyed by dotted path.
+    fn column_docs(fields: &[FieldInfo]) -> HashMap<String, String> {
+        fn collect(fields: &[FieldInfo], prefix: &str, docs: &mut HashMap<String, String>) {
+            for field in fields {
+                let path = format!("{}{}", prefix, field.name);
+                if let Some(doc) = field
+                    .doc
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|d| !d.is_empty())
+                {
+                    docs.insert(path.clone(), doc.to_string());
+                }
+                collect(&field.children, &format!("{}.", path), docs);
+            }
+        }
+        let mut docs = HashMap::new();
+        collect(fields, "", &mut docs);
+        docs
+    }
+
+    /// Optional fields in `fields` and their children, by dotted path.
+    fn optional_columns(fields: &[FieldInfo]) -> HashSet<String> {
+        fn collect(fields: &[FieldInfo], prefix: &str, optional: &mut HashSet<String>) {
+            for field in fields {
+                let path = format!("{}{}", prefix, field.name);
+                collect(&field.children, &format!("{}.", path), optional);
+                if !field.required {
+                    optional.insert(path);
+                }
+            }
+        }
+        let mut optional = HashSet::new();
+        collect(fields, "", &mut optional);
+        optional
+    }
+
+    /// Header text of the column at `col_idx`, with the optional marker when it applies.
+    fn header_text(&self, col_idx: usize) -> String {
+        let column = &self.display_columns[col_idx];
+        if self.show_optional && self.optional_columns.contains(column) {
+            format!("{}{}", column, OPTIONAL_MARKER)
+        } else {
+            column.clone()
+        }
+    }
+
+    /// Header cell for the column at `col_idx`, clipped and aligned to `width`, with the
+    /// optional marker dimmed.
+    fn header_line(&self, col_idx: usize, width: usize) -> Line<'static> {
+        let text = self.header_text(col_idx);
+        let title = self.align_cell(&self.clip_cell(&text, width), col_idx, width);
+        let marked = text.len() > self.display_columns[col_idx].len();
+        match title.trim_end().strip_suffix(OPTIONAL_MARKER) {
+            Some(name) if marked => {
+                let rest = title[name.len() + OPTIONAL_MARKER.len()..].to_string();
+                Line::from(vec![
+                    Span::styled(name.to_string(), Theme::table_header()),
+                    Span::styled(OPTIONAL_MARKER, Theme::field_id()),
+                    Span::raw(rest),
+                ])
+            }
+            _ => Line::styled(title, Theme::table_header()),
+        }
+    }
+
+    /// "name — doc" for the focused column, when its field has a doc.
+    fn focused_doc(&self) -> Option<String> {
+        let column = self.focused_column()?;
+        let doc = self.column_docs.get(column)?;
+        Some(format!("{} — {}", column, doc))
+    }
+
     fn move_up(&mut self) {
         let i = self.table_state.selected().unwrap_or(0);
         if i > 0 {
@@ -135,6 +579,219 @@ impl DataView {
         }
     }
 
+    /// Rows the table will show in `height` lines, following ratatui's scrolling: the window
+    /// stays put unless the selection has moved outside it.
+    fn visible_row_range(&self, height: usize) -> std::ops::Range<usize> {
+        let len = self.display_rows.len();
+        let offset = self.table_state.offset();
+        let start = match self.table_state.selected() {
+            Some(selected) if selected < offset => selected,
+            Some(selected) if height > 0 && selected >= offset + height => selected + 1 - height,
+            _ => offset,
+        };
+        let start = start.min(len);
+        start..(start + height).min(len)
+    }
+
+    /// Scroll so `name` becomes the leftmost (focused) column. An exact match wins over a
+    /// case-insensitive one. Returns false if no displayed column has that name.
+    fn jump_to_column(&mut self, name: &str) -> bool {
+        let position = self
+            .display_columns
+            .iter()
+            .position(|c| c == name)
+            .or_else(|| {
+                self.display_columns
+                    .iter()
+                    .position(|c| c.eq_ignore_ascii_case(name))
+            });
+        match position {
+            Some(i) => {
+                self.h_scroll = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Extend the jump prompt to the longest prefix shared by the columns it starts.
+    fn complete_jump_input(&mut self) {
+        let Some(input) = self.jump_input.as_mut() else {
+            return;
+        };
+        let mut matches = self
+            .display_columns
+            .iter()
+            .filter(|c| c.starts_with(input.as_str()));
+        let Some(first) = matches.next() else {
+            return;
+        };
+        let common = matches.fold(first.as_str(), |common, c| {
+            let len = common
+                .char_indices()
+                .zip(c.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(common.len().min(c.len()), |((i, _), _)| i);
+            &common[..len]
+        });
+        *input = common.to_string();
+    }
+
+    fn handle_jump_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let input = self.jump_input.as_mut()?;
+        match key.code {
+            KeyCode::Esc => {
+                self.jump_input = None;
+                self.jump_error = None;
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+                self.jump_error = None;
+            }
+            KeyCode::Backspace => {
+                input.pop();
+                self.jump_error = None;
+            }
+            KeyCode::Tab => {
+                self.complete_jump_input();
+                self.jump_error = None;
+            }
+            KeyCode::Enter => {
+                let input = input.clone();
+                if self.jump_to_column(&input) {
+                    self.jump_input = None;
+                    self.jump_error = None;
+                } else {
+                    self.jump_error = Some(format!("no such column: {}", input));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Block title while the jump prompt is open.
+    fn jump_title(&self) -> Option<String> {
+        match (&self.jump_input, &self.jump_error) {
+            (Some(_), Some(err)) => Some(format!(" {} (Esc to cancel) ", err)),
+            (Some(input), None) => Some(format!(" Jump to column: {}█ ", input)),
+            (None, _) => None,
+        }
+    }
+
+    /// Whether a cell shows `needle`, which is already lowercased. Matching ignores case, and
+    /// redacted cells never match so find can't reveal what they hold.
+    fn cell_matches(&self, value: &str, col_idx: usize, needle: &str) -> bool {
+        !needle.is_empty() && !self.is_redacted_at(col_idx) && value.to_lowercase().contains(needle)
+    }
+
+    /// Recompute the find matches and select the first one at or after the row the prompt
+    /// opened on, wrapping to the top. With no matches the selection goes back to that row.
+    fn update_find(&mut self) {
+        let Some(needle) = self.find.as_ref().map(|f| f.query.to_lowercase()) else {
+            return;
+        };
+        let matches = self
+            .display_rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| {
+                row.iter().enumerate().any(|(col_idx, cell)| {
+                    cell.as_deref()
+                        .is_some_and(|v| self.cell_matches(v, col_idx, &needle))
+                })
+            })
+            .map(|(i, _)| i)
+            .collect();
+        let Some(find) = self.find.as_mut() else {
+            return;
+        };
+        find.matches = matches;
+        let from = find.origin.unwrap_or(0);
+        let target = find
+            .matches
+            .iter()
+            .find(|&&i| i >= from)
+            .or(find.matches.first())
+            .copied()
+            .or(find.origin);
+        self.table_state.select(target);
+    }
+
+    /// Move to the next (or previous) match, wrapping around.
+    fn cycle_find(&mut self, forward: bool) {
+        let Some(find) = self.find.as_ref() else {
+            return;
+        };
+        let current = self.table_state.selected().unwrap_or(0);
+        let target = if forward {
+            find.matches
+                .iter()
+                .find(|&&i| i > current)
+                .or(find.matches.first())
+        } else {
+            find.matches
+                .iter()
+                .rev()
+                .find(|&&i| i < current)
+                .or(find.matches.last())
+        };
+        if let Some(&i) = target {
+            self.table_state.select(Some(i));
+        }
+    }
+
+    fn handle_find_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let find = self.find.as_mut()?;
+        match key.code {
+            KeyCode::Esc => {
+                let origin = find.origin;
+                self.find = None;
+                self.table_state.select(origin);
+            }
+            KeyCode::Enter => self.find = None,
+            KeyCode::Char(c) => {
+                find.query.push(c);
+                self.update_find();
+            }
+            KeyCode::Backspace => {
+                find.query.pop();
+                self.update_find();
+            }
+            KeyCode::Tab | KeyCode::Down => self.cycle_find(true),
+            KeyCode::BackTab | KeyCode::Up => self.cycle_find(false),
+            _ => {}
+        }
+        None
+    }
+
+    /// Block title while the find prompt is open, with the match count.
+    fn find_title(&self) -> Option<String> {
+        let find = self.find.as_ref()?;
+        let count = if find.query.is_empty() {
+            String::new()
+        } else if find.matches.is_empty() {
+            " (no matches)".to_string()
+        } else {
+            let current = self.table_state.selected();
+            let position = find.matches.iter().position(|&i| Some(i) == current);
+            format!(
+                " ({}/{})",
+                position.map_or("-".to_string(), |p| (p + 1).to_string()),
+                find.matches.len()
+            )
+        };
+        Some(format!(" Find: {}█{} ", find.query, count))
+    }
+
+    /// Lowercased query to highlight in cells while the find prompt is open.
+    fn find_needle(&self) -> Option<String> {
+        self.find
+            .as_ref()
+            .filter(|f| !f.query.is_empty())
+            .map(|f| f.query.to_lowercase())
+    }
+
     fn jump_top(&mut self) {
         self.table_state.select(Some(0));
     }
@@ -148,7 +805,26 @@ impl DataView {
 
 impl Component for DataView {
     fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if self.jump_input.is_some() {
+            return self.handle_jump_key(key);
+        }
+        if self.find.is_some() {
+            return self.handle_find_key(key);
+        }
+
         match key.code {
+            KeyCode::Char('|') => {
+                self.jump_input = Some(String::new());
+                None
+            }
+            KeyCode::Char('f') => {
+                self.find = Some(Find {
+                    query: String::new(),
+                    origin: self.table_state.selected(),
+                    matches: vec![],
+                });
+                None
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.move_up();
                 None
@@ -181,20 +857,56 @@ impl Component for DataView {
                 self.jump_bottom();
                 None
             }
+            KeyCode::Char('#') => {
+                self.show_row_numbers = !self.show_row_numbers;
+                None
+            }
+            KeyCode::Char('*') => {
+                self.redact = !self.redact;
+                None
+            }
+            KeyCode::Char('o') => {
+                self.show_optional = !self.show_optional;
+                None
+            }
+            KeyCode::Char('z') => {
+                self.stripe_color = match self.stripe_color {
+                    Some(_) => None,
+                    None => Some(self.stripe_on_color),
+                };
+                None
+            }
             KeyCode::Char('/') => Some(Action::FocusFilter),
+            #[cfg(feature = "sql")]
+            KeyCode::Char('S') => Some(Action::FocusSql),
+            KeyCode::Char('x') => Some(Action::ExplainScan),
+            KeyCode::Char('s') => Some(Action::ShowColumnStats),
             KeyCode::Char('c') => Some(Action::ToggleColumnSelector),
             _ => None,
         }
     }
 
+    fn is_input_mode(&self) -> bool {
+        self.jump_input.is_some() || self.find.is_some()
+    }
+
+    fn context_hints(&self) -> &'static str {
+        "/:filter c:columns f:find |:jump s:stats m:more"
+    }
+
     fn handle_message(&mut self, msg: &AppMessage) -> Option<Action> {
         match msg {
+            AppMessage::RowOffset(offset) => {
+                self.row_offset = *offset;
+                None
+            }
             AppMessage::DataReady {
                 batches,
                 total_rows,
                 has_more,
             } => {
                 self.batches = batches.clone();
+                self.metadata_only = false;
                 self.total_rows = *total_rows;
                 self.has_more = *has_more;
                 let new_cols = arrow_convert::column_names(&self.batches);
@@ -209,22 +921,57 @@ impl Component for DataView {
                 }
                 None
             }
+            AppMessage::MetadataReady(metadata) => {
+                self.partition_note = Self::partition_summary(metadata);
+                self.column_docs = Self::column_docs(&metadata.current_schema.fields);
+                self.optional_columns = Self::optional_columns(&metadata.current_schema.fields);
+                None
+            }
+            AppMessage::LoadingStarted(LoadTask::Data, _) => {
+                self.scanning = true;
+                None
+            }
+            AppMessage::LoadingFinished(LoadTask::Data) => {
+                self.scanning = false;
+                None
+            }
             _ => None,
         }
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        let mut suffix = self.snapshot_label.clone();
+        if let Some(ref note) = self.partition_note {
+            suffix.push_str(" · ");
+            suffix.push_str(note);
+        }
+        if let Some(doc) = self.focused_doc() {
+            suffix.push_str(" · ");
+            suffix.push_str(&doc);
+        }
+        if self.scanning {
+            suffix.push_str(" · scanning…");
+        }
+
         if self.display_rows.is_empty() {
             let block = Block::default()
                 .borders(Borders::ALL)
-                .title(" Data ")
+                .title(
+                    self.jump_title()
+                        .or_else(|| self.find_title())
+                        .unwrap_or_else(|| format!(" Data{} ", suffix)),
+                )
                 .border_style(if focused {
                     Theme::border_focused()
                 } else {
                     Theme::border_unfocused()
                 });
-            let empty = ratatui::widgets::Paragraph::new("No data loaded. Press 'r' to reload.")
-                .block(block);
+            let message = if self.metadata_only {
+                "Metadata-only mode. Press 'r' to load data."
+            } else {
+                "No data loaded. Press 'r' to reload."
+            };
+            let empty = ratatui::widgets::Paragraph::new(message).block(block);
             frame.render_widget(empty, area);
             return;
         }
@@ -233,15 +980,23 @@ impl Component for DataView {
         let end_col = (self.h_scroll + self.max_visible_cols).min(total_cols);
         let visible_col_range = self.h_scroll..end_col;
 
-        let col_widths: Vec<u16> = visible_col_range
+        // Borders and the header row take three lines.
+        let on_screen = self.visible_row_range(area.height.saturating_sub(3) as usize);
+        let auto_widths: Vec<u16> = visible_col_range
             .clone()
             .map(|col_idx| {
-                let header_width = self.display_columns[col_idx].len();
-                let max_data_width = self
-                    .display_rows
+                let header_width = self.header_text(col_idx).len();
+                let max_data_width = self.display_rows
+                    [..WIDTH_SAMPLE_ROWS.min(self.display_rows.len())]
                     .iter()
-                    .take(WIDTH_SAMPLE_ROWS)
-                    .map(|row| row.get(col_idx).map_or(0, |cell| cell.len()))
+                    .chain(&self.display_rows[on_screen.clone()])
+                    .map(|row| {
+                        row.get(col_idx).map_or(0, |cell| {
+                            cell.as_deref().map_or(self.null_string.len(), |value| {
+                                self.cell_text(value, col_idx).len()
+                            })
+                        })
+                    })
                     .max()
                     .unwrap_or(0);
                 let width = header_width
@@ -251,49 +1006,84 @@ impl Component for DataView {
             })
             .collect();
 
-        let mut header_cells = vec![Cell::from("  #").style(Theme::table_header())];
-        for col_idx in visible_col_range.clone() {
-            header_cells.push(
-                Cell::from(Text::from(self.display_columns[col_idx].clone()))
-                    .style(Theme::table_header()),
-            );
+        let digits = self.row_number_digits();
+        let row_number_width = if self.show_row_numbers {
+            digits as u16 + 1 + COLUMN_SPACING
+        } else {
+            0
+        };
+        // Borders take two columns.
+        let available = area.width.saturating_sub(2 + row_number_width);
+        let layout = self.column_layout(visible_col_range.clone(), &auto_widths, available);
+        let col_widths: Vec<u16> = layout.iter().map(|&(_, width)| width).collect();
+
+        let mut header_cells = Vec::new();
+        if self.show_row_numbers {
+            header_cells.push(Cell::from(format!("{:>digits$}", "#")).style(Theme::table_header()));
+        }
+        for (col_idx, &width) in visible_col_range.clone().zip(&col_widths) {
+            let title = self.header_line(col_idx, width as usize);
+            header_cells.push(Cell::from(title).style(Theme::table_header()));
         }
         let header = Row::new(header_cells).height(1);
 
+        let needle = self.find_needle();
         let rows: Vec<Row> = self
             .display_rows
             .iter()
             .enumerate()
             .map(|(i, row)| {
-                let style = if i % 2 == 0 {
-                    Theme::table_row_normal()
-                } else {
-                    Theme::table_row_alt()
-                };
+                let style = self.row_style(i);
 
-                let mut cells = vec![Cell::from(format!("{:>4}", i + 1)).style(style)];
-                for col_idx in visible_col_range.clone() {
-                    let text = row.get(col_idx).cloned().unwrap_or_default();
-                    cells.push(Cell::from(text).style(style));
+                let mut cells = Vec::new();
+                if self.show_row_numbers {
+                    cells.push(Cell::from(format!("{:>digits$}", self.row_number(i))).style(style));
+                }
+                for (col_idx, &width) in visible_col_range.clone().zip(&col_widths) {
+                    let (text, cell_style) = match row.get(col_idx) {
+                        Some(Some(value))
+                            if needle
+                                .as_deref()
+                                .is_some_and(|n| self.cell_matches(value, col_idx, n)) =>
+                        {
+                            (
+                                self.cell_text(value, col_idx),
+                                style.patch(Theme::find_match()),
+                            )
+                        }
+                        Some(Some(value)) => (self.cell_text(value, col_idx), style),
+                        Some(None) => (
+                            self.null_string.as_str().into(),
+                            style.patch(Theme::null_value()),
+                        ),
+                        None => ("".into(), style),
+                    };
+                    let text = self.clip_cell(&text, width as usize);
+                    let text = self.align_cell(&text, col_idx, width as usize);
+                    cells.push(Cell::from(text).style(cell_style));
                 }
                 Row::new(cells).height(1)
             })
             .collect();
 
-        let mut widths = vec![ratatui::layout::Constraint::Length(ROW_NUMBER_WIDTH)];
-
-        for w in &col_widths {
-            widths.push(ratatui::layout::Constraint::Length(*w + COLUMN_PADDING));
+        let mut widths = Vec::new();
+        if self.show_row_numbers {
+            widths.push(Constraint::Length(digits as u16 + 1));
         }
+        widths.extend(layout.iter().map(|&(constraint, _)| constraint));
 
         let row_label = if self.has_more {
-            format!(" Data ({} rows loaded) ", self.total_rows)
+            format!(" Data ({} rows loaded){} ", self.total_rows, suffix)
         } else {
-            format!(" Data ({} rows) ", self.total_rows)
+            format!(" Data ({} rows){} ", self.total_rows, suffix)
         };
         let block = Block::default()
             .borders(Borders::ALL)
-            .title(row_label)
+            .title(
+                self.jump_title()
+                    .or_else(|| self.find_title())
+                    .unwrap_or(row_label),
+            )
             .border_style(if focused {
                 Theme::border_focused()
             } else {
@@ -352,6 +1142,112 @@ mod tests {
         assert_eq!(dv.all_columns, vec!["id", "name"]);
     }
 
+    #[test]
+    fn loading_data_leaves_metadata_only_mode() {
+        let mut dv = DataView::new();
+        dv.set_metadata_only(true);
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        assert!(!dv.metadata_only);
+    }
+
+    #[test]
+    fn long_value_on_screen_widens_column() {
+        let mut names = vec!["x".to_string(); 150];
+        names[140] = "y".repeat(30);
+        let schema = Arc::new(Schema::new(vec![Field::new("name", DataType::Utf8, false)]));
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(StringArray::from(names))]).unwrap();
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: vec![batch],
+            total_rows: 150,
+            has_more: false,
+        });
+        assert_eq!(dv.visible_row_range(10), 0..10);
+
+        dv.jump_bottom();
+        assert_eq!(dv.visible_row_range(10), 140..150);
+
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 13)).unwrap();
+        terminal
+            .draw(|frame| dv.render(frame, frame.area(), true))
+            .unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        assert!(text.contains(&"y".repeat(30)));
+    }
+
+    #[test]
+    fn clipped_cells_end_with_indicator() {
+        let mut dv = DataView::new();
+        assert_eq!(dv.clip_cell("Charlie", 7), "Charlie");
+        assert_eq!(dv.clip_cell("Charlie", 4), "Cha…");
+
+        dv.set_truncation_indicator("...".to_string());
+        assert_eq!(dv.clip_cell("Charlie", 5), "Ch...");
+
+        dv.set_truncation_indicator(String::new());
+        assert_eq!(dv.clip_cell("Charlie", 4), "Charlie");
+    }
+
+    #[test]
+    fn column_width_parses_fixed_percent_and_fill() {
+        assert_eq!("12".parse(), Ok(ColumnWidth::Fixed(12)));
+        assert_eq!("30%".parse(), Ok(ColumnWidth::Percent(30)));
+        assert_eq!("Fill".parse(), Ok(ColumnWidth::Fill));
+        assert!("0".parse::<ColumnWidth>().is_err());
+        assert!("120%".parse::<ColumnWidth>().is_err());
+        assert!("wide".parse::<ColumnWidth>().is_err());
+    }
+
+    #[test]
+    fn column_overrides_replace_content_widths() {
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        assert_eq!(
+            dv.column_layout(0..2, &[4, 7], 100),
+            vec![(Constraint::Length(6), 4), (Constraint::Length(9), 7)]
+        );
+
+        dv.set_column_widths(vec![("name".to_string(), ColumnWidth::Fill)]);
+        // 100 - (4 + 2) for `id` - 1 spacing leaves 93 for `name`.
+        assert_eq!(
+            dv.column_layout(0..2, &[4, 7], 100)[1],
+            (Constraint::Fill(1), 91)
+        );
+
+        dv.set_column_widths(vec![
+            ("id".to_string(), ColumnWidth::Fixed(10)),
+            ("name".to_string(), ColumnWidth::Percent(50)),
+        ]);
+        assert_eq!(
+            dv.column_layout(0..2, &[4, 7], 100),
+            vec![
+                (Constraint::Length(12), 10),
+                (Constraint::Percentage(50), 48)
+            ]
+        );
+
+        dv.set_column_widths(vec![("id".to_string(), ColumnWidth::Fixed(u16::MAX))]);
+        assert_eq!(
+            dv.column_layout(0..1, &[4], 100),
+            vec![(Constraint::Length(u16::MAX), u16::MAX)]
+        );
+    }
+
     #[test]
     fn data_view_navigation() {
         let mut dv = DataView::new();
@@ -386,6 +1282,91 @@ mod tests {
         assert_eq!(dv.table_state.selected(), Some(0));
     }
 
+    #[test]
+    fn jump_prompt_scrolls_to_named_column() {
+        let schema = Arc::new(Schema::new(
+            ["id", "first_name", "first_seen", "email"]
+                .map(|name| Field::new(name, DataType::Utf8, false))
+                .to_vec(),
+        ));
+        let columns = (0..4)
+            .map(|_| Arc::new(StringArray::from(vec!["x"])) as arrow_array::ArrayRef)
+            .collect();
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: vec![RecordBatch::try_new(schema, columns).unwrap()],
+            total_rows: 1,
+            has_more: false,
+        });
+        let type_keys = |dv: &mut DataView, keys: &str| {
+            for c in keys.chars() {
+                dv.handle_key(KeyEvent::from(KeyCode::Char(c)));
+            }
+        };
+
+        type_keys(&mut dv, "|fi");
+        assert!(dv.is_input_mode());
+        dv.handle_key(KeyEvent::from(KeyCode::Tab));
+        assert_eq!(dv.jump_input.as_deref(), Some("first_"));
+
+        type_keys(&mut dv, "x");
+        dv.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(dv.jump_error.as_deref(), Some("no such column: first_x"));
+        assert_eq!(dv.h_scroll, 0);
+
+        dv.handle_key(KeyEvent::from(KeyCode::Backspace));
+        type_keys(&mut dv, "SEEN");
+        dv.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(!dv.is_input_mode());
+        assert_eq!(dv.focused_column(), Some("first_seen"));
+    }
+
+    #[test]
+    fn find_moves_to_matches_as_you_type() {
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        let type_keys = |dv: &mut DataView, text: &str| {
+            for c in text.chars() {
+                dv.handle_key(KeyEvent::from(KeyCode::Char(c)));
+            }
+        };
+        dv.handle_key(KeyEvent::from(KeyCode::Down));
+        dv.handle_key(KeyEvent::from(KeyCode::Char('f')));
+        assert!(dv.is_input_mode());
+
+        // "li" matches Alice and Charlie (rows 0 and 2); the search starts from row 1.
+        type_keys(&mut dv, "L");
+        assert_eq!(dv.table_state.selected(), Some(2));
+        type_keys(&mut dv, "i");
+        assert_eq!(dv.find_title().unwrap(), " Find: Li█ (2/2) ");
+        dv.handle_key(KeyEvent::from(KeyCode::Tab));
+        assert_eq!(dv.table_state.selected(), Some(0));
+        assert_eq!(dv.find_title().unwrap(), " Find: Li█ (1/2) ");
+
+        type_keys(&mut dv, "z");
+        assert_eq!(dv.find_title().unwrap(), " Find: Liz█ (no matches) ");
+        assert_eq!(dv.table_state.selected(), Some(1));
+
+        dv.handle_key(KeyEvent::from(KeyCode::Backspace));
+        dv.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert!(!dv.is_input_mode());
+        assert_eq!(dv.table_state.selected(), Some(1));
+
+        dv.handle_key(KeyEvent::from(KeyCode::Char('f')));
+        type_keys(&mut dv, "bob");
+        dv.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(!dv.is_input_mode());
+        assert_eq!(dv.table_state.selected(), Some(1));
+        dv.handle_key(KeyEvent::from(KeyCode::Char('f')));
+        type_keys(&mut dv, "3");
+        dv.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(dv.table_state.selected(), Some(2));
+    }
+
     #[test]
     fn data_view_column_filtering() {
         let mut dv = DataView::new();
@@ -399,7 +1380,341 @@ mod tests {
         // Set only one visible column
         dv.set_visible_columns(vec!["name".to_string()]);
         assert_eq!(dv.display_columns, vec!["name"]);
-        assert_eq!(dv.display_rows[0], vec!["Alice"]);
+        assert_eq!(dv.display_rows[0], vec![Some("Alice".to_string())]);
+    }
+
+    #[test]
+    fn selected_columns_missing_from_batches_are_reported() {
+        let mut dv = DataView::new();
+        dv.set_visible_columns(vec!["name".to_string(), "title".to_string()]);
+        assert!(dv.missing_columns().is_empty());
+
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        dv.set_visible_columns(vec!["name".to_string(), "title".to_string()]);
+        assert_eq!(dv.display_columns, vec!["name"]);
+        assert_eq!(dv.missing_columns(), vec!["title"]);
+    }
+
+    #[test]
+    fn data_view_keeps_nulls_distinct_from_empty_strings() {
+        let schema = Arc::new(Schema::new(vec![Field::new("title", DataType::Utf8, true)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(StringArray::from(vec![None, Some("")]))],
+        )
+        .unwrap();
+        let mut dv = DataView::new();
+        dv.set_null_string("␀".to_string());
+        dv.handle_message(&AppMessage::DataReady {
+            batches: vec![batch],
+            total_rows: 2,
+            has_more: false,
+        });
+
+        assert_eq!(dv.display_rows[0], vec![None]);
+        assert_eq!(dv.display_rows[1], vec![Some(String::new())]);
+        assert_eq!(dv.null_string, "␀");
+    }
+
+    #[test]
+    fn data_view_tracks_only_data_loading() {
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::LoadingStarted(
+            LoadTask::Manifests,
+            "Loading manifests...".into(),
+        ));
+        assert!(!dv.scanning);
+
+        dv.handle_message(&AppMessage::LoadingStarted(
+            LoadTask::Data,
+            "Scanning...".into(),
+        ));
+        assert!(dv.scanning);
+
+        dv.handle_message(&AppMessage::LoadingFinished(LoadTask::Data));
+        assert!(!dv.scanning);
+    }
+
+    #[test]
+    fn partition_summary_names_source_columns() {
+        use crate::model::table_info::{
+            FieldInfo, PartitionFieldInfo, PartitionSpecInfo, SchemaInfo,
+        };
+
+        let field = |id: i32, name: &str| FieldInfo {
+            id,
+            name: name.into(),
+            field_type: "string".into(),
+            required: false,
+            doc: None,
+            initial_default: None,
+            write_default: None,
+            children: vec![],
+        };
+        let partition = |name: &str, transform: &str, source_id: i32| PartitionFieldInfo {
+            name: name.into(),
+            transform: transform.into(),
+            source_id,
+        };
+        let mut metadata = TableMetadata {
+            location: "/t".into(),
+            metadata_location: None,
+            current_schema: SchemaInfo {
+                schema_id: 0,
+                fields: vec![field(1, "department"), field(2, "ts")],
+            },
+            schemas: vec![],
+            snapshots: vec![],
+            refs: vec![],
+            partition_specs: vec![
+                PartitionSpecInfo {
+                    spec_id: 0,
+                    fields: vec![],
+                },
+                PartitionSpecInfo {
+                    spec_id: 1,
+                    fields: vec![
+                        partition("department", "identity", 1),
+                        partition("ts_day", "day", 2),
+                    ],
+                },
+            ],
+            default_spec_id: 1,
+            sort_orders: vec![],
+            default_sort_order_id: 0,
+            statistics: vec![],
+            partition_statistics: vec![],
+            properties: Default::default(),
+            current_snapshot_id: None,
+            format_version: 2,
+            table_uuid: String::new(),
+            last_updated_ms: 0,
+            last_column_id: 0,
+            last_partition_id: 0,
+        };
+
+        assert_eq!(
+            DataView::partition_summary(&metadata).as_deref(),
+            Some("partitioned by department, day(ts)")
+        );
+
+        metadata.default_spec_id = 0;
+        assert!(DataView::partition_summary(&metadata).is_none());
+    }
+
+    #[test]
+    fn focused_column_doc_follows_focus() {
+        use crate::model::table_info::FieldInfo;
+
+        let field = |name: &str, doc: Option<&str>, children: Vec<FieldInfo>| FieldInfo {
+            id: 0,
+            name: name.into(),
+            field_type: "string".into(),
+            required: false,
+            doc: doc.map(Into::into),
+            initial_default: None,
+            write_default: None,
+            children,
+        };
+        let docs = DataView::column_docs(&[
+            field("id", None, vec![]),
+            field("name", Some("Full name"), vec![]),
+            field(
+                "address",
+                Some(" "),
+                vec![field("city", Some("City"), vec![])],
+            ),
+        ]);
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs["address.city"], "City");
+
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        dv.column_docs = docs;
+        assert_eq!(dv.focused_doc(), None);
+        dv.h_scroll = 1;
+        assert_eq!(dv.focused_doc().as_deref(), Some("name — Full name"));
+    }
+
+    #[test]
+    fn optional_columns_are_marked_until_toggled() {
+        let field = |name: &str, required: bool, children| FieldInfo {
+            id: 0,
+            name: name.into(),
+            field_type: "string".into(),
+            required,
+            doc: None,
+            initial_default: None,
+            write_default: None,
+            children,
+        };
+        let optional = DataView::optional_columns(&[
+            field("id", true, vec![]),
+            field("name", false, vec![]),
+            field("address", true, vec![field("city", false, vec![])]),
+        ]);
+        assert_eq!(optional.len(), 2);
+        assert!(optional.contains("address.city"));
+
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        dv.optional_columns = optional;
+        assert_eq!(dv.header_text(0), "id");
+        assert_eq!(dv.header_text(1), "name?");
+        let line = dv.header_line(1, 8);
+        assert_eq!(line.spans[0].content, "name");
+        assert_eq!(line.spans[1].style, Theme::field_id());
+        // A clipped header drops the marker along with the rest of the name.
+        assert_eq!(dv.header_line(1, 4).to_string(), "nam…");
+
+        dv.handle_key(KeyEvent::from(KeyCode::Char('o')));
+        assert_eq!(dv.header_text(1), "name");
+    }
+
+    #[test]
+    fn redacted_columns_are_masked_until_toggled() {
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        dv.set_redacted_columns(vec!["name".into()]);
+        assert!(dv.is_redacted("name"));
+        assert_eq!(dv.cell_text("Alice", 1), REDACTION_MASK);
+        assert_eq!(dv.cell_text("1", 0), "1");
+        // Values stay loaded; only the display is masked.
+        assert_eq!(dv.display_rows[0][1].as_deref(), Some("Alice"));
+
+        dv.handle_key(KeyEvent::from(KeyCode::Char('f')));
+        dv.handle_key(KeyEvent::from(KeyCode::Char('b')));
+        assert_eq!(dv.find_title().unwrap(), " Find: b█ (no matches) ");
+        dv.handle_key(KeyEvent::from(KeyCode::Enter));
+
+        dv.handle_key(KeyEvent::from(KeyCode::Char('*')));
+        assert!(!dv.is_redacted("name"));
+        assert_eq!(dv.cell_text("Alice", 1), "Alice");
+    }
+
+    #[test]
+    fn row_numbers_are_absolute_and_widen() {
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        assert_eq!(dv.row_number(0), 1);
+        assert_eq!(dv.row_number_digits(), MIN_ROW_NUMBER_DIGITS);
+
+        dv.handle_message(&AppMessage::RowOffset(123_456));
+        assert_eq!(dv.row_number(0), 123_457);
+        assert_eq!(dv.row_number_digits(), 6);
+    }
+
+    #[test]
+    fn grouping_applies_to_numeric_cells_only() {
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+        assert_eq!(dv.cell_text("1234567", 0), "1234567");
+
+        dv.set_group_numbers(true);
+        assert_eq!(dv.cell_text("1234567", 0), "1,234,567");
+        assert_eq!(dv.cell_text("1234567", 1), "1234567");
+    }
+
+    #[test]
+    fn row_number_column_toggles() {
+        let mut dv = DataView::new();
+        assert!(dv.show_row_numbers);
+        dv.handle_key(KeyEvent::from(KeyCode::Char('#')));
+        assert!(!dv.show_row_numbers);
+    }
+
+    #[test]
+    fn zebra_striping_toggles() {
+        let mut dv = DataView::new();
+        assert_eq!(dv.row_style(1).bg, Some(Theme::STRIPE_COLOR));
+        assert_eq!(dv.row_style(0), Theme::table_row_normal());
+
+        dv.handle_key(KeyEvent::from(KeyCode::Char('z')));
+        assert_eq!(dv.row_style(1), Theme::table_row_normal());
+
+        dv.set_stripe_color(Some(Color::Indexed(236)));
+        assert_eq!(dv.row_style(3).bg, Some(Color::Indexed(236)));
+
+        // Toggling back on restores the configured color, not the theme's.
+        dv.handle_key(KeyEvent::from(KeyCode::Char('z')));
+        dv.handle_key(KeyEvent::from(KeyCode::Char('z')));
+        assert_eq!(dv.row_style(3).bg, Some(Color::Indexed(236)));
+    }
+
+    #[test]
+    fn bool_style_replaces_boolean_text_only() {
+        use arrow_array::BooleanArray;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("is_active", DataType::Boolean, true),
+            Field::new("label", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(BooleanArray::from(vec![Some(true), Some(false), None])),
+                Arc::new(StringArray::from(vec!["true", "false", "x"])),
+            ],
+        )
+        .unwrap();
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: vec![batch],
+            total_rows: 3,
+            has_more: false,
+        });
+        assert_eq!(dv.cell_text("true", 0), "true");
+
+        dv.set_bool_style(BoolStyle::Check);
+        assert_eq!(dv.cell_text("true", 0), "✓");
+        assert_eq!(dv.cell_text("false", 0), "✗");
+        assert_eq!(dv.cell_text("true", 1), "true");
+        // The loaded values keep their text for copying and export.
+        assert_eq!(dv.display_rows[0][0].as_deref(), Some("true"));
+
+        dv.set_bool_style(BoolStyle::Dot);
+        assert_eq!(dv.cell_text("false", 0), "○");
+    }
+
+    #[test]
+    fn data_view_right_aligns_numeric_columns() {
+        let mut dv = DataView::new();
+        dv.handle_message(&AppMessage::DataReady {
+            batches: make_test_batches(),
+            total_rows: 3,
+            has_more: false,
+        });
+
+        assert_eq!(dv.display_types, vec![DataType::Int32, DataType::Utf8]);
+        assert_eq!(dv.align_cell("1", 0, 4), "   1");
+        assert_eq!(dv.align_cell("Bob", 1, 7), "Bob");
+
+        dv.set_visible_columns(vec!["name".to_string(), "id".to_string()]);
+        assert_eq!(dv.align_cell("2", 1, 3), "  2");
     }
 
     #[test]
@@ -6,22 +6,26 @@ use ratatui::Frame;
 
 use crate::event::{Action, AppMessage};
 use crate::model::table_info::DataFileInfo;
-use crate::ui::layout::SplitLayout;
+use crate::ui::layout::{self, SplitLayout};
 use crate::ui::theme::Theme;
+use crate::util::format::{format_size, BYTES_PER_MB};
 
 use super::Component;
 
-const BYTES_PER_KB: i64 = 1024;
-const BYTES_PER_MB: i64 = BYTES_PER_KB * 1024;
-const BYTES_PER_GB: i64 = BYTES_PER_MB * 1024;
 const LEFT_PANEL_PERCENT: u16 = 40;
 const BLOCK_CHARS: [char; 8] = ['█', '▉', '▊', '▋', '▌', '▍', '▎', '▏'];
 
 pub struct FileStatsPanel {
+    /// Data files grouped by manifest, kept so single manifests can be replaced.
+    groups: Vec<Vec<DataFileInfo>>,
     files: Vec<DataFileInfo>,
     stats: Option<FileStats>,
     scroll_offset: u16,
     loaded: bool,
+    /// Which snapshot the files belong to, for the block title.
+    snapshot_label: String,
+    /// Share of the width given to the left panel, adjusted with `<` and `>`.
+    left_percent: u16,
 }
 
 struct FileStats {
@@ -50,19 +54,27 @@ struct Bucket {
 impl FileStatsPanel {
     pub fn new() -> Self {
         Self {
+            groups: vec![],
             files: vec![],
             stats: None,
             scroll_offset: 0,
             loaded: false,
+            snapshot_label: String::new(),
+            left_percent: LEFT_PANEL_PERCENT,
         }
     }
 
+    pub fn set_snapshot_label(&mut self, label: String) {
+        self.snapshot_label = label;
+    }
+
     pub fn needs_load(&self) -> bool {
         !self.loaded
     }
 
     pub fn invalidate(&mut self) {
         self.loaded = false;
+        self.groups.clear();
         self.files.clear();
         self.stats = None;
         self.scroll_offset = 0;
@@ -202,18 +214,6 @@ impl FileStatsPanel {
         buckets
     }
 
-    fn format_size(bytes: i64) -> String {
-        if bytes < BYTES_PER_KB {
-            format!("{} B", bytes)
-        } else if bytes < BYTES_PER_MB {
-            format!("{:.1} KB", bytes as f64 / BYTES_PER_KB as f64)
-        } else if bytes < BYTES_PER_GB {
-            format!("{:.1} MB", bytes as f64 / BYTES_PER_MB as f64)
-        } else {
-            format!("{:.1} GB", bytes as f64 / BYTES_PER_GB as f64)
-        }
-    }
-
     fn render_bar(fraction: f64, max_width: u16) -> String {
         if fraction <= 0.0 || max_width == 0 {
             return String::new();
@@ -250,7 +250,7 @@ impl FileStatsPanel {
             ]),
             Line::from(vec![
                 Span::styled("Total size:   ", Theme::label()),
-                Span::styled(Self::format_size(s.total_size), Theme::value()),
+                Span::styled(format_size(s.total_size), Theme::value()),
             ]),
             Line::from(vec![
                 Span::styled("Total rows:   ", Theme::label()),
@@ -261,19 +261,19 @@ impl FileStatsPanel {
             Line::raw(""),
             Line::from(vec![
                 Span::styled("Average:      ", Theme::label()),
-                Span::styled(Self::format_size(s.avg_size as i64), Theme::value()),
+                Span::styled(format_size(s.avg_size as i64), Theme::value()),
             ]),
             Line::from(vec![
                 Span::styled("Median:       ", Theme::label()),
-                Span::styled(Self::format_size(s.median_size), Theme::value()),
+                Span::styled(format_size(s.median_size), Theme::value()),
             ]),
             Line::from(vec![
                 Span::styled("Min:          ", Theme::label()),
-                Span::styled(Self::format_size(s.min_size), Theme::value()),
+                Span::styled(format_size(s.min_size), Theme::value()),
             ]),
             Line::from(vec![
                 Span::styled("Max:          ", Theme::label()),
-                Span::styled(Self::format_size(s.max_size), Theme::value()),
+                Span::styled(format_size(s.max_size), Theme::value()),
             ]),
             Line::raw(""),
             Line::styled("─── Row Count ───", Theme::title()),
@@ -339,6 +339,11 @@ impl FileStatsPanel {
 
 impl Component for FileStatsPanel {
     fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if let Some(percent) = layout::resize_split(self.left_percent, key.code) {
+            self.left_percent = percent;
+            return None;
+        }
+
         match key.code {
             KeyCode::Down | KeyCode::Char('j') => {
                 self.scroll_offset = self.scroll_offset.saturating_add(1);
@@ -353,13 +358,23 @@ impl Component for FileStatsPanel {
     }
 
     fn handle_message(&mut self, msg: &AppMessage) -> Option<Action> {
-        let AppMessage::DataFileStatsReady(grouped) = msg else {
-            return None;
-        };
-        self.files = grouped.iter().flatten().cloned().collect();
+        match msg {
+            AppMessage::DataFileStatsReady(grouped) => {
+                self.groups = grouped.clone();
+                self.scroll_offset = 0;
+            }
+            AppMessage::ManifestListFailed(_) => {
+                self.groups.clear();
+                self.scroll_offset = 0;
+            }
+            AppMessage::ManifestReloaded { index, files, .. } => {
+                *self.groups.get_mut(*index)? = files.clone();
+            }
+            _ => return None,
+        }
+        self.files = self.groups.iter().flatten().cloned().collect();
         self.stats = Some(Self::compute_stats(&self.files));
         self.loaded = true;
-        self.scroll_offset = 0;
         None
     }
 
@@ -378,12 +393,12 @@ impl Component for FileStatsPanel {
             return;
         }
 
-        let split = SplitLayout::new(area, LEFT_PANEL_PERCENT);
+        let split = SplitLayout::new(area, self.left_percent);
 
         let summary_lines = self.build_summary_lines();
         let left_block = Block::default()
             .borders(Borders::ALL)
-            .title(" Summary ")
+            .title(format!(" Summary{} ", self.snapshot_label))
             .border_style(Theme::border_focused());
         let left = Paragraph::new(summary_lines)
             .block(left_block)
@@ -462,12 +477,18 @@ mod tests {
         DataFileInfo {
             file_path: format!("/data/file_{}_{}.parquet", size, rows),
             file_format: "Parquet".into(),
+            content: "Data".into(),
+            equality_ids: vec![],
             record_count: rows,
             file_size_bytes: size,
             null_value_counts: HashMap::new(),
             lower_bounds: HashMap::new(),
             upper_bounds: HashMap::new(),
             partition_data: HashMap::new(),
+            entry_status: "Added".into(),
+            entry_snapshot_id: None,
+            sequence_number: None,
+            file_sequence_number: None,
         }
     }
 
@@ -596,14 +617,6 @@ mod tests {
         assert!((4..=5).contains(&full_blocks));
     }
 
-    #[test]
-    fn format_size_works() {
-        assert_eq!(FileStatsPanel::format_size(500), "500 B");
-        assert_eq!(FileStatsPanel::format_size(1500), "1.5 KB");
-        assert_eq!(FileStatsPanel::format_size(1_500_000), "1.4 MB");
-        assert_eq!(FileStatsPanel::format_size(1_500_000_000), "1.4 GB");
-    }
-
     #[test]
     fn handle_message_flattens_grouped() {
         let mut panel = FileStatsPanel::new();
@@ -9,6 +9,9 @@ use crate::ui::theme::Theme;
 
 use super::Component;
 
+/// Number of submitted filters remembered for Up/Down recall.
+const MAX_HISTORY: usize = 50;
+
 pub struct FilterBar {
     /// Current filter text.
     pub text: String,
@@ -18,6 +21,23 @@ pub struct FilterBar {
     editing: bool,
     /// Last successfully applied filter.
     applied_filter: Option<String>,
+    /// Column names offered by Tab completion.
+    columns: Vec<String>,
+    /// In-progress completion, kept while Tab is pressed repeatedly.
+    completion: Option<Completion>,
+    /// Previously submitted filters, oldest first.
+    history: Vec<String>,
+    /// History entry shown while recalling with Up/Down; `None` shows the draft.
+    history_index: Option<usize>,
+    /// Text being typed before recall started, restored when stepping past the newest entry.
+    draft: String,
+}
+
+/// Candidates for the token being completed and which one is currently inserted.
+struct Completion {
+    start: usize,
+    candidates: Vec<String>,
+    index: usize,
 }
 
 impl FilterBar {
@@ -27,12 +47,112 @@ impl FilterBar {
             cursor: 0,
             editing: false,
             applied_filter: None,
+            columns: vec![],
+            completion: None,
+            history: vec![],
+            history_index: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Remember a submitted filter, skipping an immediate repeat.
+    fn push_history(&mut self, filter: &str) {
+        if self.history.last().map(String::as_str) == Some(filter) {
+            return;
+        }
+        self.history.push(filter.to_string());
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
         }
     }
 
+    /// Step through past filters, older (`step = -1`) or newer (`step = 1`). The draft sits
+    /// after the newest entry and both ends wrap around.
+    fn recall(&mut self, step: isize) {
+        if self.history.is_empty() {
+            return;
+        }
+        let slots = self.history.len() as isize + 1;
+        let current = match self.history_index {
+            Some(i) => i as isize,
+            None => {
+                self.draft = self.text.clone();
+                self.history.len() as isize
+            }
+        };
+        let next = (current + step).rem_euclid(slots) as usize;
+        self.history_index = (next < self.history.len()).then_some(next);
+        self.text = match self.history_index {
+            Some(i) => self.history[i].clone(),
+            None => self.draft.clone(),
+        };
+        self.cursor = self.text.len();
+    }
+
+    pub fn set_columns(&mut self, columns: Vec<String>) {
+        self.columns = columns;
+    }
+
+    /// Complete the column name under the cursor, or cycle to the next (`step = 1`) or
+    /// previous (`step = -1`) candidate when a completion is already in progress.
+    fn complete(&mut self, step: isize) {
+        let completion = match self.completion.take() {
+            Some(mut c) => {
+                let len = c.candidates.len() as isize;
+                c.index = (c.index as isize + step).rem_euclid(len) as usize;
+                c
+            }
+            None => {
+                let start = self.text[..self.cursor]
+                    .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                    .map_or(0, |i| i + 1);
+                let prefix = self.text[start..self.cursor].to_lowercase();
+                let candidates: Vec<String> = self
+                    .columns
+                    .iter()
+                    .filter(|c| c.to_lowercase().starts_with(&prefix))
+                    .cloned()
+                    .collect();
+                if candidates.is_empty() {
+                    return;
+                }
+                let index = if step < 0 { candidates.len() - 1 } else { 0 };
+                Completion {
+                    start,
+                    candidates,
+                    index,
+                }
+            }
+        };
+
+        let candidate = &completion.candidates[completion.index];
+        self.text
+            .replace_range(completion.start..self.cursor, candidate);
+        self.cursor = completion.start + candidate.len();
+        self.completion = Some(completion);
+    }
+
     pub fn start_editing(&mut self) {
         self.editing = true;
+        self.history_index = None;
+        self.cursor = self.text.len();
+    }
+
+    /// Apply `filter` as if it had been typed and submitted, e.g. from `--filter`.
+    pub fn set_applied_filter(&mut self, filter: String) {
+        self.push_history(&filter);
+        self.text = filter.clone();
         self.cursor = self.text.len();
+        self.applied_filter = Some(filter);
+    }
+
+    /// Drop the applied filter and any text being edited; history is kept.
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+        self.applied_filter = None;
+        self.completion = None;
+        self.history_index = None;
     }
 
     pub fn applied_filter(&self) -> Option<&str> {
@@ -46,6 +166,30 @@ impl Component for FilterBar {
             return None;
         }
 
+        match key.code {
+            KeyCode::Tab => {
+                self.complete(1);
+                return None;
+            }
+            KeyCode::BackTab => {
+                self.complete(-1);
+                return None;
+            }
+            _ => self.completion = None,
+        }
+
+        match key.code {
+            KeyCode::Up => {
+                self.recall(-1);
+                return None;
+            }
+            KeyCode::Down => {
+                self.recall(1);
+                return None;
+            }
+            _ => self.history_index = None,
+        }
+
         match key.code {
             KeyCode::Enter => {
                 self.editing = false;
@@ -54,6 +198,7 @@ impl Component for FilterBar {
                     self.applied_filter = None;
                     return Some(Action::SubmitFilter(String::new()));
                 }
+                self.push_history(&filter_text);
                 self.applied_filter = Some(filter_text.clone());
                 Some(Action::SubmitFilter(filter_text))
             }
@@ -160,6 +305,19 @@ mod tests {
         assert!(bar.applied_filter().is_none());
     }
 
+    #[test]
+    fn preset_filter_is_applied_and_editable() {
+        let mut bar = FilterBar::new();
+        bar.set_applied_filter("price > 100".into());
+        assert_eq!(bar.applied_filter(), Some("price > 100"));
+
+        bar.start_editing();
+        bar.handle_key(key(KeyCode::Char('0')));
+        let action = bar.handle_key(key(KeyCode::Enter));
+        assert!(matches!(action, Some(Action::SubmitFilter(ref s)) if s == "price > 1000"));
+        assert_eq!(bar.history, ["price > 100", "price > 1000"]);
+    }
+
     #[test]
     fn typing_in_filter() {
         let mut bar = FilterBar::new();
@@ -212,6 +370,62 @@ mod tests {
         assert_eq!(bar.cursor, 2);
     }
 
+    fn editing_bar(text: &str) -> FilterBar {
+        let mut bar = FilterBar::new();
+        bar.set_columns(vec!["id".into(), "department".into(), "dept_code".into()]);
+        bar.text = text.to_string();
+        bar.start_editing();
+        bar
+    }
+
+    #[test]
+    fn tab_completes_unique_prefix() {
+        let mut bar = editing_bar("i");
+        bar.handle_key(key(KeyCode::Tab));
+        assert_eq!(bar.text, "id");
+        assert_eq!(bar.cursor, 2);
+    }
+
+    #[test]
+    fn repeated_tab_cycles_candidates() {
+        let mut bar = editing_bar("age > 3 AND DE");
+        bar.handle_key(key(KeyCode::Tab));
+        assert_eq!(bar.text, "age > 3 AND department");
+        bar.handle_key(key(KeyCode::Tab));
+        assert_eq!(bar.text, "age > 3 AND dept_code");
+        bar.handle_key(key(KeyCode::Tab));
+        assert_eq!(bar.text, "age > 3 AND department");
+        bar.handle_key(key(KeyCode::BackTab));
+        assert_eq!(bar.text, "age > 3 AND dept_code");
+    }
+
+    #[test]
+    fn typing_resets_completion() {
+        let mut bar = editing_bar("de");
+        bar.handle_key(key(KeyCode::Tab));
+        bar.handle_key(key(KeyCode::Char(' ')));
+        bar.handle_key(key(KeyCode::Tab));
+        // Empty token after the space: cycling starts over from the first column.
+        assert_eq!(bar.text, "department id");
+    }
+
+    #[test]
+    fn tab_without_match_is_noop() {
+        let mut bar = editing_bar("zz");
+        bar.handle_key(key(KeyCode::Tab));
+        assert_eq!(bar.text, "zz");
+        assert!(bar.is_input_mode());
+    }
+
+    #[test]
+    fn tab_ignored_when_not_editing() {
+        let mut bar = FilterBar::new();
+        bar.set_columns(vec!["id".into()]);
+        bar.text = "i".into();
+        bar.handle_key(key(KeyCode::Tab));
+        assert_eq!(bar.text, "i");
+    }
+
     #[test]
     fn is_input_mode_when_editing() {
         let mut bar = FilterBar::new();
@@ -219,4 +433,61 @@ mod tests {
         bar.start_editing();
         assert!(bar.is_input_mode());
     }
+
+    fn submit(bar: &mut FilterBar, text: &str) {
+        bar.start_editing();
+        bar.text = text.to_string();
+        bar.handle_key(key(KeyCode::Enter));
+    }
+
+    #[test]
+    fn up_down_recall_previous_filters() {
+        let mut bar = FilterBar::new();
+        submit(&mut bar, "a > 1");
+        submit(&mut bar, "b = 'x'");
+
+        bar.start_editing();
+        bar.text = "draft".into();
+        bar.handle_key(key(KeyCode::Up));
+        assert_eq!(bar.text, "b = 'x'");
+        assert_eq!(bar.cursor, bar.text.len());
+        bar.handle_key(key(KeyCode::Up));
+        assert_eq!(bar.text, "a > 1");
+        bar.handle_key(key(KeyCode::Down));
+        assert_eq!(bar.text, "b = 'x'");
+        bar.handle_key(key(KeyCode::Down));
+        assert_eq!(bar.text, "draft");
+    }
+
+    #[test]
+    fn history_recall_wraps_around() {
+        let mut bar = FilterBar::new();
+        submit(&mut bar, "a > 1");
+        submit(&mut bar, "b > 2");
+
+        bar.start_editing();
+        bar.text.clear();
+        for expected in ["b > 2", "a > 1", "", "b > 2"] {
+            bar.handle_key(key(KeyCode::Up));
+            assert_eq!(bar.text, expected);
+        }
+        bar.handle_key(key(KeyCode::Down));
+        bar.handle_key(key(KeyCode::Down));
+        assert_eq!(bar.text, "a > 1");
+    }
+
+    #[test]
+    fn history_skips_repeats_and_empty_filters() {
+        let mut bar = FilterBar::new();
+        submit(&mut bar, "a > 1");
+        submit(&mut bar, "a > 1");
+        submit(&mut bar, "  ");
+        assert_eq!(bar.history, vec!["a > 1".to_string()]);
+
+        let mut empty = FilterBar::new();
+        empty.start_editing();
+        empty.text = "typed".into();
+        empty.handle_key(key(KeyCode::Up));
+        assert_eq!(empty.text, "typed");
+    }
 }
@@ -10,7 +10,8 @@ use crate::ui::theme::Theme;
 use super::Component;
 
 const POPUP_WIDTH: u16 = 68;
-const POPUP_HEIGHT: u16 = 22;
+/// Title, blank lines, footer and borders around the keybinding list.
+const POPUP_CHROME_HEIGHT: u16 = 6;
 const POPUP_MARGIN: u16 = 4;
 
 pub struct HelpPopup {
@@ -28,7 +29,8 @@ impl HelpPopup {
 
     fn popup_area(area: Rect) -> Rect {
         let width = POPUP_WIDTH.min(area.width.saturating_sub(POPUP_MARGIN));
-        let height = POPUP_HEIGHT.min(area.height.saturating_sub(POPUP_MARGIN));
+        let content_height = Self::keybindings().len() as u16 + POPUP_CHROME_HEIGHT;
+        let height = content_height.min(area.height.saturating_sub(POPUP_MARGIN));
         let x = (area.width.saturating_sub(width)) / 2;
         let y = (area.height.saturating_sub(height)) / 2;
         Rect::new(area.x + x, area.y + y, width, height)
@@ -40,19 +42,48 @@ impl HelpPopup {
                 "1-6",
                 "Switch tab (Data/Schema/Files/Props/Stats/Snapshots)",
             ),
-            ("q", "Quit"),
+            ("[ / ]", "Previous / next tab"),
+            ("q / Ctrl-C", "Quit"),
             ("?", "Toggle this help"),
-            ("Tab / Shift+Tab", "Cycle focus between panels"),
+            ("Tab / Shift+Tab", "Switch pane within tab (schema, files)"),
+            ("< / >", "Narrow / widen the left panel (split tabs)"),
             ("j/k or Up/Down", "Navigate within panel"),
             ("h/l or Left/Right", "Horizontal scroll (data)"),
             ("g / G", "Jump to top / bottom"),
             ("PgUp / PgDn", "Page up / down"),
             ("/", "Focus filter bar (data tab)"),
+            ("Tab (in filter)", "Complete column name; repeat to cycle"),
+            ("Up/Down (in filter)", "Recall previous filters"),
+            #[cfg(feature = "sql")]
+            ("S", "Run a SQL query over the table as `t` (data tab)"),
             ("c", "Open column selector (data tab)"),
+            ("|", "Jump to a column by name; Tab completes (data tab)"),
+            ("f", "Find rows as you type; Tab for next match (data tab)"),
+            ("*", "Show / mask --redact columns (data tab)"),
+            ("#", "Toggle row numbers (data tab)"),
+            ("o", "Mark optional columns with ? (data tab)"),
+            ("z", "Toggle alternate-row shading (data tab)"),
+            ("x", "Explain scan plan for current filter (data tab)"),
+            ("s", "Stats and histogram of the leftmost column (data tab)"),
             ("Enter", "Expand / select / time-travel (snapshots)"),
+            ("Space", "Collapse / expand nested field (schema tab)"),
+            ("a", "Toggle Arrow / iceberg field types (schema tab)"),
+            (":", "Go to snapshot by id (snapshots tab)"),
+            ("u", "Hide / show unreachable snapshots (snapshots tab)"),
+            ("t", "Limit snapshots to a time range (snapshots tab)"),
             ("Esc", "Cancel / close popup"),
-            ("r", "Reload (preserves snapshot selection)"),
+            ("Ctrl+R", "Reset columns, filter and snapshot"),
+            ("r", "Reload data and manifests (preserves snapshot)"),
             ("m", "Increase row limit"),
+            (",", "Toggle thousands separators in numbers"),
+            ("R", "Retry failed manifest (files tab)"),
+            ("v", "Show raw manifest entry fields (files tab)"),
+            ("E", "Export data file inventory to CSV (files tab)"),
+            ("e", "Hide / show empty sections (properties tab)"),
+            (
+                "Y",
+                "Copy table location and metadata path (properties tab)",
+            ),
         ]
     }
 }
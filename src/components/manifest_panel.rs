@@ -1,20 +1,36 @@
+use std::cmp::Ordering;
+
 use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::layout::Rect;
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
 use ratatui::Frame;
 
 use crate::event::{Action, AppMessage};
-use crate::model::table_info::{DataFileInfo, ManifestInfo};
-use crate::ui::layout::SplitLayout;
+use crate::model::file_diagnostics::{
+    BoundOrder, DiagnosticsRequest, FileDiagnostics, OVERLAP_THRESHOLD,
+};
+use crate::model::table_info::{DataFileInfo, ManifestInfo, SchemaInfo, TableMetadata};
+use crate::ui::layout::{self, SplitLayout};
 use crate::ui::theme::Theme;
+use crate::util::format::{format_count, format_size};
 
 use super::Component;
 
-const BYTES_PER_KB: i64 = 1024;
-const BYTES_PER_MB: i64 = BYTES_PER_KB * 1024;
-const BYTES_PER_GB: i64 = BYTES_PER_MB * 1024;
 const LEFT_PANEL_PERCENT: u16 = 40;
+/// Columns of the data file inventory export, matching `file_inventory` rows.
+pub const FILE_INVENTORY_HEADER: [&str; 8] = [
+    "file_path",
+    "file_format",
+    "content",
+    "record_count",
+    "file_size_bytes",
+    "partition",
+    "lower_bounds",
+    "upper_bounds",
+];
+/// Duplicate paths listed in the warnings box; the rest are only counted.
+const MAX_LISTED_DUPLICATES: usize = 5;
 
 pub struct ManifestPanel {
     manifests: Vec<ManifestInfo>,
@@ -23,6 +39,111 @@ pub struct ManifestPanel {
     data_file_list_state: ListState,
     focus_left: bool,
     loaded: bool,
+    /// Set when the manifest list itself failed to load.
+    list_error: Option<String>,
+    /// The table's default sort order, or `None` when unsorted.
+    sort_key: Option<SortKey>,
+    /// Show file and row counts with thousands separators.
+    group_numbers: bool,
+    /// Show the raw manifest entry fields of the selected data file.
+    show_entry_fields: bool,
+    /// Current table schema, to name the columns of equality delete keys.
+    schema: Option<SchemaInfo>,
+    /// Which snapshot the manifests belong to, for the block title.
+    snapshot_label: String,
+    /// Overlapping and duplicate files across all manifests, shown below the manifest list.
+    diagnostics: FileDiagnostics,
+    /// Bumped whenever the files or the sort key change, so late results for older ones are
+    /// dropped.
+    diagnostics_generation: u64,
+    /// Set when `diagnostics` is out of date and a new background run is due.
+    diagnostics_stale: bool,
+    /// False for V1 tables, whose manifests all report sequence number 0.
+    has_sequence_numbers: bool,
+    /// Share of the width given to the left panel, adjusted with `<` and `>`.
+    left_percent: u16,
+}
+
+/// The default sort order resolved to column names for display.
+struct SortKey {
+    description: String,
+    /// Field id and name of the leading sort column, when its transform preserves
+    /// order so file bounds can show whether data is clustered.
+    leading: Option<(i32, String)>,
+}
+
+impl SortKey {
+    fn from_metadata(meta: &TableMetadata) -> Option<Self> {
+        let order = meta.default_sort_order()?;
+        let column_name = |id: i32| {
+            meta.current_schema
+                .find_field(id)
+                .map_or(format!("#{}", id), |f| f.name.clone())
+        };
+
+        let description = order
+            .fields
+            .iter()
+            .map(|f| {
+                let name = column_name(f.source_id);
+                let column = if f.transform == "identity" {
+                    name
+                } else {
+                    format!("{}({})", f.transform, name)
+                };
+                let direction = if f.direction.starts_with("Desc") {
+                    "DESC"
+                } else {
+                    "ASC"
+                };
+                format!(
+                    "{} {} NULLS {}",
+                    column,
+                    direction,
+                    f.null_order.to_uppercase()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let leading = order
+            .fields
+            .first()
+            .filter(|f| !f.transform.starts_with("bucket") && f.transform != "void")
+            .map(|f| (f.source_id, column_name(f.source_id)));
+
+        Some(Self {
+            description,
+            leading,
+        })
+    }
+}
+
+/// Sort files by their lower bound on `field_id` and count those whose range starts
+/// before an earlier range ends. Returns `(files with bounds, overlapping files)`.
+fn range_overlaps(files: &[DataFileInfo], field_id: i32) -> (usize, usize) {
+    let mut ranges: Vec<(&str, &str)> = files
+        .iter()
+        .filter_map(|f| {
+            let lower = f.lower_bounds.get(&field_id)?;
+            let upper = f.upper_bounds.get(&field_id)?;
+            Some((lower.as_str(), upper.as_str()))
+        })
+        .collect();
+    let order = BoundOrder::of(ranges.iter().flat_map(|&(lower, upper)| [lower, upper]));
+    ranges.sort_by(|a, b| order.compare(a.0, b.0));
+
+    let mut overlapping = 0;
+    let mut max_upper: Option<&str> = None;
+    for (lower, upper) in &ranges {
+        if max_upper.is_some_and(|m| order.compare(lower, m) == Ordering::Less) {
+            overlapping += 1;
+        }
+        if max_upper.is_none_or(|m| order.compare(upper, m) == Ordering::Greater) {
+            max_upper = Some(upper);
+        }
+    }
+    (ranges.len(), overlapping)
 }
 
 impl ManifestPanel {
@@ -34,19 +155,161 @@ impl ManifestPanel {
             data_file_list_state: ListState::default(),
             focus_left: true,
             loaded: false,
+            list_error: None,
+            sort_key: None,
+            group_numbers: false,
+            show_entry_fields: false,
+            schema: None,
+            snapshot_label: String::new(),
+            diagnostics: FileDiagnostics::default(),
+            diagnostics_generation: 0,
+            diagnostics_stale: false,
+            has_sequence_numbers: true,
+            left_percent: LEFT_PANEL_PERCENT,
         }
     }
 
+    pub fn set_snapshot_label(&mut self, label: String) {
+        self.snapshot_label = label;
+    }
+
+    pub fn set_group_numbers(&mut self, group_numbers: bool) {
+        self.group_numbers = group_numbers;
+    }
+
     pub fn needs_load(&self) -> bool {
         !self.loaded
     }
 
     pub fn invalidate(&mut self) {
         self.loaded = false;
+        self.list_error = None;
         self.manifests.clear();
         self.files_by_manifest.clear();
         self.manifest_list_state = ListState::default();
         self.data_file_list_state = ListState::default();
+        self.diagnostics = FileDiagnostics::default();
+        self.diagnostics_generation += 1;
+        self.diagnostics_stale = false;
+    }
+
+    /// One row per data and delete file across all manifests, for export. Partition values
+    /// and column bounds are `key=value` pairs separated by `;`, with bounds keyed by column
+    /// name.
+    pub fn file_inventory(&self) -> Vec<Vec<String>> {
+        let column = |id: &i32| {
+            self.schema
+                .as_ref()
+                .and_then(|s| s.find_field(*id))
+                .map_or(format!("#{}", id), |f| f.name.clone())
+        };
+        let bounds = |values: &std::collections::HashMap<i32, String>| {
+            let mut ids: Vec<&i32> = values.keys().collect();
+            ids.sort();
+            ids.iter()
+                .map(|id| format!("{}={}", column(id), values[id]))
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+        self.files_by_manifest
+            .iter()
+            .flatten()
+            .map(|df| {
+                let mut partition: Vec<String> = df
+                    .partition_data
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect();
+                partition.sort();
+                vec![
+                    df.file_path.clone(),
+                    df.file_format.clone(),
+                    df.content.clone(),
+                    df.record_count.to_string(),
+                    df.file_size_bytes.to_string(),
+                    partition.join(";"),
+                    bounds(&df.lower_bounds),
+                    bounds(&df.upper_bounds),
+                ]
+            })
+            .collect()
+    }
+
+    /// The `diagnose` run due since the files or the sort key last changed, if any.
+    pub fn take_diagnostics_request(&mut self) -> Option<DiagnosticsRequest> {
+        if !std::mem::take(&mut self.diagnostics_stale) {
+            return None;
+        }
+        Some(DiagnosticsRequest {
+            generation: self.diagnostics_generation,
+            groups: self.files_by_manifest.clone(),
+            key_field: self.key_field(),
+        })
+    }
+
+    fn key_field(&self) -> Option<i32> {
+        self.sort_key
+            .as_ref()
+            .and_then(|k| k.leading.as_ref())
+            .map(|(id, _)| *id)
+    }
+
+    fn mark_diagnostics_stale(&mut self) {
+        self.diagnostics_generation += 1;
+        self.diagnostics_stale = true;
+    }
+
+    /// Warnings about files that overlap on the leading sort column or are referenced more
+    /// than once; empty when the snapshot looks healthy.
+    fn build_diagnostic_lines(&self) -> Vec<Line<'static>> {
+        let d = &self.diagnostics;
+        if d.is_clean() {
+            return vec![];
+        }
+        let mut lines = vec![];
+        if d.overlapping_pairs > 0 {
+            let column = self
+                .sort_key
+                .as_ref()
+                .and_then(|k| k.leading.as_ref())
+                .map_or("", |(_, name)| name.as_str());
+            lines.push(Line::styled(
+                format!(
+                    "{} data files overlap on {} ({} pairs share ≥{:.0}% of their range)",
+                    d.overlapping_files,
+                    column,
+                    d.overlapping_pairs,
+                    OVERLAP_THRESHOLD * 100.0
+                ),
+                Theme::status_warning(),
+            ));
+        }
+        if !d.duplicate_paths.is_empty() {
+            lines.push(Line::styled(
+                format!(
+                    "{} files referenced by more than one live entry",
+                    d.duplicate_paths.len()
+                ),
+                Theme::status_error(),
+            ));
+            for (path, count) in d.duplicate_paths.iter().take(MAX_LISTED_DUPLICATES) {
+                let filename = path.rsplit('/').next().unwrap_or(path);
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {}", filename), Theme::value()),
+                    Span::styled(format!(" ×{}", count), Theme::field_id()),
+                ]));
+            }
+            if d.duplicate_paths.len() > MAX_LISTED_DUPLICATES {
+                lines.push(Line::styled(
+                    format!(
+                        "  … and {} more",
+                        d.duplicate_paths.len() - MAX_LISTED_DUPLICATES
+                    ),
+                    Theme::field_id(),
+                ));
+            }
+        }
+        lines
     }
 
     fn selected_files(&self) -> &[DataFileInfo] {
@@ -84,15 +347,22 @@ impl ManifestPanel {
             .select(if has_files { Some(0) } else { None });
     }
 
-    fn format_size(bytes: i64) -> String {
-        if bytes < BYTES_PER_KB {
-            format!("{} B", bytes)
-        } else if bytes < BYTES_PER_MB {
-            format!("{:.1} KB", bytes as f64 / BYTES_PER_KB as f64)
-        } else if bytes < BYTES_PER_GB {
-            format!("{:.1} MB", bytes as f64 / BYTES_PER_MB as f64)
+    fn selected_manifest(&self) -> Option<&ManifestInfo> {
+        self.manifest_list_state
+            .selected()
+            .and_then(|i| self.manifests.get(i))
+    }
+
+    /// Sequence number and spec id shown after a manifest's name; V1 manifests get the spec id
+    /// only, since their sequence numbers are all 0.
+    fn manifest_ids(&self, manifest: &ManifestInfo) -> String {
+        if self.has_sequence_numbers {
+            format!(
+                "seq={} spec={}",
+                manifest.sequence_number, manifest.partition_spec_id
+            )
         } else {
-            format!("{:.1} GB", bytes as f64 / BYTES_PER_GB as f64)
+            format!("spec={}", manifest.partition_spec_id)
         }
     }
 
@@ -103,6 +373,22 @@ impl ManifestPanel {
                 Theme::status_loading(),
             )];
         }
+        if let Some(ref err) = self.list_error {
+            return vec![
+                Line::styled("Failed to load manifest list", Theme::status_error()),
+                Line::styled(err.as_str(), Theme::value()),
+                Line::raw(""),
+                Line::styled("Press R to retry", Theme::field_id()),
+            ];
+        }
+        if let Some(err) = self.selected_manifest().and_then(|m| m.load_error.as_ref()) {
+            return vec![
+                Line::styled("Failed to load manifest", Theme::status_error()),
+                Line::styled(err.as_str(), Theme::value()),
+                Line::raw(""),
+                Line::styled("Press R to retry this manifest", Theme::field_id()),
+            ];
+        }
         let files = self.selected_files();
         if files.is_empty() {
             return vec![Line::styled("No data files found", Theme::field_id())];
@@ -112,28 +398,83 @@ impl ManifestPanel {
         let total_rows: i64 = files.iter().map(|f| f.record_count).sum();
         let total_size: i64 = files.iter().map(|f| f.file_size_bytes).sum();
 
-        let mut lines = vec![
-            Line::from(vec![
-                Span::styled("Files: ", Theme::label()),
-                Span::styled(total_files.to_string(), Theme::value()),
-                Span::raw("  "),
-                Span::styled("Rows: ", Theme::label()),
-                Span::styled(total_rows.to_string(), Theme::value()),
-                Span::raw("  "),
-                Span::styled("Size: ", Theme::label()),
-                Span::styled(Self::format_size(total_size), Theme::value()),
-            ]),
-            Line::raw(""),
-        ];
+        let mut lines = vec![Line::from(vec![
+            Span::styled("Files: ", Theme::label()),
+            Span::styled(
+                format_count(total_files, self.group_numbers),
+                Theme::value(),
+            ),
+            Span::raw("  "),
+            Span::styled("Rows: ", Theme::label()),
+            Span::styled(format_count(total_rows, self.group_numbers), Theme::value()),
+            Span::raw("  "),
+            Span::styled("Size: ", Theme::label()),
+            Span::styled(format_size(total_size), Theme::value()),
+        ])];
+        lines.extend(self.build_sort_lines(files));
+        lines.push(Line::raw(""));
 
         if let Some(df) = self.selected_data_file() {
-            lines.extend(Self::build_data_file_lines(df));
+            lines.extend(Self::build_data_file_lines(
+                df,
+                self.schema.as_ref(),
+                self.group_numbers,
+                self.show_entry_fields,
+            ));
         }
 
         lines
     }
 
-    fn build_data_file_lines(df: &DataFileInfo) -> Vec<Line<'_>> {
+    /// The table's sort order and whether the selected files' bounds on the leading
+    /// sort column are non-overlapping, i.e. the data is actually clustered.
+    fn build_sort_lines(&self, files: &[DataFileInfo]) -> Vec<Line<'static>> {
+        let Some(ref key) = self.sort_key else {
+            return vec![Line::from(vec![
+                Span::styled("Sort order: ", Theme::label()),
+                Span::styled("unsorted", Theme::field_id()),
+            ])];
+        };
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled("Sort order: ", Theme::label()),
+            Span::styled(key.description.clone(), Theme::value()),
+        ])];
+
+        let Some((field_id, ref name)) = key.leading else {
+            return lines;
+        };
+        let (with_bounds, overlapping) = range_overlaps(files, field_id);
+        let (text, style) = match (with_bounds, overlapping) {
+            (0, _) => (format!("no bounds for {}", name), Theme::field_id()),
+            (n, 0) => (
+                format!(
+                    "{} ranges don't overlap across {} files (clustered)",
+                    name, n
+                ),
+                Theme::value(),
+            ),
+            (n, k) => (
+                format!(
+                    "{} of {} files overlap earlier {} ranges (not clustered)",
+                    k, n, name
+                ),
+                Theme::status_warning(),
+            ),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Clustering: ", Theme::label()),
+            Span::styled(text, style),
+        ]));
+        lines
+    }
+
+    fn build_data_file_lines<'a>(
+        df: &'a DataFileInfo,
+        schema: Option<&SchemaInfo>,
+        grouped: bool,
+        entry_fields: bool,
+    ) -> Vec<Line<'a>> {
         let filename = df
             .file_path
             .rsplit('/')
@@ -149,16 +490,55 @@ impl ManifestPanel {
                 Span::styled("Format: ", Theme::label()),
                 Span::styled(df.file_format.clone(), Theme::value()),
             ]),
+            Line::from(vec![
+                Span::styled("Content: ", Theme::label()),
+                Span::styled(df.content.clone(), Theme::value()),
+            ]),
             Line::from(vec![
                 Span::styled("Records: ", Theme::label()),
-                Span::styled(df.record_count.to_string(), Theme::value()),
+                Span::styled(format_count(df.record_count, grouped), Theme::value()),
             ]),
             Line::from(vec![
                 Span::styled("Size: ", Theme::label()),
-                Span::styled(Self::format_size(df.file_size_bytes), Theme::value()),
+                Span::styled(format_size(df.file_size_bytes), Theme::value()),
             ]),
         ];
 
+        // Rows matching a delete file's values in these columns are deleted from older data.
+        if !df.equality_ids.is_empty() {
+            let columns = df
+                .equality_ids
+                .iter()
+                .map(|&id| {
+                    schema
+                        .and_then(|s| s.find_field(id))
+                        .map_or(format!("#{}", id), |f| f.name.clone())
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(Line::from(vec![
+                Span::styled("Equality delete key: ", Theme::label()),
+                Span::styled(columns, Theme::value()),
+            ]));
+        }
+
+        if entry_fields {
+            let optional = |v: Option<i64>| v.map_or("-".to_string(), |v| v.to_string());
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("─── Manifest Entry ───", Theme::title()));
+            for (label, value) in [
+                ("status", df.entry_status.clone()),
+                ("snapshot_id", optional(df.entry_snapshot_id)),
+                ("sequence_number", optional(df.sequence_number)),
+                ("file_sequence_number", optional(df.file_sequence_number)),
+            ] {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {}: ", label), Theme::label()),
+                    Span::styled(value, Theme::value()),
+                ]));
+            }
+        }
+
         if !df.partition_data.is_empty() {
             lines.push(Line::raw(""));
             lines.push(Line::styled("─── Partition ───", Theme::title()));
@@ -208,8 +588,13 @@ impl ManifestPanel {
 
 impl Component for ManifestPanel {
     fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if let Some(percent) = layout::resize_split(self.left_percent, key.code) {
+            self.left_percent = percent;
+            return None;
+        }
+
         match key.code {
-            KeyCode::Tab => {
+            KeyCode::Tab | KeyCode::BackTab => {
                 self.focus_left = !self.focus_left;
                 None
             }
@@ -225,6 +610,23 @@ impl Component for ManifestPanel {
                 }
                 None
             }
+            KeyCode::Char('v') => {
+                self.show_entry_fields = !self.show_entry_fields;
+                None
+            }
+            KeyCode::Char('E') => self
+                .files_by_manifest
+                .iter()
+                .any(|files| !files.is_empty())
+                .then_some(Action::ExportFiles),
+            KeyCode::Char('R') => {
+                if self.list_error.is_some() {
+                    return Some(Action::RetryManifest(None));
+                }
+                let idx = self.manifest_list_state.selected()?;
+                self.selected_manifest()?.load_error.as_ref()?;
+                Some(Action::RetryManifest(Some(idx)))
+            }
             KeyCode::Down | KeyCode::Char('j') => {
                 let on_left = self.focus_left;
                 let (state, len) = self.active_list();
@@ -241,8 +643,21 @@ impl Component for ManifestPanel {
         }
     }
 
+    fn context_hints(&self) -> &'static str {
+        "Tab:pane v:entry fields E:export R:retry"
+    }
+
     fn handle_message(&mut self, msg: &AppMessage) -> Option<Action> {
         match msg {
+            AppMessage::MetadataReady(meta) => {
+                let key_field = self.key_field();
+                self.sort_key = SortKey::from_metadata(meta);
+                self.schema = Some(meta.current_schema.clone());
+                self.has_sequence_numbers = meta.has_sequence_numbers();
+                if self.key_field() != key_field && !self.files_by_manifest.is_empty() {
+                    self.mark_diagnostics_stale();
+                }
+            }
             AppMessage::ManifestsReady(manifests) => {
                 self.manifests = manifests.clone();
                 self.loaded = true;
@@ -253,6 +668,33 @@ impl Component for ManifestPanel {
             AppMessage::DataFileStatsReady(grouped) => {
                 self.files_by_manifest = grouped.clone();
                 self.reset_data_file_cursor();
+                self.mark_diagnostics_stale();
+            }
+            AppMessage::ManifestListFailed(err) => {
+                self.loaded = true;
+                self.list_error = Some(err.clone());
+            }
+            AppMessage::ManifestReloaded {
+                index,
+                manifest,
+                files,
+            } => {
+                if let Some(slot) = self.manifests.get_mut(*index) {
+                    *slot = manifest.clone();
+                }
+                if let Some(slot) = self.files_by_manifest.get_mut(*index) {
+                    *slot = files.clone();
+                }
+                if self.manifest_list_state.selected() == Some(*index) {
+                    self.reset_data_file_cursor();
+                }
+                self.mark_diagnostics_stale();
+            }
+            AppMessage::FileDiagnosticsReady {
+                generation,
+                diagnostics,
+            } if *generation == self.diagnostics_generation => {
+                self.diagnostics = diagnostics.clone();
             }
             _ => {}
         }
@@ -260,7 +702,7 @@ impl Component for ManifestPanel {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
-        let split = SplitLayout::new(area, LEFT_PANEL_PERCENT);
+        let split = SplitLayout::new(area, self.left_percent);
 
         let items: Vec<ListItem> = self
             .manifests
@@ -273,33 +715,49 @@ impl Component for ManifestPanel {
                 } else {
                     "[data]"
                 };
+                let count = |n: i64| format_count(n, self.group_numbers);
                 let added_files = m.added_data_files_count.unwrap_or(0);
                 let added_rows = m.added_rows_count.unwrap_or(0);
-                let mut stats = format!("+{added_files} files, +{added_rows} rows");
+                let mut stats = format!(
+                    "+{} files, +{} rows",
+                    count(added_files.into()),
+                    count(added_rows)
+                );
 
                 let exist_files = m.existing_data_files_count.unwrap_or(0);
                 let exist_rows = m.existing_rows_count.unwrap_or(0);
                 if exist_files > 0 || exist_rows > 0 {
-                    stats.push_str(&format!(", ={exist_files} files, ={exist_rows} rows"));
+                    stats.push_str(&format!(
+                        ", ={} files, ={} rows",
+                        count(exist_files.into()),
+                        count(exist_rows)
+                    ));
                 }
 
                 let del_files = m.deleted_data_files_count.unwrap_or(0);
                 let del_rows = m.deleted_rows_count.unwrap_or(0);
                 if del_files > 0 || del_rows > 0 {
-                    stats.push_str(&format!(", -{del_files} files, -{del_rows} rows"));
+                    stats.push_str(&format!(
+                        ", -{} files, -{} rows",
+                        count(del_files.into()),
+                        count(del_rows)
+                    ));
                 }
 
+                let marker = if m.load_error.is_some() {
+                    Span::styled("✗ ", Theme::status_error())
+                } else {
+                    Span::raw("")
+                };
                 let line = Line::from(vec![
                     Span::styled(format!("{:>3}. ", i + 1), Theme::field_id()),
+                    marker,
                     Span::styled(format!("{tag} "), Theme::label()),
                     Span::styled(filename, Theme::value()),
                     Span::raw(" "),
                     Span::styled(stats, Theme::field_type()),
                     Span::raw(" "),
-                    Span::styled(
-                        format!("seq={} spec={}", m.sequence_number, m.partition_spec_id),
-                        Theme::field_id(),
-                    ),
+                    Span::styled(self.manifest_ids(m), Theme::field_id()),
                 ]);
                 ListItem::new(line)
             })
@@ -307,7 +765,11 @@ impl Component for ManifestPanel {
 
         let left_block = Block::default()
             .borders(Borders::ALL)
-            .title(format!(" Manifests ({}) ", self.manifests.len()))
+            .title(format!(
+                " Manifests ({}){} ",
+                self.manifests.len(),
+                self.snapshot_label
+            ))
             .border_style(if focused && self.focus_left {
                 Theme::border_focused()
             } else {
@@ -318,7 +780,29 @@ impl Component for ManifestPanel {
             .block(left_block)
             .highlight_style(Theme::table_row_selected());
 
-        frame.render_stateful_widget(list, split.left, &mut self.manifest_list_state);
+        let warnings = self.build_diagnostic_lines();
+        let list_area = if warnings.is_empty() {
+            split.left
+        } else {
+            let [list_area, warnings_area] = Layout::vertical([
+                Constraint::Min(3),
+                Constraint::Length(warnings.len() as u16 + 2),
+            ])
+            .areas(split.left);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(" Warnings ")
+                .border_style(Theme::border_unfocused());
+            frame.render_widget(
+                Paragraph::new(warnings)
+                    .block(block)
+                    .wrap(Wrap { trim: false }),
+                warnings_area,
+            );
+            list_area
+        };
+
+        frame.render_stateful_widget(list, list_area, &mut self.manifest_list_state);
 
         let lines = self.build_right_panel_lines();
 
@@ -342,14 +826,7 @@ impl Component for ManifestPanel {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn format_size_works() {
-        assert_eq!(ManifestPanel::format_size(500), "500 B");
-        assert_eq!(ManifestPanel::format_size(1500), "1.5 KB");
-        assert_eq!(ManifestPanel::format_size(1_500_000), "1.4 MB");
-        assert_eq!(ManifestPanel::format_size(1_500_000_000), "1.4 GB");
-    }
+    use crate::model::file_diagnostics::diagnose;
 
     #[test]
     fn manifest_panel_initial() {
@@ -358,6 +835,15 @@ mod tests {
         assert!(panel.manifests.is_empty());
     }
 
+    /// Run the pending `diagnose` request the way the app does in the background.
+    fn run_diagnostics(panel: &mut ManifestPanel) {
+        let request = panel.take_diagnostics_request().unwrap();
+        panel.handle_message(&AppMessage::FileDiagnosticsReady {
+            generation: request.generation,
+            diagnostics: diagnose(&request.groups, request.key_field),
+        });
+    }
+
     fn make_manifest(
         path: &str,
         content_type: &str,
@@ -377,9 +863,20 @@ mod tests {
             deleted_rows_count: deleted_rows,
             sequence_number: 1,
             partition_spec_id: 0,
+            load_error: None,
         }
     }
 
+    #[test]
+    fn v1_manifests_omit_sequence_number() {
+        let mut panel = ManifestPanel::new();
+        let manifest = make_manifest("/m.avro", "data", None, None, None, None);
+        assert_eq!(panel.manifest_ids(&manifest), "seq=1 spec=0");
+
+        panel.has_sequence_numbers = false;
+        assert_eq!(panel.manifest_ids(&manifest), "spec=0");
+    }
+
     #[test]
     fn manifest_panel_handles_manifests_ready() {
         let mut panel = ManifestPanel::new();
@@ -464,15 +961,74 @@ mod tests {
         DataFileInfo {
             file_path: path.into(),
             file_format: "Parquet".into(),
+            content: "Data".into(),
+            equality_ids: vec![],
             record_count: records,
             file_size_bytes: size,
             null_value_counts: std::collections::HashMap::new(),
             lower_bounds: std::collections::HashMap::new(),
             upper_bounds: std::collections::HashMap::new(),
             partition_data: std::collections::HashMap::new(),
+            entry_status: "Added".into(),
+            entry_snapshot_id: None,
+            sequence_number: None,
+            file_sequence_number: None,
         }
     }
 
+    #[test]
+    fn file_inventory_names_bound_columns() {
+        use crate::model::table_info::FieldInfo;
+
+        let mut panel = ManifestPanel::new();
+        assert_eq!(panel.handle_key(KeyEvent::from(KeyCode::Char('E'))), None);
+
+        panel.schema = Some(SchemaInfo {
+            schema_id: 0,
+            fields: vec![FieldInfo {
+                id: 1,
+                name: "id".into(),
+                field_type: "long".into(),
+                required: true,
+                doc: None,
+                initial_default: None,
+                write_default: None,
+                children: vec![],
+            }],
+        });
+        let mut df = make_data_file("/t/a.parquet", 10, 1000);
+        df.lower_bounds.insert(1, "1".into());
+        df.lower_bounds.insert(7, "a".into());
+        df.upper_bounds.insert(1, "10".into());
+        df.partition_data.insert("region".into(), "eu".into());
+        df.partition_data.insert("day".into(), "2024-01-01".into());
+        panel.handle_message(&AppMessage::DataFileStatsReady(vec![
+            vec![df],
+            vec![make_data_file("/t/b.parquet", 5, 500)],
+        ]));
+
+        assert_eq!(
+            panel.file_inventory(),
+            vec![
+                vec![
+                    "/t/a.parquet",
+                    "Parquet",
+                    "Data",
+                    "10",
+                    "1000",
+                    "day=2024-01-01;region=eu",
+                    "id=1;#7=a",
+                    "id=10",
+                ],
+                vec!["/t/b.parquet", "Parquet", "Data", "5", "500", "", "", ""],
+            ]
+        );
+        assert_eq!(
+            panel.handle_key(KeyEvent::from(KeyCode::Char('E'))),
+            Some(Action::ExportFiles)
+        );
+    }
+
     #[test]
     fn selected_files_follows_manifest_cursor() {
         let mut panel = ManifestPanel::new();
@@ -497,6 +1053,53 @@ mod tests {
         assert_eq!(panel.selected_files()[0].file_path, "/f2.parquet");
     }
 
+    #[test]
+    fn failed_manifest_can_be_retried() {
+        let mut panel = ManifestPanel::new();
+        let mut failed = make_manifest("/m2.avro", "data", Some(2), Some(20), None, None);
+        failed.load_error = Some("connection reset".into());
+        panel.handle_message(&AppMessage::ManifestsReady(vec![
+            make_manifest("/m1.avro", "data", Some(1), Some(10), None, None),
+            failed,
+        ]));
+        panel.handle_message(&AppMessage::DataFileStatsReady(vec![
+            vec![make_data_file("/f1.parquet", 10, 1000)],
+            vec![],
+        ]));
+
+        let retry = KeyEvent::from(KeyCode::Char('R'));
+        assert_eq!(panel.handle_key(retry), None);
+
+        panel.manifest_list_state.select(Some(1));
+        assert_eq!(
+            panel.handle_key(retry),
+            Some(Action::RetryManifest(Some(1)))
+        );
+
+        panel.handle_message(&AppMessage::ManifestReloaded {
+            index: 1,
+            manifest: make_manifest("/m2.avro", "data", Some(2), Some(20), None, None),
+            files: vec![make_data_file("/f2.parquet", 20, 2000)],
+        });
+        assert!(panel.manifests[1].load_error.is_none());
+        assert_eq!(panel.selected_files()[0].file_path, "/f2.parquet");
+        assert_eq!(panel.handle_key(retry), None);
+    }
+
+    #[test]
+    fn manifest_list_failure_offers_full_retry() {
+        let mut panel = ManifestPanel::new();
+        panel.handle_message(&AppMessage::ManifestListFailed("not found".into()));
+        assert!(!panel.needs_load());
+        assert_eq!(
+            panel.handle_key(KeyEvent::from(KeyCode::Char('R'))),
+            Some(Action::RetryManifest(None))
+        );
+
+        panel.invalidate();
+        assert!(panel.list_error.is_none());
+    }
+
     #[test]
     fn manifest_info_with_none_counts() {
         let m = make_manifest("/path/to/m.avro", "data", None, None, None, None);
@@ -505,4 +1108,214 @@ mod tests {
         assert!(m.deleted_data_files_count.is_none());
         assert!(m.deleted_rows_count.is_none());
     }
+
+    #[test]
+    fn v_toggles_manifest_entry_fields() {
+        let mut df = make_data_file("/data/f.parquet", 10, 100);
+        df.entry_snapshot_id = Some(42);
+        df.sequence_number = Some(3);
+        let text = |entry_fields: bool| -> Vec<String> {
+            ManifestPanel::build_data_file_lines(&df, None, false, entry_fields)
+                .iter()
+                .map(|l| l.to_string())
+                .collect()
+        };
+        assert!(!text(false).iter().any(|l| l.contains("Manifest Entry")));
+        let expert = text(true);
+        assert!(expert.contains(&"  status: Added".to_string()));
+        assert!(expert.contains(&"  snapshot_id: 42".to_string()));
+        assert!(expert.contains(&"  file_sequence_number: -".to_string()));
+
+        let mut panel = ManifestPanel::new();
+        panel.handle_key(KeyEvent::from(KeyCode::Char('v')));
+        assert!(panel.show_entry_fields);
+    }
+
+    #[test]
+    fn equality_delete_files_name_their_key_columns() {
+        use crate::model::table_info::FieldInfo;
+
+        let field = |id: i32, name: &str| FieldInfo {
+            id,
+            name: name.into(),
+            field_type: "long".into(),
+            required: true,
+            doc: None,
+            initial_default: None,
+            write_default: None,
+            children: vec![],
+        };
+        let schema = SchemaInfo {
+            schema_id: 0,
+            fields: vec![field(1, "id"), field(2, "region")],
+        };
+        let mut df = make_data_file("/data/eq-deletes.parquet", 3, 100);
+        df.content = "EqualityDeletes".into();
+        df.equality_ids = vec![2, 1, 7];
+
+        let text: Vec<String> =
+            ManifestPanel::build_data_file_lines(&df, Some(&schema), false, false)
+                .iter()
+                .map(|l| l.to_string())
+                .collect();
+        assert!(text.contains(&"Content: EqualityDeletes".to_string()));
+        assert!(text.contains(&"Equality delete key: region, id, #7".to_string()));
+
+        let plain = make_data_file("/data/f.parquet", 3, 100);
+        let text: Vec<String> =
+            ManifestPanel::build_data_file_lines(&plain, Some(&schema), false, false)
+                .iter()
+                .map(|l| l.to_string())
+                .collect();
+        assert!(!text.iter().any(|l| l.starts_with("Equality delete key")));
+    }
+
+    fn file_with_bounds(id_range: (&str, &str)) -> DataFileInfo {
+        let mut df = make_data_file("/data/f.parquet", 10, 100);
+        df.lower_bounds.insert(1, id_range.0.into());
+        df.upper_bounds.insert(1, id_range.1.into());
+        df
+    }
+
+    #[test]
+    fn range_overlaps_detects_clustering() {
+        // Numeric bounds compare as numbers: 9 < 10.
+        let clustered = vec![
+            file_with_bounds(("10", "19")),
+            file_with_bounds(("1", "9")),
+            file_with_bounds(("20", "29")),
+        ];
+        assert_eq!(range_overlaps(&clustered, 1), (3, 0));
+
+        let overlapping = vec![
+            file_with_bounds(("1", "50")),
+            file_with_bounds(("10", "19")),
+            file_with_bounds(("60", "70")),
+        ];
+        assert_eq!(range_overlaps(&overlapping, 1), (3, 1));
+    }
+
+    #[test]
+    fn range_overlaps_ignores_files_without_bounds() {
+        let files = vec![
+            file_with_bounds(("a", "c")),
+            make_data_file("/data/g.parquet", 1, 1),
+        ];
+        assert_eq!(range_overlaps(&files, 1), (1, 0));
+        assert_eq!(range_overlaps(&files, 2), (0, 0));
+    }
+
+    #[test]
+    fn duplicate_paths_are_listed_as_warnings() {
+        let mut panel = ManifestPanel::new();
+        assert!(panel.build_diagnostic_lines().is_empty());
+
+        let files: Vec<DataFileInfo> = (0..MAX_LISTED_DUPLICATES + 1)
+            .map(|i| make_data_file(&format!("/data/f{}.parquet", i), 1, 1))
+            .collect();
+        panel.handle_message(&AppMessage::DataFileStatsReady(vec![files.clone(), files]));
+        run_diagnostics(&mut panel);
+        let text: Vec<String> = panel
+            .build_diagnostic_lines()
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(text[0], "6 files referenced by more than one live entry");
+        assert_eq!(text[1], "  f0.parquet ×2");
+        assert_eq!(text.last().unwrap(), "  … and 1 more");
+    }
+
+    #[test]
+    fn diagnostics_of_outdated_files_are_dropped() {
+        let mut panel = ManifestPanel::new();
+        assert!(panel.take_diagnostics_request().is_none());
+
+        let files = vec![make_data_file("/data/a.parquet", 1, 1)];
+        panel.handle_message(&AppMessage::DataFileStatsReady(vec![files.clone(), files]));
+        let outdated = panel.take_diagnostics_request().unwrap();
+        assert_eq!(outdated.groups.len(), 2);
+        // One request per change.
+        assert!(panel.take_diagnostics_request().is_none());
+
+        panel.invalidate();
+        panel.handle_message(&AppMessage::FileDiagnosticsReady {
+            generation: outdated.generation,
+            diagnostics: diagnose(&outdated.groups, outdated.key_field),
+        });
+        assert!(panel.build_diagnostic_lines().is_empty());
+        assert!(panel.take_diagnostics_request().is_none());
+    }
+
+    #[test]
+    fn sort_key_describes_default_order() {
+        use crate::model::table_info::{FieldInfo, SchemaInfo, SortFieldInfo, SortOrderInfo};
+
+        let field = |id: i32, name: &str| FieldInfo {
+            id,
+            name: name.into(),
+            field_type: "long".into(),
+            required: true,
+            doc: None,
+            initial_default: None,
+            write_default: None,
+            children: vec![],
+        };
+        let sort_field = |source_id: i32, transform: &str, direction: &str| SortFieldInfo {
+            source_id,
+            transform: transform.into(),
+            direction: direction.into(),
+            null_order: "First".into(),
+        };
+        let meta = TableMetadata {
+            location: "/t".into(),
+            metadata_location: None,
+            current_schema: SchemaInfo {
+                schema_id: 0,
+                fields: vec![field(1, "id"), field(2, "ts")],
+            },
+            schemas: vec![],
+            snapshots: vec![],
+            refs: vec![],
+            partition_specs: vec![],
+            default_spec_id: 0,
+            sort_orders: vec![
+                SortOrderInfo {
+                    order_id: 0,
+                    fields: vec![],
+                },
+                SortOrderInfo {
+                    order_id: 1,
+                    fields: vec![
+                        sort_field(1, "identity", "Ascending"),
+                        sort_field(2, "day", "Descending"),
+                    ],
+                },
+            ],
+            default_sort_order_id: 1,
+            statistics: vec![],
+            partition_statistics: vec![],
+            properties: Default::default(),
+            current_snapshot_id: None,
+            format_version: 2,
+            table_uuid: String::new(),
+            last_updated_ms: 0,
+            last_column_id: 0,
+            last_partition_id: 0,
+        };
+
+        let key = SortKey::from_metadata(&meta).unwrap();
+        assert_eq!(
+            key.description,
+            "id ASC NULLS FIRST, day(ts) DESC NULLS FIRST"
+        );
+        assert_eq!(key.leading, Some((1, "id".to_string())));
+
+        let unsorted = TableMetadata {
+            default_sort_order_id: 0,
+            statistics: vec![],
+            partition_statistics: vec![],
+            ..meta
+        };
+        assert!(SortKey::from_metadata(&unsorted).is_none());
+    }
 }
@@ -5,8 +5,13 @@ pub mod filter_bar;
 pub mod help_popup;
 pub mod manifest_panel;
 pub mod properties_panel;
+pub mod recent_picker;
+pub mod scan_plan_popup;
 pub mod schema_panel;
 pub mod snapshot_panel;
+#[cfg(feature = "sql")]
+pub mod sql_prompt;
+pub mod stats_popup;
 pub mod status_bar;
 
 use crossterm::event::KeyEvent;
@@ -30,4 +35,9 @@ pub trait Component {
     fn is_input_mode(&self) -> bool {
         false
     }
+
+    /// The component's most useful keys, shown in the status bar while its tab is active.
+    fn context_hints(&self) -> &'static str {
+        ""
+    }
 }
@@ -7,15 +7,28 @@ use ratatui::Frame;
 use crate::event::{Action, AppMessage};
 use crate::model::table_info::TableMetadata;
 use crate::ui::theme::Theme;
+use crate::util::format::{format_relative, format_timestamp_ms};
 
 use super::Component;
 
 const PAGE_SCROLL_SIZE: u16 = 10;
+/// Narrowest value column before continuation lines fall back to a short indent.
+const MIN_VALUE_WIDTH: usize = 20;
+const FALLBACK_INDENT: usize = 4;
+const MS_PER_HOUR: i64 = 3_600_000;
+/// Current snapshots younger than this are shown as fresh.
+const FRESH_AGE_MS: i64 = MS_PER_HOUR;
+/// Default age at which the current snapshot is shown as stale.
+pub const DEFAULT_STALE_AFTER_HOURS: u64 = 24;
 
 pub struct PropertiesPanel {
     metadata: Option<TableMetadata>,
     selected_snapshot_id: Option<i64>,
     scroll: u16,
+    /// Omit sections that would only say they're empty, e.g. "Unpartitioned".
+    hide_empty: bool,
+    /// Age at which the current snapshot is colored as stale.
+    stale_after_ms: i64,
 }
 
 impl PropertiesPanel {
@@ -24,15 +37,182 @@ impl PropertiesPanel {
             metadata: None,
             selected_snapshot_id: None,
             scroll: 0,
+            hide_empty: false,
+            stale_after_ms: DEFAULT_STALE_AFTER_HOURS as i64 * MS_PER_HOUR,
         }
     }
 
+    pub fn set_hide_empty(&mut self, hide_empty: bool) {
+        self.hide_empty = hide_empty;
+    }
+
+    pub fn set_stale_after_hours(&mut self, hours: u64) {
+        self.stale_after_ms = (hours as i64).saturating_mul(MS_PER_HOUR);
+    }
+
     pub fn set_viewed_snapshot(&mut self, id: Option<i64>) {
         self.selected_snapshot_id = id;
         self.scroll = 0;
     }
 
-    fn build_lines(&self) -> Vec<Line<'_>> {
+    /// Table location and metadata file path, one per line, for pasting elsewhere.
+    fn location_text(&self) -> Option<String> {
+        let meta = self.metadata.as_ref()?;
+        Some(match &meta.metadata_location {
+            Some(path) => format!("{}\n{}", meta.location, path),
+            None => meta.location.clone(),
+        })
+    }
+
+    /// Green while the current snapshot is under an hour old, red once it reaches the stale
+    /// threshold, yellow in between.
+    fn age_style(&self, age_ms: i64) -> ratatui::style::Style {
+        if age_ms < FRESH_AGE_MS.min(self.stale_after_ms) {
+            Theme::age_fresh()
+        } else if age_ms < self.stale_after_ms {
+            Theme::age_recent()
+        } else {
+            Theme::age_stale()
+        }
+    }
+
+    /// How long ago the table last committed: the current snapshot's timestamp, or the
+    /// metadata's last update for tables without snapshots.
+    fn age_line(&self, meta: &TableMetadata, now_ms: i64) -> Line<'static> {
+        let committed_ms = meta
+            .current_snapshot_id
+            .and_then(|id| meta.snapshots.iter().find(|s| s.snapshot_id == id))
+            .map_or(meta.last_updated_ms, |s| s.timestamp_ms);
+        let age_ms = now_ms - committed_ms;
+        Line::from(vec![
+            Span::styled("  Age: ", Theme::label()),
+            Span::styled(format_relative(age_ms), self.age_style(age_ms)),
+        ])
+    }
+
+    /// Split `value` into slices of at most `width` characters.
+    fn wrap_chars(value: &str, width: usize) -> Vec<&str> {
+        let width = width.max(1);
+        let mut chunks = Vec::new();
+        let mut rest = value;
+        while rest.chars().count() > width {
+            let (split, _) = rest.char_indices().nth(width).unwrap_or((rest.len(), ' '));
+            chunks.push(&rest[..split]);
+            rest = &rest[split..];
+        }
+        chunks.push(rest);
+        chunks
+    }
+
+    /// A `key: value` entry whose long value wraps with continuation lines indented under
+    /// the value rather than starting at column 0.
+    fn key_value_lines<'a>(key: &str, value: &'a str, width: usize) -> Vec<Line<'a>> {
+        let prefix = format!("  {}: ", key);
+        let mut indent = prefix.chars().count();
+        if width.saturating_sub(indent) < MIN_VALUE_WIDTH {
+            indent = FALLBACK_INDENT;
+        }
+        let first_width = width.saturating_sub(prefix.chars().count()).max(1);
+        let (first, rest) = match value.char_indices().nth(first_width) {
+            Some((split, _)) => value.split_at(split),
+            None => (value, ""),
+        };
+
+        let mut lines = vec![Line::from(vec![
+            Span::styled(prefix, Theme::label()),
+            Span::styled(first, Theme::value()),
+        ])];
+        if !rest.is_empty() {
+            for chunk in Self::wrap_chars(rest, width.saturating_sub(indent)) {
+                lines.push(Line::from(vec![
+                    Span::raw(" ".repeat(indent)),
+                    Span::styled(chunk, Theme::value()),
+                ]));
+            }
+        }
+        lines
+    }
+
+    /// Rough magnitude such as "1.2M", for estimates where exact digits would mislead.
+    fn format_approx_count(n: u64) -> String {
+        match n {
+            0..=999 => n.to_string(),
+            1_000..=999_999 => format!("{:.1}K", n as f64 / 1e3),
+            1_000_000..=999_999_999 => format!("{:.1}M", n as f64 / 1e6),
+            _ => format!("{:.1}B", n as f64 / 1e9),
+        }
+    }
+
+    fn column_names(meta: &TableMetadata, field_ids: &[i32]) -> String {
+        field_ids
+            .iter()
+            .map(|id| {
+                std::iter::once(&meta.current_schema)
+                    .chain(&meta.schemas)
+                    .find_map(|schema| schema.find_field(*id))
+                    .map_or_else(|| format!("field {}", id), |f| f.name.clone())
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Puffin statistics per snapshot: which columns have NDV estimates and where the blobs live.
+    fn statistics_lines(meta: &TableMetadata) -> Vec<Line<'_>> {
+        if meta.statistics.is_empty() && meta.partition_statistics.is_empty() {
+            return vec![Line::styled("  No statistics files", Theme::field_id())];
+        }
+
+        let mut lines = Vec::new();
+        for file in &meta.statistics {
+            let rows = meta
+                .snapshots
+                .iter()
+                .find(|s| s.snapshot_id == file.snapshot_id)
+                .and_then(|s| s.summary.get("total-records"))
+                .map(|r| format!(" ({} rows)", r))
+                .unwrap_or_default();
+            lines.push(Line::from(vec![Span::styled(
+                format!("  Snapshot {}{}: ", file.snapshot_id, rows),
+                Theme::label(),
+            )]));
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(&file.path, Theme::value()),
+            ]));
+            for blob in &file.blobs {
+                let kind = if blob.blob_type.starts_with("apache-datasketches-theta") {
+                    "theta sketch"
+                } else {
+                    blob.blob_type.as_str()
+                };
+                let estimate = blob
+                    .ndv()
+                    .map(|n| format!(" NDV ≈ {}", Self::format_approx_count(n)))
+                    .unwrap_or_default();
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(
+                        Self::column_names(meta, &blob.field_ids),
+                        Theme::field_name(),
+                    ),
+                    Span::raw(": "),
+                    Span::styled(format!("{}{}", kind, estimate), Theme::field_type()),
+                ]));
+            }
+        }
+        for file in &meta.partition_statistics {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  Partition stats, snapshot {}: ", file.snapshot_id),
+                    Theme::label(),
+                ),
+                Span::styled(&file.path, Theme::value()),
+            ]));
+        }
+        lines
+    }
+
+    fn build_lines(&self, width: usize) -> Vec<Line<'_>> {
         let Some(meta) = &self.metadata else {
             return vec![Line::styled("No metadata loaded", Theme::field_id())];
         };
@@ -52,14 +232,16 @@ impl PropertiesPanel {
                 Span::styled(&meta.location, Theme::value()),
             ]),
             Line::from(vec![
-                Span::styled("  Last Updated: ", Theme::label()),
+                Span::styled("  Metadata File: ", Theme::label()),
                 Span::styled(
-                    chrono::DateTime::from_timestamp_millis(meta.last_updated_ms)
-                        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
-                        .unwrap_or_else(|| format!("{}ms", meta.last_updated_ms)),
+                    meta.metadata_location.as_deref().unwrap_or("-"),
                     Theme::value(),
                 ),
             ]),
+            Line::from(vec![
+                Span::styled("  Last Updated: ", Theme::label()),
+                Span::styled(format_timestamp_ms(meta.last_updated_ms), Theme::value()),
+            ]),
             Line::from(vec![
                 Span::styled("  Current Snapshot: ", Theme::label()),
                 Span::styled(
@@ -68,6 +250,7 @@ impl PropertiesPanel {
                     Theme::value(),
                 ),
             ]),
+            self.age_line(meta, chrono::Utc::now().timestamp_millis()),
             Line::from(vec![
                 Span::styled("  Schemas: ", Theme::label()),
                 Span::styled(meta.schemas.len().to_string(), Theme::value()),
@@ -76,6 +259,14 @@ impl PropertiesPanel {
                 Span::styled("  Snapshots: ", Theme::label()),
                 Span::styled(meta.snapshots.len().to_string(), Theme::value()),
             ]),
+            Line::from(vec![
+                Span::styled("  Last Column ID: ", Theme::label()),
+                Span::styled(meta.last_column_id.to_string(), Theme::value()),
+            ]),
+            Line::from(vec![
+                Span::styled("  Last Partition ID: ", Theme::label()),
+                Span::styled(meta.last_partition_id.to_string(), Theme::value()),
+            ]),
         ];
 
         if let Some(snap_id) = self.selected_snapshot_id {
@@ -93,17 +284,14 @@ impl PropertiesPanel {
                 ]));
                 lines.push(Line::from(vec![
                     Span::styled("  Timestamp: ", Theme::label()),
-                    Span::styled(
-                        chrono::DateTime::from_timestamp_millis(snap.timestamp_ms)
-                            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
-                            .unwrap_or_else(|| format!("{}ms", snap.timestamp_ms)),
-                        Theme::value(),
-                    ),
-                ]));
-                lines.push(Line::from(vec![
-                    Span::styled("  Sequence Number: ", Theme::label()),
-                    Span::styled(snap.sequence_number.to_string(), Theme::value()),
+                    Span::styled(format_timestamp_ms(snap.timestamp_ms), Theme::value()),
                 ]));
+                if meta.has_sequence_numbers() {
+                    lines.push(Line::from(vec![
+                        Span::styled("  Sequence Number: ", Theme::label()),
+                        Span::styled(snap.sequence_number.to_string(), Theme::value()),
+                    ]));
+                }
                 if let Some(parent) = snap.parent_snapshot_id {
                     lines.push(Line::from(vec![
                         Span::styled("  Parent Snapshot: ", Theme::label()),
@@ -122,10 +310,7 @@ impl PropertiesPanel {
                     let mut entries: Vec<_> = snap.summary.iter().collect();
                     entries.sort_by_key(|(k, _)| *k);
                     for (key, val) in entries {
-                        lines.push(Line::from(vec![
-                            Span::styled(format!("  {}: ", key), Theme::label()),
-                            Span::styled(val, Theme::value()),
-                        ]));
+                        lines.extend(Self::key_value_lines(key, val, width));
                     }
                 }
             } else {
@@ -133,68 +318,87 @@ impl PropertiesPanel {
             }
         }
 
-        lines.push(Line::raw(""));
-        lines.push(Line::styled("═══ Partition Spec ═══", Theme::title()));
-        if meta.partition_specs.is_empty() {
-            lines.push(Line::styled("  Unpartitioned", Theme::field_id()));
-        } else {
-            for spec in &meta.partition_specs {
-                lines.push(Line::from(vec![Span::styled(
-                    format!("  Spec {}: ", spec.spec_id),
-                    Theme::label(),
-                )]));
-                for field in &spec.fields {
-                    lines.push(Line::from(vec![
-                        Span::raw("    "),
-                        Span::styled(&field.name, Theme::field_name()),
-                        Span::raw(" = "),
-                        Span::styled(&field.transform, Theme::field_type()),
-                        Span::raw(format!("(source_id={})", field.source_id)),
-                    ]));
+        // An unpartitioned table still has a spec, just one without fields.
+        let unpartitioned = meta.partition_specs.iter().all(|s| s.fields.is_empty());
+        let unsorted = meta.sort_orders.iter().all(|o| o.fields.is_empty());
+        let no_statistics = meta.statistics.is_empty() && meta.partition_statistics.is_empty();
+
+        if !(self.hide_empty && unpartitioned) {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("═══ Partition Spec ═══", Theme::title()));
+            if meta.partition_specs.is_empty() {
+                lines.push(Line::styled("  Unpartitioned", Theme::field_id()));
+            } else {
+                for spec in &meta.partition_specs {
+                    lines.push(Line::from(vec![Span::styled(
+                        format!("  Spec {}: ", spec.spec_id),
+                        Theme::label(),
+                    )]));
+                    for field in &spec.fields {
+                        lines.push(Line::from(vec![
+                            Span::raw("    "),
+                            Span::styled(&field.name, Theme::field_name()),
+                            Span::raw(" = "),
+                            Span::styled(&field.transform, Theme::field_type()),
+                            Span::raw(format!("(source_id={})", field.source_id)),
+                        ]));
+                    }
                 }
             }
         }
 
-        lines.push(Line::raw(""));
-        lines.push(Line::styled("═══ Sort Order ═══", Theme::title()));
-        if meta.sort_orders.is_empty() || meta.sort_orders.iter().all(|o| o.fields.is_empty()) {
-            lines.push(Line::styled("  Unsorted", Theme::field_id()));
-        } else {
-            for order in &meta.sort_orders {
-                if order.fields.is_empty() {
-                    continue;
-                }
-                lines.push(Line::from(vec![Span::styled(
-                    format!("  Order {}: ", order.order_id),
-                    Theme::label(),
-                )]));
-                for field in &order.fields {
-                    lines.push(Line::from(vec![
-                        Span::raw("    "),
-                        Span::styled(
-                            format!("source_id={}", field.source_id),
-                            Theme::field_name(),
-                        ),
-                        Span::raw(" "),
-                        Span::styled(&field.transform, Theme::field_type()),
-                        Span::raw(format!(" {} {}", field.direction, field.null_order)),
-                    ]));
+        if !(self.hide_empty && unsorted) {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("═══ Sort Order ═══", Theme::title()));
+            if unsorted {
+                lines.push(Line::styled("  Unsorted", Theme::field_id()));
+            } else {
+                for order in &meta.sort_orders {
+                    if order.fields.is_empty() {
+                        continue;
+                    }
+                    let default_marker = if order.order_id == meta.default_sort_order_id {
+                        " (default)"
+                    } else {
+                        ""
+                    };
+                    lines.push(Line::from(vec![Span::styled(
+                        format!("  Order {}{}: ", order.order_id, default_marker),
+                        Theme::label(),
+                    )]));
+                    for field in &order.fields {
+                        lines.push(Line::from(vec![
+                            Span::raw("    "),
+                            Span::styled(
+                                format!("source_id={}", field.source_id),
+                                Theme::field_name(),
+                            ),
+                            Span::raw(" "),
+                            Span::styled(&field.transform, Theme::field_type()),
+                            Span::raw(format!(" {} {}", field.direction, field.null_order)),
+                        ]));
+                    }
                 }
             }
         }
 
-        lines.push(Line::raw(""));
-        lines.push(Line::styled("═══ Table Properties ═══", Theme::title()));
-        if meta.properties.is_empty() {
-            lines.push(Line::styled("  No properties set", Theme::field_id()));
-        } else {
-            let mut entries: Vec<_> = meta.properties.iter().collect();
-            entries.sort_by_key(|(k, _)| *k);
-            for (key, val) in entries {
-                lines.push(Line::from(vec![
-                    Span::styled(format!("  {}: ", key), Theme::label()),
-                    Span::styled(val, Theme::value()),
-                ]));
+        if !(self.hide_empty && no_statistics) {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("═══ Statistics Files ═══", Theme::title()));
+            lines.extend(Self::statistics_lines(meta));
+        }
+
+        if !(self.hide_empty && meta.properties.is_empty()) {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("═══ Table Properties ═══", Theme::title()));
+            if meta.properties.is_empty() {
+                lines.push(Line::styled("  No properties set", Theme::field_id()));
+            } else {
+                let mut entries: Vec<_> = meta.properties.iter().collect();
+                entries.sort_by_key(|(k, _)| *k);
+                for (key, val) in entries {
+                    lines.extend(Self::key_value_lines(key, val, width));
+                }
             }
         }
 
@@ -225,10 +429,23 @@ impl Component for PropertiesPanel {
                 self.scroll = 0;
                 None
             }
+            KeyCode::Char('e') => {
+                self.hide_empty = !self.hide_empty;
+                self.scroll = 0;
+                None
+            }
+            KeyCode::Char('Y') => self.location_text().map(|text| Action::CopyToClipboard {
+                text,
+                label: "table location and metadata path".into(),
+            }),
             _ => None,
         }
     }
 
+    fn context_hints(&self) -> &'static str {
+        "e:hide empty Y:copy location"
+    }
+
     fn handle_message(&mut self, msg: &AppMessage) -> Option<Action> {
         if let AppMessage::MetadataReady(metadata) = msg {
             self.metadata = Some(*metadata.clone());
@@ -238,7 +455,8 @@ impl Component for PropertiesPanel {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
-        let lines = self.build_lines();
+        // Leave room for the block's borders.
+        let lines = self.build_lines(area.width.saturating_sub(2) as usize);
 
         let block = Block::default()
             .borders(Borders::ALL)
@@ -264,9 +482,12 @@ mod tests {
     use crate::model::table_info::SnapshotInfo;
     use std::collections::HashMap;
 
+    const TEST_WIDTH: usize = 80;
+
     fn sample_metadata() -> TableMetadata {
         TableMetadata {
             location: "/tmp/test".into(),
+            metadata_location: Some("/tmp/test/metadata/v2.metadata.json".into()),
             current_schema: crate::model::table_info::SchemaInfo {
                 schema_id: 0,
                 fields: vec![],
@@ -297,13 +518,20 @@ mod tests {
                     schema_id: None,
                 },
             ],
+            refs: vec![],
             partition_specs: vec![],
+            default_spec_id: 0,
             sort_orders: vec![],
+            default_sort_order_id: 0,
+            statistics: vec![],
+            partition_statistics: vec![],
             properties: HashMap::new(),
             current_snapshot_id: Some(200),
             format_version: 2,
             table_uuid: "test-uuid".into(),
             last_updated_ms: 1700001000000,
+            last_column_id: 12,
+            last_partition_id: 1000,
         }
     }
 
@@ -315,6 +543,151 @@ mod tests {
         assert_eq!(panel.scroll, 0);
     }
 
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn age_of_current_snapshot_is_colored_by_staleness() {
+        let mut panel = PropertiesPanel::new();
+        let mut meta = sample_metadata();
+        let committed = 1700001000000;
+
+        let line = panel.age_line(&meta, committed + 5 * 60_000);
+        assert_eq!(line_text(&line), "  Age: 5 minutes");
+        assert_eq!(line.spans[1].style, Theme::age_fresh());
+        let line = panel.age_line(&meta, committed + 3 * MS_PER_HOUR);
+        assert_eq!(line.spans[1].style, Theme::age_recent());
+        let line = panel.age_line(&meta, committed + 2 * 24 * MS_PER_HOUR);
+        assert_eq!(line.spans[1].style, Theme::age_stale());
+
+        panel.set_stale_after_hours(72);
+        let line = panel.age_line(&meta, committed + 2 * 24 * MS_PER_HOUR);
+        assert_eq!(line.spans[1].style, Theme::age_recent());
+
+        meta.current_snapshot_id = None;
+        meta.last_updated_ms = committed - 10 * MS_PER_HOUR;
+        let line = panel.age_line(&meta, committed);
+        assert_eq!(line_text(&line), "  Age: 10 hours");
+    }
+
+    #[test]
+    fn long_property_value_wraps_with_hanging_indent() {
+        let value = "x".repeat(200);
+        let lines = PropertiesPanel::key_value_lines("write.metadata.metrics.default", &value, 80);
+        let text: Vec<String> = lines.iter().map(line_text).collect();
+
+        let indent = "  write.metadata.metrics.default: ".len();
+        assert!(text[0].starts_with("  write.metadata.metrics.default: xxx"));
+        assert!(text.iter().all(|l| l.chars().count() <= 80));
+        for line in &text[1..] {
+            assert!(line.starts_with(&" ".repeat(indent)));
+            assert!(line[indent..].starts_with('x'));
+        }
+        let value_chars: usize = text.iter().map(|l| l.matches('x').count()).sum();
+        assert_eq!(value_chars, 200);
+    }
+
+    #[test]
+    fn long_key_falls_back_to_short_indent() {
+        let key = "k".repeat(70);
+        let value = "v".repeat(50);
+        let lines = PropertiesPanel::key_value_lines(&key, &value, 80);
+        let text: Vec<String> = lines.iter().map(line_text).collect();
+        assert!(text[1].starts_with("    v"));
+        assert!(text.iter().all(|l| l.chars().count() <= 80));
+    }
+
+    #[test]
+    fn short_property_value_stays_on_one_line() {
+        let lines = PropertiesPanel::key_value_lines("owner", "alice", 80);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(line_text(&lines[0]), "  owner: alice");
+    }
+
+    #[test]
+    fn wrap_chars_is_char_boundary_safe() {
+        assert_eq!(PropertiesPanel::wrap_chars("äöüß", 3), vec!["äöü", "ß"]);
+        assert_eq!(PropertiesPanel::wrap_chars("", 3), vec![""]);
+    }
+
+    #[test]
+    fn statistics_lines_describe_ndv_blobs() {
+        use crate::model::table_info::{FieldInfo, StatisticsBlobInfo, StatisticsFileInfo};
+
+        let mut meta = sample_metadata();
+        assert_eq!(
+            line_text(&PropertiesPanel::statistics_lines(&meta)[0]),
+            "  No statistics files"
+        );
+
+        meta.current_schema.fields = vec![FieldInfo {
+            id: 1,
+            name: "user_id".into(),
+            field_type: "long".into(),
+            required: true,
+            doc: None,
+            initial_default: None,
+            write_default: None,
+            children: vec![],
+        }];
+        meta.snapshots[1]
+            .summary
+            .insert("total-records".into(), "5000000".into());
+        meta.statistics = vec![StatisticsFileInfo {
+            snapshot_id: 200,
+            path: "/tmp/test/metadata/stats.puffin".into(),
+            blobs: vec![
+                StatisticsBlobInfo {
+                    blob_type: "apache-datasketches-theta-v1".into(),
+                    field_ids: vec![1],
+                    properties: HashMap::from([("ndv".into(), "1234567".into())]),
+                },
+                StatisticsBlobInfo {
+                    blob_type: "custom-v1".into(),
+                    field_ids: vec![9],
+                    properties: HashMap::new(),
+                },
+            ],
+        }];
+
+        let text: Vec<String> = PropertiesPanel::statistics_lines(&meta)
+            .iter()
+            .map(line_text)
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "  Snapshot 200 (5000000 rows): ",
+                "    /tmp/test/metadata/stats.puffin",
+                "    user_id: theta sketch NDV ≈ 1.2M",
+                "    field 9: custom-v1",
+            ]
+        );
+    }
+
+    #[test]
+    fn format_approx_count_scales() {
+        assert_eq!(PropertiesPanel::format_approx_count(999), "999");
+        assert_eq!(PropertiesPanel::format_approx_count(12_345), "12.3K");
+        assert_eq!(PropertiesPanel::format_approx_count(4_000_000_000), "4.0B");
+    }
+
+    #[test]
+    fn copy_location_key() {
+        let mut panel = PropertiesPanel::new();
+        assert_eq!(panel.handle_key(KeyEvent::from(KeyCode::Char('Y'))), None);
+
+        panel.handle_message(&AppMessage::MetadataReady(Box::new(sample_metadata())));
+        assert_eq!(
+            panel.handle_key(KeyEvent::from(KeyCode::Char('Y'))),
+            Some(Action::CopyToClipboard {
+                text: "/tmp/test\n/tmp/test/metadata/v2.metadata.json".into(),
+                label: "table location and metadata path".into(),
+            })
+        );
+    }
+
     #[test]
     fn scroll_navigation() {
         let mut panel = PropertiesPanel::new();
@@ -348,7 +721,7 @@ mod tests {
         let mut panel = PropertiesPanel::new();
         panel.metadata = Some(sample_metadata());
 
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(TEST_WIDTH);
         let text: String = lines
             .iter()
             .map(|l| l.to_string())
@@ -364,7 +737,7 @@ mod tests {
         panel.metadata = Some(sample_metadata());
         panel.set_viewed_snapshot(Some(100));
 
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(TEST_WIDTH);
         let text: String = lines
             .iter()
             .map(|l| l.to_string())
@@ -382,7 +755,7 @@ mod tests {
         panel.metadata = Some(sample_metadata());
         panel.set_viewed_snapshot(Some(200));
 
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(TEST_WIDTH);
         let text: String = lines
             .iter()
             .map(|l| l.to_string())
@@ -394,13 +767,31 @@ mod tests {
         assert!(text.contains("100"));
     }
 
+    #[test]
+    fn build_lines_omit_sequence_number_for_v1() {
+        let mut panel = PropertiesPanel::new();
+        panel.metadata = Some(sample_metadata());
+        panel.set_viewed_snapshot(Some(100));
+        let text = |panel: &PropertiesPanel| -> Vec<String> {
+            panel
+                .build_lines(TEST_WIDTH)
+                .iter()
+                .map(line_text)
+                .collect()
+        };
+        assert!(text(&panel).contains(&"  Sequence Number: 1".to_string()));
+
+        panel.metadata.as_mut().unwrap().format_version = 1;
+        assert!(!text(&panel).iter().any(|l| l.contains("Sequence Number")));
+    }
+
     #[test]
     fn build_lines_snapshot_not_found() {
         let mut panel = PropertiesPanel::new();
         panel.metadata = Some(sample_metadata());
         panel.set_viewed_snapshot(Some(999));
 
-        let lines = panel.build_lines();
+        let lines = panel.build_lines(TEST_WIDTH);
         let text: String = lines
             .iter()
             .map(|l| l.to_string())
@@ -409,4 +800,49 @@ mod tests {
         assert!(text.contains("Snapshot 999"));
         assert!(text.contains("Snapshot not found"));
     }
+
+    #[test]
+    fn hide_empty_omits_sections_without_content() {
+        let mut panel = PropertiesPanel::new();
+        let mut meta = sample_metadata();
+        meta.properties
+            .insert("write.format.default".into(), "parquet".into());
+        panel.metadata = Some(meta);
+
+        let text = |panel: &PropertiesPanel| {
+            panel
+                .build_lines(TEST_WIDTH)
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        assert!(text(&panel).contains("Unpartitioned"));
+        assert!(text(&panel).contains("Unsorted"));
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('e')));
+        let hidden = text(&panel);
+        assert!(!hidden.contains("Partition Spec"));
+        assert!(!hidden.contains("Sort Order"));
+        assert!(!hidden.contains("Statistics Files"));
+        assert!(hidden.contains("Table Properties"));
+        assert!(hidden.contains("General Info"));
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('e')));
+        assert!(text(&panel).contains("Unpartitioned"));
+    }
+
+    #[test]
+    fn build_lines_shows_last_assigned_ids() {
+        let mut panel = PropertiesPanel::new();
+        panel.metadata = Some(sample_metadata());
+
+        let text: Vec<String> = panel
+            .build_lines(TEST_WIDTH)
+            .iter()
+            .map(line_text)
+            .collect();
+        assert!(text.contains(&"  Last Column ID: 12".to_string()));
+        assert!(text.contains(&"  Last Partition ID: 1000".to_string()));
+    }
 }
@@ -0,0 +1,207 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::Frame;
+
+use crate::event::{Action, AppMessage};
+use crate::recents::RecentTable;
+use crate::ui::theme::Theme;
+use crate::util::format::format_relative;
+
+use super::Component;
+
+/// Full-screen list of recently opened tables, shown when icepeek starts without a table.
+pub struct RecentPicker {
+    entries: Vec<RecentTable>,
+    /// Which entries point at local tables that no longer exist.
+    missing: Vec<bool>,
+    list_state: ListState,
+    /// Current time, for the "opened ... ago" column.
+    now_ms: i64,
+}
+
+impl RecentPicker {
+    pub fn new(entries: Vec<RecentTable>, now_ms: i64) -> Self {
+        let missing = entries.iter().map(RecentTable::is_missing).collect();
+        let mut list_state = ListState::default();
+        if !entries.is_empty() {
+            list_state.select(Some(0));
+        }
+        Self {
+            entries,
+            missing,
+            list_state,
+            now_ms,
+        }
+    }
+
+    pub fn entries(&self) -> &[RecentTable] {
+        &self.entries
+    }
+
+    pub fn entry(&self, index: usize) -> Option<&RecentTable> {
+        self.entries.get(index)
+    }
+
+    /// Drop an entry, keeping the selection on the same position.
+    pub fn remove(&mut self, index: usize) {
+        if index >= self.entries.len() {
+            return;
+        }
+        self.entries.remove(index);
+        self.missing.remove(index);
+        let selected = if self.entries.is_empty() {
+            None
+        } else {
+            Some(index.min(self.entries.len() - 1))
+        };
+        self.list_state.select(selected);
+    }
+
+    fn build_item(&self, index: usize) -> ListItem<'static> {
+        let entry = &self.entries[index];
+        let mut spans = vec![Span::styled(entry.label(), Theme::value())];
+        if let Some(profile) = &entry.profile {
+            spans.push(Span::styled(
+                format!("  [{}]", profile),
+                Theme::field_type(),
+            ));
+        }
+        spans.push(Span::styled(
+            format!(
+                "  {} ago",
+                format_relative(self.now_ms - entry.opened_at_ms)
+            ),
+            Theme::label(),
+        ));
+        if self.missing[index] {
+            spans.push(Span::styled("  (missing)", Theme::status_warning()));
+        }
+        ListItem::new(Line::from(spans))
+    }
+}
+
+impl Component for RecentPicker {
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(Action::Quit),
+            KeyCode::Up | KeyCode::Char('k') => {
+                let i = self.list_state.selected()?;
+                if i > 0 {
+                    self.list_state.select(Some(i - 1));
+                }
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let i = self.list_state.selected()?;
+                if i + 1 < self.entries.len() {
+                    self.list_state.select(Some(i + 1));
+                }
+                None
+            }
+            KeyCode::Enter => self.list_state.selected().map(Action::OpenRecent),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                self.list_state.selected().map(Action::RemoveRecent)
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_message(&mut self, _msg: &AppMessage) -> Option<Action> {
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, _focused: bool) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Recent tables (enter=open, d=remove, q=quit) ")
+            .border_style(Theme::border_focused());
+
+        if self.entries.is_empty() {
+            let hint = Paragraph::new(vec![
+                Line::styled("No recently opened tables.", Theme::null_value()),
+                Line::raw(""),
+                Line::styled(
+                    "Open one with `icepeek open PATH` or `icepeek catalog --uri URI --table TABLE`.",
+                    Theme::label(),
+                ),
+            ])
+            .block(block);
+            frame.render_widget(hint, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = (0..self.entries.len())
+            .map(|i| self.build_item(i))
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Theme::table_row_selected());
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recents::RecentSource;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn picker(paths: &[&str]) -> RecentPicker {
+        let entries = paths
+            .iter()
+            .map(|p| RecentTable {
+                source: RecentSource::Path {
+                    path: p.to_string(),
+                },
+                profile: None,
+                opened_at_ms: 0,
+            })
+            .collect();
+        RecentPicker::new(entries, 0)
+    }
+
+    #[test]
+    fn enter_opens_selected_entry() {
+        let mut p = picker(&["s3://b/a", "s3://b/b"]);
+        assert_eq!(
+            p.handle_key(key(KeyCode::Enter)),
+            Some(Action::OpenRecent(0))
+        );
+        p.handle_key(key(KeyCode::Char('j')));
+        p.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(
+            p.handle_key(key(KeyCode::Enter)),
+            Some(Action::OpenRecent(1))
+        );
+        assert_eq!(p.handle_key(key(KeyCode::Char('q'))), Some(Action::Quit));
+    }
+
+    #[test]
+    fn remove_keeps_selection_in_range() {
+        let mut p = picker(&["s3://b/a", "s3://b/b"]);
+        p.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            p.handle_key(key(KeyCode::Char('d'))),
+            Some(Action::RemoveRecent(1))
+        );
+        p.remove(1);
+        assert_eq!(p.entries().len(), 1);
+        assert_eq!(p.list_state.selected(), Some(0));
+
+        p.remove(0);
+        assert!(p.entries().is_empty());
+        assert_eq!(p.handle_key(key(KeyCode::Enter)), None);
+    }
+
+    #[test]
+    fn missing_local_tables_are_marked() {
+        let p = picker(&["/no/such/table", "s3://b/a"]);
+        assert_eq!(p.missing, [true, false]);
+    }
+}
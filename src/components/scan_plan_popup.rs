@@ -0,0 +1,206 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::event::{Action, AppMessage};
+use crate::loader::scan::ScanPlan;
+use crate::ui::theme::Theme;
+
+use super::Component;
+
+const POPUP_WIDTH_PERCENT: u16 = 80;
+const POPUP_HEIGHT_PERCENT: u16 = 70;
+
+/// Popup explaining how the current filter is split between file pruning and row-level residuals.
+pub struct ScanPlanPopup {
+    plan: Option<ScanPlan>,
+    scroll: u16,
+    pub visible: bool,
+}
+
+impl ScanPlanPopup {
+    pub fn new() -> Self {
+        Self {
+            plan: None,
+            scroll: 0,
+            visible: false,
+        }
+    }
+
+    fn popup_area(area: Rect) -> Rect {
+        let width = area.width * POPUP_WIDTH_PERCENT / 100;
+        let height = area.height * POPUP_HEIGHT_PERCENT / 100;
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(area.x + x, area.y + y, width, height)
+    }
+
+    /// Distinct residual predicates with the number of files each applies to.
+    fn residual_groups(plan: &ScanPlan) -> Vec<(String, usize)> {
+        let mut groups: Vec<(String, usize)> = Vec::new();
+        for task in &plan.tasks {
+            let residual = task.residual.clone().unwrap_or_else(|| "(none)".into());
+            match groups.iter_mut().find(|(r, _)| *r == residual) {
+                Some((_, count)) => *count += 1,
+                None => groups.push((residual, 1)),
+            }
+        }
+        groups
+    }
+
+    fn build_lines(plan: &ScanPlan) -> Vec<Line<'_>> {
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("Filter: ", Theme::label()),
+                Span::styled(
+                    plan.filter.clone().unwrap_or_else(|| "(none)".into()),
+                    Theme::value(),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Files: ", Theme::label()),
+                Span::styled(
+                    format!(
+                        "{} scanned of {} ({} pruned by partition and column stats)",
+                        plan.tasks.len(),
+                        plan.total_files,
+                        plan.pruned_files()
+                    ),
+                    Theme::value(),
+                ),
+            ]),
+            Line::raw(""),
+            Line::styled("─── Residual (evaluated per row) ───", Theme::title()),
+        ];
+
+        for (residual, count) in Self::residual_groups(plan) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", residual), Theme::value()),
+                Span::styled(format!("({} files)", count), Theme::field_id()),
+            ]));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("─── Planned Files ───", Theme::title()));
+        for task in &plan.tasks {
+            let filename = task.file_path.rsplit('/').next().unwrap_or_default();
+            let rows = task
+                .record_count
+                .map_or("? rows".to_string(), |r| format!("{} rows", r));
+            let mut spans = vec![
+                Span::styled(format!("  {} ", filename), Theme::value()),
+                Span::styled(rows, Theme::field_type()),
+            ];
+            if task.delete_files > 0 {
+                spans.push(Span::styled(
+                    format!(" +{} delete files", task.delete_files),
+                    Theme::field_id(),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines
+    }
+}
+
+impl Component for ScanPlanPopup {
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('x') | KeyCode::Char('q') => {
+                self.visible = false;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll = self.scroll.saturating_add(1);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_message(&mut self, msg: &AppMessage) -> Option<Action> {
+        if let AppMessage::ScanPlanReady(plan) = msg {
+            self.plan = Some(plan.clone());
+            self.scroll = 0;
+            self.visible = true;
+        }
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, _focused: bool) {
+        if !self.visible {
+            return;
+        }
+        let Some(ref plan) = self.plan else {
+            return;
+        };
+
+        let popup = Self::popup_area(area);
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Scan Plan (x/Esc to close) ")
+            .border_style(Theme::border_focused());
+        let paragraph = Paragraph::new(Self::build_lines(plan))
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+
+        frame.render_widget(paragraph, popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::scan::PlannedTask;
+
+    fn task(path: &str, residual: Option<&str>) -> PlannedTask {
+        PlannedTask {
+            file_path: path.into(),
+            record_count: Some(10),
+            residual: residual.map(String::from),
+            delete_files: 0,
+        }
+    }
+
+    #[test]
+    fn plan_ready_opens_popup() {
+        let mut popup = ScanPlanPopup::new();
+        assert!(!popup.visible);
+        popup.handle_message(&AppMessage::ScanPlanReady(ScanPlan {
+            snapshot_id: Some(1),
+            filter: None,
+            total_files: 1,
+            tasks: vec![task("/a.parquet", None)],
+        }));
+        assert!(popup.visible);
+
+        popup.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert!(!popup.visible);
+    }
+
+    #[test]
+    fn residuals_grouped_by_predicate() {
+        let plan = ScanPlan {
+            snapshot_id: Some(1),
+            filter: Some("age > 30".into()),
+            total_files: 4,
+            tasks: vec![
+                task("/a.parquet", Some("age > 30")),
+                task("/b.parquet", Some("age > 30")),
+                task("/c.parquet", None),
+            ],
+        };
+        assert_eq!(
+            ScanPlanPopup::residual_groups(&plan),
+            vec![("age > 30".to_string(), 2), ("(none)".to_string(), 1)]
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::Rect;
 use ratatui::text::{Line, Span};
@@ -6,7 +8,7 @@ use ratatui::Frame;
 
 use crate::event::{Action, AppMessage};
 use crate::model::table_info::{FieldInfo, SchemaInfo};
-use crate::ui::layout::SplitLayout;
+use crate::ui::layout::{self, SplitLayout};
 use crate::ui::theme::Theme;
 
 use super::Component;
@@ -18,19 +20,31 @@ struct FlatField {
     depth: usize,
     field: FieldInfo,
     has_children: bool,
+    collapsed: bool,
 }
 
 pub struct SchemaPanel {
     schemas: Vec<SchemaInfo>,
     current_schema_id: i32,
     head_schema_id: i32,
-    /// Flattened field list for the current schema.
+    /// Flattened field list for the current schema, without the children of collapsed fields.
     flat_fields: Vec<FlatField>,
+    /// Ids of collapsed nested fields. Field ids are stable across schema versions,
+    /// so collapsing survives switching schemas.
+    collapsed: HashSet<i32>,
     list_state: ListState,
     /// Which schema index in the history list is selected.
     schema_list_state: ListState,
     /// Focus: left (field tree) or right (detail).
     focus_left: bool,
+    /// Which snapshot the shown schema belongs to, for the block title.
+    snapshot_label: String,
+    /// Share of the width given to the left panel, adjusted with `<` and `>`.
+    left_percent: u16,
+    /// Arrow type of each field by schema id and then field id, for the Arrow view.
+    arrow_types: HashMap<i32, HashMap<i32, String>>,
+    /// Show Arrow types in the tree instead of iceberg types.
+    show_arrow: bool,
 }
 
 impl SchemaPanel {
@@ -40,12 +54,21 @@ impl SchemaPanel {
             current_schema_id: 0,
             head_schema_id: 0,
             flat_fields: vec![],
+            collapsed: HashSet::new(),
             list_state: ListState::default(),
             schema_list_state: ListState::default(),
             focus_left: true,
+            snapshot_label: String::new(),
+            left_percent: LEFT_PANEL_PERCENT,
+            arrow_types: HashMap::new(),
+            show_arrow: false,
         }
     }
 
+    pub fn set_snapshot_label(&mut self, label: String) {
+        self.snapshot_label = label;
+    }
+
     pub fn set_viewed_schema(&mut self, schema_id: Option<i32>) {
         let id = schema_id.unwrap_or(self.head_schema_id);
         if id == self.current_schema_id {
@@ -55,37 +78,121 @@ impl SchemaPanel {
         self.rebuild_flat_fields();
     }
 
-    fn flatten_fields(fields: &[FieldInfo], depth: usize) -> Vec<FlatField> {
+    fn flatten_fields(
+        fields: &[FieldInfo],
+        depth: usize,
+        collapsed: &HashSet<i32>,
+    ) -> Vec<FlatField> {
         let mut result = Vec::new();
         for field in fields {
             let has_children = !field.children.is_empty();
+            let is_collapsed = has_children && collapsed.contains(&field.id);
             result.push(FlatField {
                 depth,
                 field: field.clone(),
                 has_children,
+                collapsed: is_collapsed,
             });
-            if has_children {
-                result.extend(Self::flatten_fields(&field.children, depth + 1));
+            if has_children && !is_collapsed {
+                result.extend(Self::flatten_fields(&field.children, depth + 1, collapsed));
             }
         }
         result
     }
 
-    fn rebuild_flat_fields(&mut self) {
-        let Some(schema) = self
-            .schemas
+    fn current_schema(&self) -> Option<&SchemaInfo> {
+        self.schemas
             .iter()
             .find(|s| s.schema_id == self.current_schema_id)
             .or_else(|| self.schemas.first())
-        else {
+    }
+
+    fn rebuild_flat_fields(&mut self) {
+        let Some(schema) = self.current_schema() else {
             return;
         };
-        self.flat_fields = Self::flatten_fields(&schema.fields, 0);
+        self.flat_fields = Self::flatten_fields(&schema.fields, 0, &self.collapsed);
         if !self.flat_fields.is_empty() {
             self.list_state.select(Some(0));
         }
     }
 
+    /// Collapse the selected nested field, or expand it if already collapsed.
+    /// Only the field's descendants move, so the selection stays on it.
+    fn toggle_selected(&mut self) {
+        let Some(ff) = self
+            .list_state
+            .selected()
+            .and_then(|i| self.flat_fields.get(i))
+        else {
+            return;
+        };
+        if !ff.has_children {
+            return;
+        }
+        let id = ff.field.id;
+        if !self.collapsed.remove(&id) {
+            self.collapsed.insert(id);
+        }
+        let selected = self.list_state.selected();
+        self.rebuild_flat_fields();
+        self.list_state.select(selected);
+    }
+
+    /// Number of leaf fields and the deepest nesting level below `fields`.
+    fn leaf_stats(fields: &[FieldInfo]) -> (usize, usize) {
+        let mut leaves = 0;
+        let mut depth = 0;
+        for field in fields {
+            if field.children.is_empty() {
+                leaves += 1;
+                depth = depth.max(1);
+            } else {
+                let (child_leaves, child_depth) = Self::leaf_stats(&field.children);
+                leaves += child_leaves;
+                depth = depth.max(child_depth + 1);
+            }
+        }
+        (leaves, depth)
+    }
+
+    /// Tree title with the number of leaf fields, plus the nesting depth for nested schemas.
+    /// Counts the whole schema, including the children of collapsed fields.
+    fn tree_title(&self) -> String {
+        let (leaves, depth) = self
+            .current_schema()
+            .map_or((0, 0), |s| Self::leaf_stats(&s.fields));
+        let noun = if leaves == 1 { "field" } else { "fields" };
+        if depth > 1 {
+            format!(
+                " Schema (id={}){} · {} {}, depth {} ",
+                self.current_schema_id, self.snapshot_label, leaves, noun, depth
+            )
+        } else {
+            format!(
+                " Schema (id={}){} · {} {} ",
+                self.current_schema_id, self.snapshot_label, leaves, noun
+            )
+        }
+    }
+
+    /// Arrow type `field` is read as under the shown schema, once the types have arrived.
+    fn arrow_type(&self, field: &FieldInfo) -> Option<&str> {
+        self.arrow_types
+            .get(&self.current_schema_id)?
+            .get(&field.id)
+            .map(String::as_str)
+    }
+
+    /// Type shown next to a field in the tree: iceberg's, or Arrow's in the Arrow view.
+    fn tree_type(&self, field: &FieldInfo) -> String {
+        if self.show_arrow {
+            self.arrow_type(field).unwrap_or("?").to_string()
+        } else {
+            field.field_type.clone()
+        }
+    }
+
     fn selected_field(&self) -> Option<&FieldInfo> {
         self.list_state
             .selected()
@@ -96,8 +203,13 @@ impl SchemaPanel {
 
 impl Component for SchemaPanel {
     fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if let Some(percent) = layout::resize_split(self.left_percent, key.code) {
+            self.left_percent = percent;
+            return None;
+        }
+
         match key.code {
-            KeyCode::Tab => {
+            KeyCode::Tab | KeyCode::BackTab => {
                 self.focus_left = !self.focus_left;
                 None
             }
@@ -129,10 +241,15 @@ impl Component for SchemaPanel {
                 }
                 None
             }
+            KeyCode::Char('a') => {
+                self.show_arrow = !self.show_arrow;
+                None
+            }
+            KeyCode::Enter | KeyCode::Char(' ') if self.focus_left => {
+                self.toggle_selected();
+                None
+            }
             KeyCode::Enter => {
-                if self.focus_left {
-                    return None;
-                }
                 let idx = self.schema_list_state.selected()?;
                 let schema = self.schemas.get(idx)?;
                 self.current_schema_id = schema.schema_id;
@@ -143,7 +260,14 @@ impl Component for SchemaPanel {
         }
     }
 
+    fn context_hints(&self) -> &'static str {
+        "Tab:pane Space:collapse a:arrow types"
+    }
+
     fn handle_message(&mut self, msg: &AppMessage) -> Option<Action> {
+        if let AppMessage::ArrowTypesReady(types) = msg {
+            self.arrow_types = types.clone();
+        }
         if let AppMessage::MetadataReady(metadata) = msg {
             self.schemas = metadata.schemas.clone();
             self.head_schema_id = metadata.current_schema.schema_id;
@@ -157,14 +281,18 @@ impl Component for SchemaPanel {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
-        let split = SplitLayout::new(area, LEFT_PANEL_PERCENT);
+        let split = SplitLayout::new(area, self.left_percent);
 
         let items: Vec<ListItem> = self
             .flat_fields
             .iter()
             .map(|ff| {
                 let indent = "  ".repeat(ff.depth);
-                let prefix = if ff.has_children { "▼ " } else { "  " };
+                let prefix = match (ff.has_children, ff.collapsed) {
+                    (true, true) => "▶ ",
+                    (true, false) => "▼ ",
+                    _ => "  ",
+                };
                 let req_marker = if ff.field.required { "" } else { "?" };
 
                 let line = Line::from(vec![
@@ -173,7 +301,7 @@ impl Component for SchemaPanel {
                     Span::styled(&ff.field.name, Theme::field_name()),
                     Span::styled(req_marker, Theme::field_id()),
                     Span::raw(": "),
-                    Span::styled(&ff.field.field_type, Theme::field_type()),
+                    Span::styled(self.tree_type(&ff.field), Theme::field_type()),
                 ]);
                 ListItem::new(line)
             })
@@ -181,7 +309,11 @@ impl Component for SchemaPanel {
 
         let left_block = Block::default()
             .borders(Borders::ALL)
-            .title(format!(" Schema (id={}) ", self.current_schema_id))
+            .title(if self.show_arrow {
+                format!("{}· Arrow types ", self.tree_title())
+            } else {
+                self.tree_title()
+            })
             .border_style(if focused && self.focus_left {
                 Theme::border_focused()
             } else {
@@ -209,6 +341,12 @@ impl Component for SchemaPanel {
                 Span::styled("Type: ", Theme::label()),
                 Span::styled(field.field_type.clone(), Theme::field_type()),
             ]));
+            if let Some(arrow_type) = self.arrow_type(&field) {
+                detail_lines.push(Line::from(vec![
+                    Span::styled("Arrow Type: ", Theme::label()),
+                    Span::styled(arrow_type.to_string(), Theme::field_type()),
+                ]));
+            }
             detail_lines.push(Line::from(vec![
                 Span::styled("Required: ", Theme::label()),
                 Span::styled(field.required.to_string(), Theme::value()),
@@ -219,6 +357,18 @@ impl Component for SchemaPanel {
                     Span::styled(doc.clone(), Theme::value()),
                 ]));
             }
+            if let Some(ref value) = field.initial_default {
+                detail_lines.push(Line::from(vec![
+                    Span::styled("Initial Default: ", Theme::label()),
+                    Span::styled(value.clone(), Theme::value()),
+                ]));
+            }
+            if let Some(ref value) = field.write_default {
+                detail_lines.push(Line::from(vec![
+                    Span::styled("Write Default: ", Theme::label()),
+                    Span::styled(value.clone(), Theme::value()),
+                ]));
+            }
         }
 
         detail_lines.push(Line::raw(""));
@@ -273,6 +423,7 @@ mod tests {
     fn make_metadata() -> Box<TableMetadata> {
         Box::new(TableMetadata {
             location: "/test".into(),
+            metadata_location: None,
             current_schema: SchemaInfo {
                 schema_id: 0,
                 fields: vec![
@@ -282,6 +433,8 @@ mod tests {
                         field_type: "int".into(),
                         required: true,
                         doc: None,
+                        initial_default: None,
+                        write_default: None,
                         children: vec![],
                     },
                     FieldInfo {
@@ -290,12 +443,16 @@ mod tests {
                         field_type: "struct".into(),
                         required: false,
                         doc: Some("nested".into()),
+                        initial_default: None,
+                        write_default: None,
                         children: vec![FieldInfo {
                             id: 3,
                             name: "value".into(),
                             field_type: "string".into(),
                             required: true,
                             doc: None,
+                            initial_default: None,
+                            write_default: None,
                             children: vec![],
                         }],
                     },
@@ -311,6 +468,8 @@ mod tests {
                             field_type: "int".into(),
                             required: true,
                             doc: None,
+                            initial_default: None,
+                            write_default: None,
                             children: vec![],
                         },
                         FieldInfo {
@@ -319,12 +478,16 @@ mod tests {
                             field_type: "struct".into(),
                             required: false,
                             doc: Some("nested".into()),
+                            initial_default: None,
+                            write_default: None,
                             children: vec![FieldInfo {
                                 id: 3,
                                 name: "value".into(),
                                 field_type: "string".into(),
                                 required: true,
                                 doc: None,
+                                initial_default: None,
+                                write_default: None,
                                 children: vec![],
                             }],
                         },
@@ -338,18 +501,27 @@ mod tests {
                         field_type: "long".into(),
                         required: true,
                         doc: None,
+                        initial_default: None,
+                        write_default: None,
                         children: vec![],
                     }],
                 },
             ],
             snapshots: vec![],
+            refs: vec![],
             partition_specs: vec![],
+            default_spec_id: 0,
             sort_orders: vec![],
+            default_sort_order_id: 0,
+            statistics: vec![],
+            partition_statistics: vec![],
             properties: HashMap::new(),
             current_snapshot_id: None,
             format_version: 2,
             table_uuid: "test-uuid".into(),
             last_updated_ms: 0,
+            last_column_id: 0,
+            last_partition_id: 0,
         })
     }
 
@@ -363,6 +535,72 @@ mod tests {
         assert_eq!(panel.current_schema_id, 0);
     }
 
+    #[test]
+    fn arrow_view_shows_converted_types() {
+        let mut panel = SchemaPanel::new();
+        panel.handle_message(&AppMessage::MetadataReady(make_metadata()));
+        let id = panel.flat_fields[0].field.clone();
+        assert_eq!(panel.tree_type(&id), "int");
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('a')));
+        assert_eq!(panel.tree_type(&id), "?");
+
+        panel.handle_message(&AppMessage::ArrowTypesReady(HashMap::from([(
+            0,
+            HashMap::from([(1, "Int32".to_string())]),
+        )])));
+        assert_eq!(panel.tree_type(&id), "Int32");
+        assert_eq!(panel.arrow_type(&id), Some("Int32"));
+
+        panel.set_viewed_schema(Some(1));
+        assert_eq!(panel.arrow_type(&id), None);
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char('a')));
+        assert_eq!(panel.tree_type(&id), "int");
+    }
+
+    #[test]
+    fn title_counts_leaf_fields_and_depth() {
+        let mut panel = SchemaPanel::new();
+        panel.handle_message(&AppMessage::MetadataReady(make_metadata()));
+        assert_eq!(panel.tree_title(), " Schema (id=0) · 2 fields, depth 2 ");
+
+        panel.set_viewed_schema(Some(1));
+        assert_eq!(panel.tree_title(), " Schema (id=1) · 1 field ");
+
+        panel.set_snapshot_label(crate::ui::snapshot_label(Some(42), None));
+        assert_eq!(
+            panel.tree_title(),
+            " Schema (id=1) @ snapshot 42 · 1 field "
+        );
+    }
+
+    #[test]
+    fn collapsing_hides_children() {
+        let mut panel = SchemaPanel::new();
+        panel.handle_message(&AppMessage::MetadataReady(make_metadata()));
+        panel.handle_key(KeyEvent::from(KeyCode::Down));
+
+        panel.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(panel.flat_fields.len(), 2);
+        assert!(panel.flat_fields[1].collapsed);
+        assert_eq!(panel.list_state.selected(), Some(1));
+        assert_eq!(panel.tree_title(), " Schema (id=0) · 2 fields, depth 2 ");
+
+        panel.handle_key(KeyEvent::from(KeyCode::Char(' ')));
+        assert_eq!(panel.flat_fields.len(), 3);
+        assert!(!panel.flat_fields[1].collapsed);
+    }
+
+    #[test]
+    fn toggling_a_leaf_does_nothing() {
+        let mut panel = SchemaPanel::new();
+        panel.handle_message(&AppMessage::MetadataReady(make_metadata()));
+        panel.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(panel.collapsed.is_empty());
+        assert_eq!(panel.flat_fields.len(), 3);
+    }
+
     #[test]
     fn schema_panel_navigation() {
         let mut panel = SchemaPanel::new();
@@ -376,6 +614,16 @@ mod tests {
         assert_eq!(panel.list_state.selected(), Some(1));
     }
 
+    #[test]
+    fn tab_and_back_tab_switch_panes() {
+        let mut panel = SchemaPanel::new();
+        assert!(panel.focus_left);
+        panel.handle_key(KeyEvent::from(KeyCode::Tab));
+        assert!(!panel.focus_left);
+        panel.handle_key(KeyEvent::from(KeyCode::BackTab));
+        assert!(panel.focus_left);
+    }
+
     #[test]
     fn set_viewed_schema_switches_to_snapshot_schema() {
         let mut panel = SchemaPanel::new();
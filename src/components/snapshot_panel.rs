@@ -1,3 +1,5 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::Rect;
 use ratatui::text::{Line, Span};
@@ -5,19 +7,349 @@ use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wra
 use ratatui::Frame;
 
 use crate::event::{Action, AppMessage};
-use crate::model::table_info::SnapshotInfo;
-use crate::ui::layout::SplitLayout;
+use crate::model::table_info::{resolve_snapshot_schema_id, RefInfo, SnapshotInfo};
+use crate::ui::layout::{self, SplitLayout};
 use crate::ui::theme::Theme;
+use crate::util::format::{
+    format_size, format_timestamp_ms, parse_timestamp, parse_timestamp_until,
+};
 
 use super::Component;
 
 const LEFT_PANEL_PERCENT: u16 = 45;
 
+/// Summary keys naming the job that wrote a snapshot, most specific first.
+const JOB_ID_KEYS: [&str; 4] = ["spark.app.id", "app-id", "flink.job-id", "trino_query_id"];
+
+/// Which engine, job and user produced a snapshot, from the provenance keys writers put in
+/// its summary. Only keys that are present produce a row.
+fn provenance(summary: &HashMap<String, String>) -> Vec<(&'static str, String)> {
+    let get = |key: &str| summary.get(key).map(|v| v.trim()).filter(|v| !v.is_empty());
+    let mut rows = Vec::new();
+    match (get("engine-name"), get("engine-version")) {
+        (Some(name), Some(version)) => rows.push(("Engine", format!("{} {}", name, version))),
+        (Some(name), None) => rows.push(("Engine", name.to_string())),
+        _ => {}
+    }
+    if let Some(job) = JOB_ID_KEYS.iter().find_map(|key| get(key)) {
+        rows.push(("Job", job.to_string()));
+    }
+    if let Some(user) = get("trino_user") {
+        rows.push(("User", user.to_string()));
+    }
+    if let Some(version) = get("iceberg-version") {
+        rows.push(("Iceberg", version.to_string()));
+    }
+    rows
+}
+
+/// File and row counts a snapshot added and removed, parsed from its summary.
+/// Writers may omit any key, so every count is optional.
+#[derive(Debug, Default, PartialEq)]
+struct ChangeSummary {
+    added_data_files: Option<i64>,
+    deleted_data_files: Option<i64>,
+    added_delete_files: Option<i64>,
+    removed_delete_files: Option<i64>,
+    added_records: Option<i64>,
+    deleted_records: Option<i64>,
+    added_position_deletes: Option<i64>,
+    removed_position_deletes: Option<i64>,
+    added_equality_deletes: Option<i64>,
+    removed_equality_deletes: Option<i64>,
+    added_files_size: Option<i64>,
+    removed_files_size: Option<i64>,
+    total_files_size: Option<i64>,
+}
+
+impl ChangeSummary {
+    fn from_summary(summary: &HashMap<String, String>) -> Self {
+        let count = |key: &str| summary.get(key).and_then(|v| v.trim().parse::<i64>().ok());
+        // Older writers only report position and equality delete files separately.
+        let either = |key: &str, a: &str, b: &str| {
+            count(key).or_else(|| match (count(a), count(b)) {
+                (None, None) => None,
+                (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+            })
+        };
+        Self {
+            added_data_files: count("added-data-files"),
+            deleted_data_files: count("deleted-data-files"),
+            added_delete_files: either(
+                "added-delete-files",
+                "added-position-delete-files",
+                "added-equality-delete-files",
+            ),
+            removed_delete_files: either(
+                "removed-delete-files",
+                "removed-position-delete-files",
+                "removed-equality-delete-files",
+            ),
+            added_records: count("added-records"),
+            deleted_records: count("deleted-records"),
+            added_position_deletes: count("added-position-deletes"),
+            removed_position_deletes: count("removed-position-deletes"),
+            added_equality_deletes: count("added-equality-deletes"),
+            removed_equality_deletes: count("removed-equality-deletes"),
+            added_files_size: count("added-files-size"),
+            removed_files_size: count("removed-files-size"),
+            total_files_size: count("total-files-size"),
+        }
+    }
+
+    /// Rows added minus rows deleted, ignoring rows masked by delete files.
+    fn net_records(&self) -> Option<i64> {
+        match (self.added_records, self.deleted_records) {
+            (None, None) => None,
+            (added, deleted) => Some(added.unwrap_or(0) - deleted.unwrap_or(0)),
+        }
+    }
+
+    /// Data files added minus data files deleted.
+    fn net_data_files(&self) -> Option<i64> {
+        match (self.added_data_files, self.deleted_data_files) {
+            (None, None) => None,
+            (added, deleted) => Some(added.unwrap_or(0) - deleted.unwrap_or(0)),
+        }
+    }
+
+    fn format_pair(added: Option<i64>, removed: Option<i64>) -> Option<String> {
+        match (added, removed) {
+            (None, None) => None,
+            (added, removed) => Some(format!(
+                "+{} / -{}",
+                added.unwrap_or(0),
+                removed.unwrap_or(0)
+            )),
+        }
+    }
+
+    fn format_size_pair(added: Option<i64>, removed: Option<i64>) -> Option<String> {
+        match (added, removed) {
+            (None, None) => None,
+            (added, removed) => Some(format!(
+                "+{} / -{}",
+                format_size(added.unwrap_or(0)),
+                format_size(removed.unwrap_or(0))
+            )),
+        }
+    }
+
+    /// Label and value rows for the detail pane, skipping counts the writer didn't record.
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        let mut rows = Vec::new();
+        let mut push = |label, value: Option<String>| {
+            if let Some(value) = value {
+                rows.push((label, value));
+            }
+        };
+        push(
+            "Data files",
+            Self::format_pair(self.added_data_files, self.deleted_data_files),
+        );
+        push(
+            "Delete files",
+            Self::format_pair(self.added_delete_files, self.removed_delete_files),
+        );
+        push(
+            "Rows",
+            Self::format_pair(self.added_records, self.deleted_records),
+        );
+        push("Net rows", self.net_records().map(|n| format!("{:+}", n)));
+        push(
+            "File size",
+            Self::format_size_pair(self.added_files_size, self.removed_files_size),
+        );
+        push("Table size", self.total_files_size.map(format_size));
+        push(
+            "Position deletes",
+            Self::format_pair(self.added_position_deletes, self.removed_position_deletes),
+        );
+        push(
+            "Equality deletes",
+            Self::format_pair(self.added_equality_deletes, self.removed_equality_deletes),
+        );
+        rows
+    }
+}
+
+/// Badge after the delta column in the snapshot list, for changes the row delta doesn't show.
+/// `replace` keeps the rows and rewrites files, so compactions show the net change in data
+/// files, as do snapshots whose writer recorded no row counts. Colored by whether the table
+/// grew.
+fn change_badge(snap: &SnapshotInfo) -> Option<(String, ratatui::style::Style)> {
+    let change = ChangeSummary::from_summary(&snap.summary);
+    if snap.operation != "replace" && change.net_records().is_some() {
+        return None;
+    }
+    Some(match change.net_data_files()? {
+        0 => (" (±0 files)".to_string(), Theme::change_none()),
+        n if n > 0 => (format!(" ({:+} files)", n), Theme::change_growth()),
+        n => (format!(" ({} files)", n), Theme::change_shrink()),
+    })
+}
+
+/// Net row change shown in the snapshot list's delta column, colored by whether the table
+/// grew. Snapshots whose writer recorded neither `added-records` nor `deleted-records` show
+/// `-`.
+fn row_delta(snap: &SnapshotInfo) -> (String, ratatui::style::Style) {
+    match ChangeSummary::from_summary(&snap.summary).net_records() {
+        None => ("-".to_string(), Theme::field_id()),
+        Some(0) => ("0".to_string(), Theme::change_none()),
+        Some(n) if n > 0 => (format!("{:+}", n), Theme::change_growth()),
+        Some(n) => (n.to_string(), Theme::change_shrink()),
+    }
+}
+
+/// Snapshots reachable from a branch or tag (or the current snapshot) through parent links.
+/// The rest are dangling: expiring snapshots may remove them at any time.
+fn reachable_snapshots(
+    snapshots: &[SnapshotInfo],
+    refs: &[RefInfo],
+    current_snapshot_id: Option<i64>,
+) -> HashSet<i64> {
+    let parents: HashMap<i64, Option<i64>> = snapshots
+        .iter()
+        .map(|s| (s.snapshot_id, s.parent_snapshot_id))
+        .collect();
+    let mut reachable = HashSet::new();
+    let roots = refs
+        .iter()
+        .map(|r| r.snapshot_id)
+        .chain(current_snapshot_id);
+    for root in roots {
+        let mut cursor = Some(root);
+        while let Some(id) = cursor.filter(|id| parents.contains_key(id)) {
+            if !reachable.insert(id) {
+                break;
+            }
+            cursor = parents[&id];
+        }
+    }
+    reachable
+}
+
+/// Parse a time range typed as `SINCE..UNTIL`, where either end may be left out, or a lone
+/// `SINCE`. A date alone as `UNTIL` includes that whole day. An empty range shows every
+/// snapshot again.
+fn parse_time_range(input: &str) -> Result<(Option<i64>, Option<i64>), String> {
+    let (since, until) = input.split_once("..").unwrap_or((input, ""));
+    let parse = |s: &str, parser: fn(&str) -> Result<i64, String>| {
+        let s = s.trim();
+        (!s.is_empty()).then(|| parser(s)).transpose()
+    };
+    let (since, until) = (
+        parse(since, parse_timestamp)?,
+        parse(until, parse_timestamp_until)?,
+    );
+    if let (Some(since), Some(until)) = (since, until) {
+        if since > until {
+            return Err("the range ends before it starts".into());
+        }
+    }
+    Ok((since, until))
+}
+
+/// Running totals compared by the rollback preview: label, summary key, and whether the
+/// value is a size in bytes.
+const ROLLBACK_TOTALS: [(&str, &str, bool); 4] = [
+    ("Rows", "total-records", false),
+    ("Data files", "total-data-files", false),
+    ("Delete files", "total-delete-files", false),
+    ("Table size", "total-files-size", true),
+];
+
+/// What rolling the table back from `current` to `target` would change, from the snapshots
+/// in between and the running totals in both summaries. Totals either writer omitted are
+/// skipped.
+fn rollback_preview(
+    snapshots: &[SnapshotInfo],
+    current: &SnapshotInfo,
+    target: &SnapshotInfo,
+) -> Vec<(&'static str, String)> {
+    let mut rows = Vec::new();
+
+    // Walk back from the current snapshot; a rollback undoes everything after the target.
+    let mut undone: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut undone_count = 0;
+    let mut cursor = Some(current);
+    while let Some(snap) = cursor.filter(|s| s.snapshot_id != target.snapshot_id) {
+        *undone.entry(snap.operation.as_str()).or_default() += 1;
+        undone_count += 1;
+        cursor = snap
+            .parent_snapshot_id
+            .and_then(|id| snapshots.iter().find(|s| s.snapshot_id == id))
+            .filter(|_| undone_count < snapshots.len());
+    }
+    if cursor.is_some() {
+        let operations = undone
+            .iter()
+            .map(|(op, n)| format!("{} {}", n, op))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let noun = if undone_count == 1 {
+            "snapshot"
+        } else {
+            "snapshots"
+        };
+        rows.push((
+            "Undoes",
+            format!("{} {} ({})", undone_count, noun, operations),
+        ));
+    } else {
+        rows.push((
+            "Undoes",
+            "nothing: not an ancestor of the current snapshot".to_string(),
+        ));
+    }
+
+    let total = |snap: &SnapshotInfo, key: &str| {
+        snap.summary
+            .get(key)
+            .and_then(|v| v.trim().parse::<i64>().ok())
+    };
+    for (label, key, is_size) in ROLLBACK_TOTALS {
+        let (Some(now), Some(then)) = (total(current, key), total(target, key)) else {
+            continue;
+        };
+        let delta = then - now;
+        let value = if is_size {
+            let sign = if delta < 0 { "-" } else { "+" };
+            format!(
+                "{} → {} ({}{})",
+                format_size(now),
+                format_size(then),
+                sign,
+                format_size(delta.abs())
+            )
+        } else {
+            format!("{} → {} ({:+})", now, then, delta)
+        };
+        rows.push((label, value));
+    }
+    rows
+}
+
 pub struct SnapshotPanel {
     snapshots: Vec<SnapshotInfo>,
     current_snapshot_id: Option<i64>,
     viewed_snapshot_id: Option<i64>,
     list_state: ListState,
+    /// Snapshot id being typed into the goto prompt, if the prompt is open.
+    goto_input: Option<String>,
+    goto_error: Option<String>,
+    /// Snapshots on the history of a branch or tag.
+    reachable: HashSet<i64>,
+    /// Hide snapshots that no branch or tag reaches.
+    live_only: bool,
+    /// Commit time range shown, in epoch milliseconds; either end may be open.
+    since: Option<i64>,
+    until: Option<i64>,
+    /// Range being typed into the range prompt, if the prompt is open.
+    range_input: Option<String>,
+    range_error: Option<String>,
+    /// Share of the width given to the left panel, adjusted with `<` and `>`.
+    left_percent: u16,
 }
 
 impl SnapshotPanel {
@@ -27,36 +359,227 @@ impl SnapshotPanel {
             current_snapshot_id: None,
             viewed_snapshot_id: None,
             list_state: ListState::default(),
+            goto_input: None,
+            goto_error: None,
+            reachable: HashSet::new(),
+            live_only: false,
+            since: None,
+            until: None,
+            range_input: None,
+            range_error: None,
+            left_percent: LEFT_PANEL_PERCENT,
         }
     }
 
+    /// Show only snapshots committed within `since..=until`, keeping the selection when it's
+    /// still shown.
+    pub fn set_time_range(&mut self, since: Option<i64>, until: Option<i64>) {
+        let selected = self.selected_snapshot().map(|s| s.snapshot_id);
+        self.since = since;
+        self.until = until;
+        self.reselect(selected);
+    }
+
+    fn in_time_range(&self, snap: &SnapshotInfo) -> bool {
+        self.since.is_none_or(|since| snap.timestamp_ms >= since)
+            && self.until.is_none_or(|until| snap.timestamp_ms <= until)
+    }
+
     pub fn set_viewed_snapshot(&mut self, id: Option<i64>) {
         self.viewed_snapshot_id = id;
     }
 
     pub fn schema_id_for_snapshot(&self, snapshot_id: i64) -> Option<i32> {
+        resolve_snapshot_schema_id(&self.snapshots, snapshot_id)
+    }
+
+    pub fn snapshot(&self, snapshot_id: i64) -> Option<&SnapshotInfo> {
+        self.snapshots.iter().find(|s| s.snapshot_id == snapshot_id)
+    }
+
+    /// Snapshots in the list, newest first: all of them or only reachable ones, within the
+    /// time range.
+    fn shown_snapshots(&self) -> Vec<&SnapshotInfo> {
         self.snapshots
             .iter()
-            .find(|s| s.snapshot_id == snapshot_id)
-            .and_then(|s| s.schema_id)
+            .filter(|s| !self.live_only || self.reachable.contains(&s.snapshot_id))
+            .filter(|s| self.in_time_range(s))
+            .collect()
     }
 
     pub fn selected_snapshot(&self) -> Option<&SnapshotInfo> {
         self.list_state
             .selected()
-            .and_then(|i| self.snapshots.get(i))
+            .and_then(|i| self.shown_snapshots().get(i).copied())
+    }
+
+    /// Select the snapshot with the given id, showing all snapshots if it's hidden.
+    /// Returns false if there is no such snapshot.
+    pub fn select_snapshot_id(&mut self, snapshot_id: i64) -> bool {
+        let Some(snap) = self.snapshot(snapshot_id) else {
+            return false;
+        };
+        if !self.in_time_range(snap) {
+            self.since = None;
+            self.until = None;
+        }
+        if self.live_only && !self.reachable.contains(&snapshot_id) {
+            self.live_only = false;
+        }
+        let position = self
+            .shown_snapshots()
+            .iter()
+            .position(|s| s.snapshot_id == snapshot_id);
+        match position {
+            Some(i) => {
+                self.list_state.select(Some(i));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Switch between all snapshots and reachable ones, keeping the selection when it's
+    /// still shown.
+    fn toggle_live_only(&mut self) {
+        let selected = self.selected_snapshot().map(|s| s.snapshot_id);
+        self.live_only = !self.live_only;
+        self.reselect(selected);
+    }
+
+    /// Select `selected` again after the shown snapshots changed, or the first one when it's
+    /// no longer shown.
+    fn reselect(&mut self, selected: Option<i64>) {
+        let position = selected.and_then(|id| {
+            self.shown_snapshots()
+                .iter()
+                .position(|s| s.snapshot_id == id)
+        });
+        let shown = self.shown_snapshots().len();
+        self.list_state
+            .select(position.or((shown > 0).then_some(0)));
+    }
+
+    fn handle_goto_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let input = self.goto_input.as_mut()?;
+        match key.code {
+            KeyCode::Esc => {
+                self.goto_input = None;
+                self.goto_error = None;
+            }
+            KeyCode::Char(c) if c.is_ascii_digit() || (c == '-' && input.is_empty()) => {
+                input.push(c);
+                self.goto_error = None;
+            }
+            KeyCode::Backspace => {
+                input.pop();
+                self.goto_error = None;
+            }
+            KeyCode::Enter => {
+                let input = input.clone();
+                match input.parse::<i64>() {
+                    Ok(id) if self.select_snapshot_id(id) => {
+                        self.goto_input = None;
+                        self.goto_error = None;
+                    }
+                    _ => self.goto_error = Some(format!("snapshot {} not found", input)),
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn handle_range_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let input = self.range_input.as_mut()?;
+        match key.code {
+            KeyCode::Esc => {
+                self.range_input = None;
+                self.range_error = None;
+            }
+            KeyCode::Char(c) => {
+                input.push(c);
+                self.range_error = None;
+            }
+            KeyCode::Backspace => {
+                input.pop();
+                self.range_error = None;
+            }
+            KeyCode::Enter => match parse_time_range(input) {
+                Ok((since, until)) => {
+                    self.range_input = None;
+                    self.range_error = None;
+                    self.set_time_range(since, until);
+                }
+                Err(e) => self.range_error = Some(e),
+            },
+            _ => {}
+        }
+        None
     }
 
-    pub fn format_timestamp(ms: i64) -> String {
-        chrono::DateTime::from_timestamp_millis(ms)
-            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
-            .unwrap_or_else(|| format!("{}ms", ms))
+    /// The active time range, e.g. "since 2025-01-01 00:00:00 UTC".
+    fn range_label(&self) -> Option<String> {
+        match (self.since, self.until) {
+            (Some(since), Some(until)) => Some(format!(
+                "{} to {}",
+                format_timestamp_ms(since),
+                format_timestamp_ms(until)
+            )),
+            (Some(since), None) => Some(format!("since {}", format_timestamp_ms(since))),
+            (None, Some(until)) => Some(format!("until {}", format_timestamp_ms(until))),
+            (None, None) => None,
+        }
+    }
+
+    fn list_title(&self) -> String {
+        if let Some(input) = &self.range_input {
+            return match &self.range_error {
+                Some(err) => format!(" {} (Esc to cancel) ", err),
+                None => format!(" Range (SINCE..UNTIL): {}█ ", input),
+            };
+        }
+        let name = if self.live_only {
+            "Live snapshots"
+        } else {
+            "Snapshots"
+        };
+        let shown = self.shown_snapshots().len();
+        let count = if !self.live_only && shown == self.snapshots.len() {
+            shown.to_string()
+        } else {
+            format!("{}/{}", shown, self.snapshots.len())
+        };
+        match (&self.goto_input, &self.goto_error) {
+            (Some(_), Some(err)) => format!(" {} (Esc to cancel) ", err),
+            (Some(input), None) => format!(" Go to snapshot: {}█ ", input),
+            (None, _) => match self.range_label() {
+                Some(range) => format!(" {} ({}) · {} ", name, count, range),
+                None => format!(" {} ({}) ", name, count),
+            },
+        }
     }
 }
 
 impl Component for SnapshotPanel {
     fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if self.goto_input.is_some() {
+            return self.handle_goto_key(key);
+        }
+        if self.range_input.is_some() {
+            return self.handle_range_key(key);
+        }
+
+        if let Some(percent) = layout::resize_split(self.left_percent, key.code) {
+            self.left_percent = percent;
+            return None;
+        }
+
         match key.code {
+            KeyCode::Char(':') => {
+                self.goto_input = Some(String::new());
+                None
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 let i = self.list_state.selected().unwrap_or(0);
                 if i > 0 {
@@ -66,11 +589,19 @@ impl Component for SnapshotPanel {
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 let i = self.list_state.selected().unwrap_or(0);
-                if i + 1 < self.snapshots.len() {
+                if i + 1 < self.shown_snapshots().len() {
                     self.list_state.select(Some(i + 1));
                 }
                 None
             }
+            KeyCode::Char('u') => {
+                self.toggle_live_only();
+                None
+            }
+            KeyCode::Char('t') => {
+                self.range_input = Some(String::new());
+                None
+            }
             KeyCode::Enter => self
                 .selected_snapshot()
                 .map(|snap| Action::SelectSnapshot(snap.snapshot_id)),
@@ -78,16 +609,28 @@ impl Component for SnapshotPanel {
         }
     }
 
+    fn context_hints(&self) -> &'static str {
+        "Enter:time travel u:unreachable t:time range"
+    }
+
     fn handle_message(&mut self, msg: &AppMessage) -> Option<Action> {
         if let AppMessage::MetadataReady(metadata) = msg {
             self.snapshots = metadata.snapshots.clone();
+            // V1 sequence numbers are all 0, so ties fall through to the snapshot id to keep the
+            // order stable across reloads.
             self.snapshots.sort_by(|a, b| {
                 b.timestamp_ms
                     .cmp(&a.timestamp_ms)
                     .then(b.sequence_number.cmp(&a.sequence_number))
+                    .then(b.snapshot_id.cmp(&a.snapshot_id))
             });
             self.current_snapshot_id = metadata.current_snapshot_id;
-            if !self.snapshots.is_empty() {
+            self.reachable = reachable_snapshots(
+                &metadata.snapshots,
+                &metadata.refs,
+                metadata.current_snapshot_id,
+            );
+            if !self.shown_snapshots().is_empty() {
                 self.list_state.select(Some(0));
             }
         }
@@ -95,12 +638,21 @@ impl Component for SnapshotPanel {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
-        let split = SplitLayout::new(area, LEFT_PANEL_PERCENT);
+        let split = SplitLayout::new(area, self.left_percent);
 
-        let items: Vec<ListItem> = self
-            .snapshots
+        let shown = self.shown_snapshots();
+        let deltas: Vec<_> = shown.iter().map(|snap| row_delta(snap)).collect();
+        let delta_width = deltas.iter().map(|(d, _)| d.len()).max().unwrap_or(0);
+        let operation_width = shown
             .iter()
-            .map(|snap| {
+            .map(|snap| snap.operation.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let items: Vec<ListItem> = shown
+            .into_iter()
+            .zip(deltas)
+            .map(|(snap, (delta, delta_style))| {
                 let is_current = self.current_snapshot_id == Some(snap.snapshot_id);
                 let is_viewed = self.viewed_snapshot_id == Some(snap.snapshot_id);
                 let marker = match (is_viewed, is_current) {
@@ -108,24 +660,29 @@ impl Component for SnapshotPanel {
                     (false, true) => "▸",
                     _ => " ",
                 };
-                let ts = Self::format_timestamp(snap.timestamp_ms);
-
-                let added = snap
-                    .summary
-                    .get("added-records")
-                    .or_else(|| snap.summary.get("added-data-files"))
-                    .cloned()
-                    .unwrap_or_default();
+                let ts = format_timestamp_ms(snap.timestamp_ms);
 
                 let line = Line::from(vec![
                     Span::raw(format!("{} ", marker)),
-                    Span::styled(snap.operation.clone(), Theme::label()),
+                    Span::styled(
+                        format!("{:<width$}", snap.operation, width = operation_width),
+                        Theme::label(),
+                    ),
                     Span::raw("  "),
                     Span::styled(ts, Theme::value()),
-                    if !added.is_empty() {
-                        Span::styled(format!(" (+{})", added), Theme::field_type())
-                    } else {
+                    Span::raw("  "),
+                    Span::styled(
+                        format!("{:>width$}", delta, width = delta_width),
+                        delta_style,
+                    ),
+                    match change_badge(snap) {
+                        Some((text, style)) => Span::styled(text, style),
+                        None => Span::raw(""),
+                    },
+                    if self.reachable.contains(&snap.snapshot_id) {
                         Span::raw("")
+                    } else {
+                        Span::styled(" unreachable", Theme::field_id())
                     },
                 ]);
                 ListItem::new(line)
@@ -134,7 +691,7 @@ impl Component for SnapshotPanel {
 
         let left_block = Block::default()
             .borders(Borders::ALL)
-            .title(format!(" Snapshots ({}) ", self.snapshots.len()))
+            .title(self.list_title())
             .border_style(if focused {
                 Theme::border_focused()
             } else {
@@ -164,22 +721,82 @@ impl Component for SnapshotPanel {
             ]));
             lines.push(Line::from(vec![
                 Span::styled("Timestamp: ", Theme::label()),
-                Span::styled(Self::format_timestamp(snap.timestamp_ms), Theme::value()),
+                Span::styled(format_timestamp_ms(snap.timestamp_ms), Theme::value()),
             ]));
             lines.push(Line::from(vec![
                 Span::styled("Operation: ", Theme::label()),
                 Span::styled(snap.operation.clone(), Theme::value()),
             ]));
-            if let Some(schema_id) = snap.schema_id {
-                lines.push(Line::from(vec![
+            if let Some(schema_id) = self.schema_id_for_snapshot(snap.snapshot_id) {
+                let mut spans = vec![
                     Span::styled("Schema ID: ", Theme::label()),
                     Span::styled(schema_id.to_string(), Theme::value()),
-                ]));
+                ];
+                if snap.schema_id.is_none() {
+                    spans.push(Span::styled(" (inferred)", Theme::field_id()));
+                }
+                lines.push(Line::from(spans));
             }
             lines.push(Line::from(vec![
                 Span::styled("Manifest List: ", Theme::label()),
                 Span::styled(snap.manifest_list.clone(), Theme::value()),
             ]));
+            if !self.reachable.contains(&snap.snapshot_id) {
+                lines.push(Line::styled(
+                    "Unreachable: no branch or tag leads here, so expiring snapshots may remove it",
+                    Theme::status_warning(),
+                ));
+            }
+
+            let written_by = provenance(&snap.summary);
+            if !written_by.is_empty() {
+                lines.push(Line::raw(""));
+                lines.push(Line::styled("─── Written By ───", Theme::title()));
+                for (label, value) in written_by {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {}: ", label), Theme::label()),
+                        Span::styled(value, Theme::value()),
+                    ]));
+                }
+            }
+
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("─── Changes ───", Theme::title()));
+            let changes = ChangeSummary::from_summary(&snap.summary).rows();
+            if changes.is_empty() {
+                lines.push(Line::styled(
+                    "  No change counts recorded",
+                    Theme::field_id(),
+                ));
+            }
+            for (label, value) in changes {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {}: ", label), Theme::label()),
+                    Span::styled(value, Theme::value()),
+                ]));
+            }
+
+            let current = self
+                .current_snapshot_id
+                .filter(|&id| id != snap.snapshot_id)
+                .and_then(|id| self.snapshot(id));
+            if let Some(current) = current {
+                lines.push(Line::raw(""));
+                lines.push(Line::styled(
+                    "─── Rollback Preview (read-only) ───",
+                    Theme::title(),
+                ));
+                for (label, value) in rollback_preview(&self.snapshots, current, &snap) {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {}: ", label), Theme::label()),
+                        Span::styled(value, Theme::value()),
+                    ]));
+                }
+                lines.push(Line::styled(
+                    "  icepeek never writes; roll back with your engine if this looks right",
+                    Theme::field_id(),
+                ));
+            }
 
             lines.push(Line::raw(""));
             lines.push(Line::styled("─── Summary ───", Theme::title()));
@@ -199,7 +816,7 @@ impl Component for SnapshotPanel {
 
             lines.push(Line::raw(""));
             lines.push(Line::styled(
-                "Press Enter to time-travel to this snapshot",
+                "Press Enter to time-travel to this snapshot, : to go to a snapshot id",
                 Theme::status_key_hint(),
             ));
         } else {
@@ -217,18 +834,16 @@ impl Component for SnapshotPanel {
 
         frame.render_widget(detail, split.right);
     }
+
+    fn is_input_mode(&self) -> bool {
+        self.goto_input.is_some() || self.range_input.is_some()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn format_timestamp_valid() {
-        let ts = SnapshotPanel::format_timestamp(1700000000000);
-        assert!(ts.contains("2023"));
-    }
-
     #[test]
     fn snapshot_panel_initial() {
         let panel = SnapshotPanel::new();
@@ -256,7 +871,7 @@ mod tests {
             sequence_number: 1,
             timestamp_ms: 0,
             operation: "append".into(),
-            summary: std::collections::HashMap::new(),
+            summary: HashMap::new(),
             manifest_list: String::new(),
             schema_id: Some(2),
         }];
@@ -278,10 +893,423 @@ mod tests {
             sequence_number: 1,
             timestamp_ms: 0,
             operation: "append".into(),
-            summary: std::collections::HashMap::new(),
+            summary: HashMap::new(),
             manifest_list: String::new(),
             schema_id: None,
         }];
         assert_eq!(panel.schema_id_for_snapshot(100), None);
     }
+
+    fn snapshot(id: i64) -> SnapshotInfo {
+        SnapshotInfo {
+            snapshot_id: id,
+            parent_snapshot_id: None,
+            sequence_number: 1,
+            timestamp_ms: 0,
+            operation: "append".into(),
+            summary: HashMap::new(),
+            manifest_list: String::new(),
+            schema_id: None,
+        }
+    }
+
+    fn type_keys(panel: &mut SnapshotPanel, keys: &str) {
+        for c in keys.chars() {
+            panel.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn schema_id_for_snapshot_inferred_from_parent() {
+        let mut panel = SnapshotPanel::new();
+        let mut parent = snapshot(100);
+        parent.schema_id = Some(1);
+        let mut child = snapshot(200);
+        child.parent_snapshot_id = Some(100);
+        panel.snapshots = vec![child, parent];
+        assert_eq!(panel.schema_id_for_snapshot(200), Some(1));
+    }
+
+    fn summary(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn provenance_from_spark_summary() {
+        let rows = provenance(&summary(&[
+            ("engine-name", "spark"),
+            ("engine-version", "3.5.1"),
+            ("spark.app.id", "local-1700000000000"),
+            ("app-id", "ignored"),
+            ("iceberg-version", "Apache Iceberg 1.5.0"),
+            ("added-records", "10"),
+        ]));
+        assert_eq!(
+            rows,
+            vec![
+                ("Engine", "spark 3.5.1".to_string()),
+                ("Job", "local-1700000000000".to_string()),
+                ("Iceberg", "Apache Iceberg 1.5.0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn provenance_skips_missing_and_blank_keys() {
+        assert!(provenance(&summary(&[("added-records", "10")])).is_empty());
+        assert_eq!(
+            provenance(&summary(&[
+                ("engine-name", " "),
+                ("trino_query_id", "20240101_000000_00001_abcde"),
+                ("trino_user", "alice"),
+            ])),
+            vec![
+                ("Job", "20240101_000000_00001_abcde".to_string()),
+                ("User", "alice".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn change_summary_for_overwrite() {
+        let changes = ChangeSummary::from_summary(&summary(&[
+            ("added-data-files", "2"),
+            ("deleted-data-files", "3"),
+            ("added-records", "100"),
+            ("deleted-records", "250"),
+            ("total-records", "900"),
+            ("added-files-size", "2576980378"),
+            ("removed-files-size", "1536"),
+            ("total-files-size", "3221225472"),
+        ]));
+        assert_eq!(changes.net_records(), Some(-150));
+        assert_eq!(
+            changes.rows(),
+            vec![
+                ("Data files", "+2 / -3".to_string()),
+                ("Rows", "+100 / -250".to_string()),
+                ("Net rows", "-150".to_string()),
+                ("File size", "+2.4 GB / -1.5 KB".to_string()),
+                ("Table size", "3.0 GB".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn change_summary_sums_split_delete_file_counts() {
+        let changes = ChangeSummary::from_summary(&summary(&[
+            ("added-position-delete-files", "1"),
+            ("added-equality-delete-files", "2"),
+            ("added-position-deletes", "40"),
+        ]));
+        assert_eq!(changes.added_delete_files, Some(3));
+        assert_eq!(
+            changes.rows(),
+            vec![
+                ("Delete files", "+3 / -0".to_string()),
+                ("Position deletes", "+40 / -0".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn change_summary_ignores_missing_and_malformed_keys() {
+        let changes = ChangeSummary::from_summary(&summary(&[
+            ("added-records", "lots"),
+            ("spark.app.id", "x"),
+        ]));
+        assert_eq!(changes, ChangeSummary::default());
+        assert!(changes.rows().is_empty());
+    }
+
+    fn badge(operation: &str, entries: &[(&str, &str)]) -> Option<(String, ratatui::style::Style)> {
+        change_badge(&SnapshotInfo {
+            operation: operation.into(),
+            summary: summary(entries),
+            ..snapshot(1)
+        })
+    }
+
+    #[test]
+    fn row_changes_get_no_badge() {
+        // The delta column already shows these.
+        assert_eq!(
+            badge(
+                "append",
+                &[("added-records", "50"), ("added-data-files", "2")]
+            ),
+            None
+        );
+        assert_eq!(
+            badge(
+                "overwrite",
+                &[("added-records", "100"), ("deleted-records", "250")]
+            ),
+            None
+        );
+        assert_eq!(badge("append", &[]), None);
+    }
+
+    #[test]
+    fn badge_falls_back_to_data_files() {
+        assert_eq!(
+            badge("append", &[("added-data-files", "2")]),
+            Some((" (+2 files)".to_string(), Theme::change_growth()))
+        );
+        assert_eq!(
+            badge("delete", &[("deleted-data-files", "3")]),
+            Some((" (-3 files)".to_string(), Theme::change_shrink()))
+        );
+        assert_eq!(badge("delete", &[]), None);
+    }
+
+    #[test]
+    fn replace_badge_shows_net_data_files() {
+        let compaction = [
+            ("added-data-files", "1"),
+            ("deleted-data-files", "12"),
+            ("added-records", "500"),
+            ("deleted-records", "500"),
+        ];
+        assert_eq!(
+            badge("replace", &compaction),
+            Some((" (-11 files)".to_string(), Theme::change_shrink()))
+        );
+        assert_eq!(
+            badge(
+                "replace",
+                &[("added-data-files", "2"), ("deleted-data-files", "2")]
+            ),
+            Some((" (±0 files)".to_string(), Theme::change_none()))
+        );
+        assert_eq!(
+            badge(
+                "replace",
+                &[("added-records", "500"), ("deleted-records", "500")]
+            ),
+            None
+        );
+    }
+
+    fn delta(entries: &[(&str, &str)]) -> (String, ratatui::style::Style) {
+        row_delta(&SnapshotInfo {
+            summary: summary(entries),
+            ..snapshot(1)
+        })
+    }
+
+    #[test]
+    fn row_delta_nets_added_and_deleted_records() {
+        assert_eq!(
+            delta(&[("added-records", "50"), ("added-data-files", "2")]),
+            ("+50".to_string(), Theme::change_growth())
+        );
+        assert_eq!(
+            delta(&[("added-records", "100"), ("deleted-records", "250")]),
+            ("-150".to_string(), Theme::change_shrink())
+        );
+        assert_eq!(
+            delta(&[("deleted-records", "40")]),
+            ("-40".to_string(), Theme::change_shrink())
+        );
+        assert_eq!(
+            delta(&[("added-records", "500"), ("deleted-records", "500")]),
+            ("0".to_string(), Theme::change_none())
+        );
+    }
+
+    #[test]
+    fn row_delta_without_record_counts_is_a_dash() {
+        assert_eq!(
+            delta(&[("added-data-files", "2")]),
+            ("-".to_string(), Theme::field_id())
+        );
+        assert_eq!(delta(&[]), ("-".to_string(), Theme::field_id()));
+    }
+
+    fn branch(name: &str, snapshot_id: i64) -> RefInfo {
+        RefInfo {
+            name: name.into(),
+            snapshot_id,
+        }
+    }
+
+    #[test]
+    fn reachability_follows_refs_through_parents() {
+        // 1 <- 2 <- 3 (main), 2 <- 4 (audit), 5 dangling after its branch was dropped.
+        let snapshots = vec![
+            snapshot(1),
+            child(2, 1, "append", &[]),
+            child(3, 2, "append", &[]),
+            child(4, 2, "overwrite", &[]),
+            child(5, 3, "append", &[]),
+        ];
+        let reachable = reachable_snapshots(&snapshots, &[branch("audit", 4)], Some(3));
+        assert_eq!(reachable, HashSet::from([1, 2, 3, 4]));
+
+        assert_eq!(reachable_snapshots(&snapshots, &[], None), HashSet::new());
+    }
+
+    #[test]
+    fn live_only_toggle_hides_unreachable_snapshots() {
+        let mut panel = SnapshotPanel::new();
+        panel.snapshots = vec![child(3, 1, "append", &[]), snapshot(2), snapshot(1)];
+        panel.reachable = reachable_snapshots(&panel.snapshots, &[], Some(3));
+        panel.list_state.select(Some(2));
+
+        type_keys(&mut panel, "u");
+        assert!(panel.live_only);
+        assert_eq!(panel.shown_snapshots().len(), 2);
+        assert_eq!(panel.selected_snapshot().unwrap().snapshot_id, 1);
+        assert_eq!(panel.list_title(), " Live snapshots (2/3) ");
+
+        assert!(panel.select_snapshot_id(2));
+        assert!(!panel.live_only);
+        assert_eq!(panel.selected_snapshot().unwrap().snapshot_id, 2);
+    }
+
+    #[test]
+    fn time_range_prompt_narrows_the_list() {
+        let at = |day: &str| parse_timestamp(day).unwrap();
+        let mut panel = SnapshotPanel::new();
+        panel.snapshots = [(3, "2025-03-01"), (2, "2025-02-01"), (1, "2025-01-01")]
+            .into_iter()
+            .map(|(id, day)| SnapshotInfo {
+                timestamp_ms: at(day),
+                ..snapshot(id)
+            })
+            .collect();
+        panel.list_state.select(Some(1));
+
+        type_keys(&mut panel, "t2025-01-15..");
+        assert!(panel.is_input_mode());
+        panel.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(!panel.is_input_mode());
+        assert_eq!(panel.shown_snapshots().len(), 2);
+        assert_eq!(panel.selected_snapshot().unwrap().snapshot_id, 2);
+        assert_eq!(
+            panel.list_title(),
+            " Snapshots (2/3) · since 2025-01-15 00:00:00 UTC "
+        );
+
+        panel.set_time_range(None, Some(at("2025-01-31")));
+        assert_eq!(panel.selected_snapshot().unwrap().snapshot_id, 1);
+
+        type_keys(&mut panel, "t2025-03-01..2025-02-01");
+        panel.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(panel.range_error.is_some());
+        panel.handle_key(KeyEvent::from(KeyCode::Esc));
+
+        assert!(panel.select_snapshot_id(3));
+        assert_eq!(panel.shown_snapshots().len(), 3);
+        assert_eq!(panel.list_title(), " Snapshots (3) ");
+    }
+
+    fn child(id: i64, parent: i64, operation: &str, totals: &[(&str, &str)]) -> SnapshotInfo {
+        SnapshotInfo {
+            parent_snapshot_id: Some(parent),
+            operation: operation.into(),
+            summary: summary(totals),
+            ..snapshot(id)
+        }
+    }
+
+    #[test]
+    fn rollback_preview_lists_undone_snapshots_and_total_deltas() {
+        let base = SnapshotInfo {
+            summary: summary(&[
+                ("total-records", "1000"),
+                ("total-data-files", "4"),
+                ("total-files-size", "2048"),
+            ]),
+            ..snapshot(1)
+        };
+        let appended = child(2, 1, "append", &[("total-records", "1500")]);
+        let deleted = child(
+            3,
+            2,
+            "delete",
+            &[
+                ("total-records", "1200"),
+                ("total-data-files", "5"),
+                ("total-files-size", "3072"),
+            ],
+        );
+        let snapshots = vec![deleted.clone(), appended, base.clone()];
+
+        assert_eq!(
+            rollback_preview(&snapshots, &deleted, &base),
+            vec![
+                ("Undoes", "2 snapshots (1 append, 1 delete)".to_string()),
+                ("Rows", "1200 → 1000 (-200)".to_string()),
+                ("Data files", "5 → 4 (-1)".to_string()),
+                ("Table size", "3.0 KB → 2.0 KB (-1.0 KB)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rollback_preview_flags_targets_off_the_current_lineage() {
+        let base = snapshot(1);
+        let current = child(2, 1, "append", &[]);
+        let sibling = child(3, 1, "append", &[]);
+        let snapshots = vec![sibling.clone(), current.clone(), base];
+        assert_eq!(
+            rollback_preview(&snapshots, &current, &sibling),
+            vec![(
+                "Undoes",
+                "nothing: not an ancestor of the current snapshot".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn goto_prompt_selects_matching_snapshot() {
+        let mut panel = SnapshotPanel::new();
+        panel.snapshots = vec![snapshot(300), snapshot(200), snapshot(100)];
+        panel.list_state.select(Some(0));
+
+        type_keys(&mut panel, ":100");
+        assert!(panel.is_input_mode());
+        assert_eq!(panel.handle_key(KeyEvent::from(KeyCode::Enter)), None);
+
+        assert!(!panel.is_input_mode());
+        assert_eq!(panel.selected_snapshot().unwrap().snapshot_id, 100);
+        assert_eq!(
+            panel.handle_key(KeyEvent::from(KeyCode::Enter)),
+            Some(Action::SelectSnapshot(100))
+        );
+    }
+
+    #[test]
+    fn goto_prompt_reports_unknown_snapshot() {
+        let mut panel = SnapshotPanel::new();
+        panel.snapshots = vec![snapshot(300), snapshot(200)];
+        panel.list_state.select(Some(0));
+
+        type_keys(&mut panel, ":42x");
+        panel.handle_key(KeyEvent::from(KeyCode::Enter));
+
+        assert!(panel.is_input_mode());
+        assert_eq!(panel.goto_error.as_deref(), Some("snapshot 42 not found"));
+        assert_eq!(panel.list_state.selected(), Some(0));
+
+        panel.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert!(!panel.is_input_mode());
+        assert!(panel.goto_error.is_none());
+    }
+
+    #[test]
+    fn split_keys_resize_left_panel_outside_goto_prompt() {
+        let mut panel = SnapshotPanel::new();
+        type_keys(&mut panel, ">>");
+        assert_eq!(panel.left_percent, LEFT_PANEL_PERCENT + 10);
+
+        type_keys(&mut panel, ":<");
+        assert_eq!(panel.left_percent, LEFT_PANEL_PERCENT + 10);
+        assert_eq!(panel.goto_input.as_deref(), Some(""));
+    }
 }
@@ -0,0 +1,180 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::event::{Action, AppMessage};
+use crate::loader::sql::SQL_TABLE_NAME;
+use crate::ui::theme::Theme;
+
+use super::Component;
+
+/// One-line SQL editor drawn over the filter bar while open. The submitted query runs over the
+/// scanned rows, registered as table `t`, and its result replaces the Data tab's rows.
+pub struct SqlPrompt {
+    text: String,
+    /// Cursor position within the text, in characters.
+    cursor: usize,
+    editing: bool,
+    /// Query the Data tab currently shows the result of.
+    applied: Option<String>,
+}
+
+impl SqlPrompt {
+    pub fn new() -> Self {
+        Self {
+            text: String::new(),
+            cursor: 0,
+            editing: false,
+            applied: None,
+        }
+    }
+
+    /// Record a query applied outside the prompt, e.g. with `--sql`.
+    pub fn set_applied(&mut self, query: Option<String>) {
+        self.applied = query;
+    }
+
+    /// Open the prompt on the applied query, so it can be edited and run again.
+    pub fn start_editing(&mut self) {
+        self.text = self.applied.clone().unwrap_or_default();
+        self.cursor = self.text.chars().count();
+        self.editing = true;
+    }
+
+    fn byte_index(&self) -> usize {
+        self.text
+            .char_indices()
+            .nth(self.cursor)
+            .map_or(self.text.len(), |(i, _)| i)
+    }
+
+    fn label() -> String {
+        format!(" SQL (table {}): ", SQL_TABLE_NAME)
+    }
+}
+
+impl Component for SqlPrompt {
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if !self.editing {
+            return None;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                self.editing = false;
+                None
+            }
+            KeyCode::Enter => {
+                self.editing = false;
+                let query = self.text.trim();
+                self.applied = (!query.is_empty()).then(|| query.to_string());
+                Some(Action::SubmitSql(self.applied.clone()))
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let at = self.byte_index();
+                self.text.remove(at);
+                None
+            }
+            KeyCode::Delete if self.cursor < self.text.chars().count() => {
+                let at = self.byte_index();
+                self.text.remove(at);
+                None
+            }
+            KeyCode::Left => {
+                self.cursor = self.cursor.saturating_sub(1);
+                None
+            }
+            KeyCode::Right => {
+                self.cursor = (self.cursor + 1).min(self.text.chars().count());
+                None
+            }
+            KeyCode::Home => {
+                self.cursor = 0;
+                None
+            }
+            KeyCode::End => {
+                self.cursor = self.text.chars().count();
+                None
+            }
+            KeyCode::Char(c) => {
+                let at = self.byte_index();
+                self.text.insert(at, c);
+                self.cursor += 1;
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_message(&mut self, _msg: &AppMessage) -> Option<Action> {
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, _focused: bool) {
+        let label = Self::label();
+        let cursor_x = area.x + label.len() as u16 + self.cursor as u16;
+        frame.set_cursor_position((cursor_x, area.y));
+        let line = Line::from(vec![
+            Span::styled(label, Theme::label()),
+            Span::styled(self.text.as_str(), Theme::filter_active()),
+        ]);
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(line), area);
+    }
+
+    fn is_input_mode(&self) -> bool {
+        self.editing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(prompt: &mut SqlPrompt, text: &str) {
+        for c in text.chars() {
+            prompt.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn enter_submits_the_trimmed_query() {
+        let mut prompt = SqlPrompt::new();
+        assert_eq!(prompt.handle_key(KeyEvent::from(KeyCode::Char('x'))), None);
+
+        prompt.start_editing();
+        assert!(prompt.is_input_mode());
+        type_text(&mut prompt, " SELECT COUNT(*) FROM t ");
+        assert_eq!(
+            prompt.handle_key(KeyEvent::from(KeyCode::Enter)),
+            Some(Action::SubmitSql(Some("SELECT COUNT(*) FROM t".into())))
+        );
+        assert!(!prompt.is_input_mode());
+
+        // Reopening edits the applied query; clearing it goes back to the plain scan.
+        prompt.start_editing();
+        assert_eq!(prompt.text, "SELECT COUNT(*) FROM t");
+        for _ in 0..prompt.text.len() {
+            prompt.handle_key(KeyEvent::from(KeyCode::Backspace));
+        }
+        assert_eq!(
+            prompt.handle_key(KeyEvent::from(KeyCode::Enter)),
+            Some(Action::SubmitSql(None))
+        );
+    }
+
+    #[test]
+    fn esc_keeps_the_applied_query() {
+        let mut prompt = SqlPrompt::new();
+        prompt.set_applied(Some("SELECT * FROM t".into()));
+        prompt.start_editing();
+        prompt.handle_key(KeyEvent::from(KeyCode::Home));
+        type_text(&mut prompt, "-- ");
+        assert_eq!(prompt.text, "-- SELECT * FROM t");
+        assert_eq!(prompt.handle_key(KeyEvent::from(KeyCode::Esc)), None);
+        assert!(!prompt.is_input_mode());
+        assert_eq!(prompt.applied.as_deref(), Some("SELECT * FROM t"));
+    }
+}
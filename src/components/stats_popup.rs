@@ -0,0 +1,232 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::event::{Action, AppMessage};
+use crate::loader::arrow_convert::ColumnStats;
+use crate::ui::theme::Theme;
+
+use super::Component;
+
+const POPUP_WIDTH_PERCENT: u16 = 60;
+const POPUP_HEIGHT_PERCENT: u16 = 60;
+/// Buckets in the histogram, one line each.
+pub const HISTOGRAM_BUCKETS: usize = 10;
+/// Partial block characters, by eighths of a cell.
+const PARTIAL_BLOCKS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Popup summarizing the focused numeric column of the loaded rows, with a histogram.
+pub struct StatsPopup {
+    column: String,
+    /// `None` when the column isn't numeric.
+    stats: Option<ColumnStats>,
+    pub visible: bool,
+}
+
+impl StatsPopup {
+    pub fn new() -> Self {
+        Self {
+            column: String::new(),
+            stats: None,
+            visible: false,
+        }
+    }
+
+    pub fn show(&mut self, column: String, stats: Option<ColumnStats>) {
+        self.column = column;
+        self.stats = stats;
+        self.visible = true;
+    }
+
+    fn popup_area(area: Rect) -> Rect {
+        let width = area.width * POPUP_WIDTH_PERCENT / 100;
+        let height = area.height * POPUP_HEIGHT_PERCENT / 100;
+        let x = (area.width.saturating_sub(width)) / 2;
+        let y = (area.height.saturating_sub(height)) / 2;
+        Rect::new(area.x + x, area.y + y, width, height)
+    }
+
+    fn build_lines(stats: Option<&ColumnStats>, width: usize) -> Vec<Line<'static>> {
+        let Some(stats) = stats else {
+            return vec![Line::styled(
+                "Not a numeric column",
+                Theme::status_warning(),
+            )];
+        };
+
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{}: ", label), Theme::label()),
+                Span::styled(value, Theme::value()),
+            ])
+        };
+        let mut lines = vec![
+            field("Values", stats.count.to_string()),
+            field("Nulls", stats.nulls.to_string()),
+        ];
+        let Some((lo, hi)) = stats.range else {
+            lines.push(Line::raw(""));
+            lines.push(Line::styled("No values to chart", Theme::null_value()));
+            return lines;
+        };
+        lines.push(field("Min", format_value(lo)));
+        lines.push(field("Max", format_value(hi)));
+        if let Some(mean) = stats.mean {
+            lines.push(field("Mean", format_value(mean)));
+        }
+
+        lines.push(Line::raw(""));
+        lines.push(Line::styled("─── Histogram ───", Theme::title()));
+
+        let step = (hi - lo) / stats.histogram.len() as f64;
+        let labels: Vec<String> = (0..stats.histogram.len())
+            .map(|i| {
+                let start = lo + step * i as f64;
+                if stats.histogram.len() == 1 {
+                    format_value(start)
+                } else {
+                    format!("{} – {}", format_value(start), format_value(start + step))
+                }
+            })
+            .collect();
+        let label_width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let count_width = stats
+            .histogram
+            .iter()
+            .map(|c| c.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let bar_width = width.saturating_sub(label_width + count_width + 3);
+        let max_count = stats.histogram.iter().copied().max().unwrap_or(0);
+
+        for (label, &count) in labels.iter().zip(&stats.histogram) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>label_width$} ", label), Theme::label()),
+                Span::styled(
+                    format!("{:<bar_width$}", bar(count, max_count, bar_width)),
+                    Theme::field_type(),
+                ),
+                Span::styled(format!(" {:>count_width$}", count), Theme::value()),
+            ]));
+        }
+        lines
+    }
+}
+
+/// A bar of block characters `count / max` of `width` cells long, in eighth-cell steps.
+fn bar(count: usize, max: usize, width: usize) -> String {
+    if max == 0 {
+        return String::new();
+    }
+    let eighths = count * width * 8 / max;
+    let mut bar = "█".repeat(eighths / 8);
+    if !eighths.is_multiple_of(8) {
+        bar.push(PARTIAL_BLOCKS[eighths % 8]);
+    }
+    bar
+}
+
+/// Whole numbers without a fraction, everything else to at most four decimals.
+fn format_value(v: f64) -> String {
+    if v.fract() == 0.0 && v.abs() < 1e15 {
+        format!("{}", v as i64)
+    } else {
+        let s = format!("{:.4}", v);
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+impl Component for StatsPopup {
+    fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if let KeyCode::Esc | KeyCode::Char('s') | KeyCode::Char('q') = key.code {
+            self.visible = false;
+        }
+        None
+    }
+
+    fn handle_message(&mut self, _msg: &AppMessage) -> Option<Action> {
+        None
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, _focused: bool) {
+        if !self.visible {
+            return;
+        }
+
+        let popup = Self::popup_area(area);
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" Stats: {} (s/Esc to close) ", self.column))
+            .border_style(Theme::border_focused());
+        let inner_width = popup.width.saturating_sub(2) as usize;
+        let paragraph =
+            Paragraph::new(Self::build_lines(self.stats.as_ref(), inner_width)).block(block);
+
+        frame.render_widget(paragraph, popup);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars_scale_to_width() {
+        assert_eq!(bar(10, 10, 4), "████");
+        assert_eq!(bar(5, 10, 4), "██");
+        assert_eq!(bar(1, 32, 4), "▏");
+        assert_eq!(bar(3, 32, 4), "▍");
+        assert_eq!(bar(0, 10, 4), "");
+        assert_eq!(bar(0, 0, 4), "");
+    }
+
+    #[test]
+    fn values_drop_needless_decimals() {
+        assert_eq!(format_value(42.0), "42");
+        assert_eq!(format_value(-2.5), "-2.5");
+        assert_eq!(format_value(1.0 / 3.0), "0.3333");
+    }
+
+    #[test]
+    fn histogram_has_one_line_per_bucket() {
+        let stats = ColumnStats {
+            count: 4,
+            nulls: 1,
+            range: Some((0.0, 10.0)),
+            mean: Some(4.0),
+            histogram: vec![3, 1],
+        };
+        let lines = StatsPopup::build_lines(Some(&stats), 40);
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert!(text
+            .iter()
+            .any(|l| l.starts_with(" 0 – 5 █") && l.ends_with(" 3")));
+        assert!(text
+            .iter()
+            .any(|l| l.starts_with("5 – 10 █") && l.ends_with(" 1")));
+        assert!(text.iter().all(|l| l.chars().count() <= 40));
+    }
+
+    #[test]
+    fn degenerate_columns_render_without_histogram() {
+        let empty = ColumnStats {
+            count: 0,
+            nulls: 3,
+            range: None,
+            mean: None,
+            histogram: vec![],
+        };
+        let text: Vec<String> = StatsPopup::build_lines(Some(&empty), 40)
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        assert!(text.contains(&"No values to chart".to_string()));
+
+        let text = StatsPopup::build_lines(None, 40)[0].to_string();
+        assert_eq!(text, "Not a numeric column");
+    }
+}
@@ -1,15 +1,36 @@
+use std::time::Duration;
+
 use crossterm::event::KeyEvent;
 use ratatui::layout::Rect;
 use ratatui::text::{Line, Span};
 use ratatui::Frame;
 
-use crate::event::{Action, AppMessage};
+use crate::event::{Action, AppMessage, LoadTask};
 use crate::ui::theme::Theme;
+use crate::util::format::{format_count, format_relative};
 
 use super::Component;
 
-const ERROR_DISPLAY_MAX_LEN: usize = 40;
-const ERROR_TRUNCATED_LEN: usize = ERROR_DISPLAY_MAX_LEN - 3; // room for "..."
+/// Messages always get at least this many columns, even if they push out the key hints.
+const MESSAGE_MIN_WIDTH: usize = 40;
+const KEY_HINTS: &str = " q:quit ?:help ";
+
+fn format_duration(elapsed: Duration) -> String {
+    if elapsed.as_secs() >= 1 {
+        format!("{:.1}s", elapsed.as_secs_f64())
+    } else {
+        format!("{}ms", elapsed.as_millis())
+    }
+}
+
+/// Shorten `text` to at most `max` characters, ending in "..." when cut.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
 
 pub struct StatusBar {
     pub loaded_rows: usize,
@@ -17,13 +38,29 @@ pub struct StatusBar {
     pub filtered_rows: Option<usize>,
     pub visible_columns: usize,
     pub total_columns: usize,
-    pub loading_message: Option<String>,
+    /// Selected columns left out because the loaded data doesn't have them.
+    pub missing_columns: usize,
+    /// Background tasks in flight, in start order, with their progress messages.
+    loading: Vec<(LoadTask, String)>,
     pub error_message: Option<String>,
+    pub warning_message: Option<String>,
+    /// Confirmation of a user action, cleared when the next load starts.
+    pub info_message: Option<String>,
     pub filter_active: bool,
     pub has_more: bool,
     selected_snapshot_id: Option<i64>,
     current_snapshot_id: Option<i64>,
+    selected_timestamp_ms: Option<i64>,
     highlighted_snapshot: Option<String>,
+    /// Latest metadata version picked up by `--watch`, e.g. "table v4 → v5 at 12:03:07".
+    table_update: Option<String>,
+    /// Latest duration of each timed phase, shown when `show_timings` is set.
+    timings: Vec<(LoadTask, Duration)>,
+    pub show_timings: bool,
+    /// Show row counts with thousands separators.
+    pub group_numbers: bool,
+    /// Keys of the active tab, shown before the global ones when there is room.
+    pub context_hints: &'static str,
 }
 
 impl StatusBar {
@@ -34,25 +71,85 @@ impl StatusBar {
             filtered_rows: None,
             visible_columns: 0,
             total_columns: 0,
-            loading_message: None,
+            missing_columns: 0,
+            loading: vec![],
             error_message: None,
+            warning_message: None,
+            info_message: None,
             filter_active: false,
             has_more: false,
             selected_snapshot_id: None,
             current_snapshot_id: None,
+            selected_timestamp_ms: None,
             highlighted_snapshot: None,
+            table_update: None,
+            timings: vec![],
+            show_timings: false,
+            group_numbers: false,
+            context_hints: "",
         }
     }
 
-    pub fn set_snapshot_view(&mut self, selected: Option<i64>, current: Option<i64>) {
+    /// `selected_timestamp_ms` is the commit time of the selected snapshot, used to show its age.
+    pub fn set_snapshot_view(
+        &mut self,
+        selected: Option<i64>,
+        current: Option<i64>,
+        selected_timestamp_ms: Option<i64>,
+    ) {
         self.selected_snapshot_id = selected;
         self.current_snapshot_id = current;
+        self.selected_timestamp_ms = selected_timestamp_ms;
+    }
+
+    /// Warning shown while time traveling, e.g. "Viewing historical data (snapshot 42, 3 days old)".
+    fn historical_label(&self, now_ms: i64) -> Option<String> {
+        let snap_id = self
+            .selected_snapshot_id
+            .filter(|_| self.is_time_traveling())?;
+        Some(match self.selected_timestamp_ms {
+            Some(ts) => format!(
+                "Viewing historical data (snapshot {}, {} old)",
+                snap_id,
+                format_relative(now_ms - ts)
+            ),
+            None => format!("Viewing historical data (snapshot {})", snap_id),
+        })
     }
 
     pub fn set_highlighted_snapshot(&mut self, label: Option<String>) {
         self.highlighted_snapshot = label;
     }
 
+    /// Aggregate loading text: the most recent task plus a count of the others.
+    pub fn loading_message(&self) -> Option<String> {
+        let (_, latest) = self.loading.last()?;
+        match self.loading.len() {
+            1 => Some(latest.clone()),
+            n => Some(format!("{} (+{} more)", latest, n - 1)),
+        }
+    }
+
+    /// Phase timings such as "loaded 1.2s, scanned 80ms", in load order.
+    pub fn timing_summary(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            (LoadTask::Table, "loaded"),
+            (LoadTask::Data, "scanned"),
+            (LoadTask::Count, "counted"),
+        ]
+        .iter()
+        .filter_map(|(task, verb)| {
+            let (_, elapsed) = self.timings.iter().find(|(t, _)| t == task)?;
+            Some(format!("{} {}", verb, format_duration(*elapsed)))
+        })
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    pub fn is_loading(&self, task: LoadTask) -> bool {
+        self.loading.iter().any(|(t, _)| *t == task)
+    }
+
     pub fn is_time_traveling(&self) -> bool {
         match (self.selected_snapshot_id, self.current_snapshot_id) {
             (Some(sel), Some(cur)) => sel != cur,
@@ -80,21 +177,37 @@ impl Component for StatusBar {
                     self.loaded_rows = *total_rows;
                     self.filtered_rows = None;
                 }
-                self.loading_message = None;
             }
             AppMessage::TotalRowCount(total) => {
                 self.table_total_rows = Some(*total);
             }
-            AppMessage::LoadingStarted(msg) => {
-                self.loading_message = Some(msg.clone());
+            AppMessage::LoadingStarted(task, msg) => {
+                self.loading.retain(|(t, _)| t != task);
+                self.loading.push((*task, msg.clone()));
                 self.error_message = None;
+                self.warning_message = None;
+                self.info_message = None;
+            }
+            AppMessage::LoadingFinished(task) => {
+                self.loading.retain(|(t, _)| t != task);
+            }
+            AppMessage::PhaseTimed(task, elapsed) => {
+                self.timings.retain(|(t, _)| t != task);
+                self.timings.push((*task, *elapsed));
+            }
+            AppMessage::TableAdvanced { from, to } => {
+                self.table_update = Some(format!(
+                    "table {} → {} at {}",
+                    from,
+                    to,
+                    chrono::Local::now().format("%H:%M:%S")
+                ));
             }
-            AppMessage::LoadingFinished => {
-                self.loading_message = None;
+            AppMessage::Warning(warning) => {
+                self.warning_message = Some(warning.clone());
             }
             AppMessage::Error(err) => {
                 self.error_message = Some(err.clone());
-                self.loading_message = None;
             }
             _ => {}
         }
@@ -104,18 +217,28 @@ impl Component for StatusBar {
     fn render(&mut self, frame: &mut Frame, area: Rect, _focused: bool) {
         let mut spans = Vec::new();
 
-        let total_suffix = self
-            .table_total_rows
-            .map(|t| format!("/{}", t))
-            .unwrap_or_default();
+        let count = |n: usize| format_count(n, self.group_numbers);
+        let total_suffix = match self.table_total_rows {
+            Some(t) => format!("/{}", count(t)),
+            None if self.is_loading(LoadTask::Count) => "/…".to_string(),
+            None => String::new(),
+        };
         let more_hint = if self.has_more { " (m:+rows)" } else { "" };
         let row_text = if let Some(filtered) = self.filtered_rows {
             format!(
                 " Rows: {}/{}{} (filtered){}",
-                filtered, self.loaded_rows, total_suffix, more_hint
+                count(filtered),
+                count(self.loaded_rows),
+                total_suffix,
+                more_hint
             )
         } else if self.loaded_rows > 0 || self.table_total_rows.is_some() {
-            format!(" Rows: {}{}{}", self.loaded_rows, total_suffix, more_hint)
+            format!(
+                " Rows: {}{}{}",
+                count(self.loaded_rows),
+                total_suffix,
+                more_hint
+            )
         } else {
             " Rows: -".to_string()
         };
@@ -128,13 +251,21 @@ impl Component for StatusBar {
                 Theme::status_bar(),
             ));
         }
+        if self.missing_columns > 0 {
+            spans.push(Span::styled(
+                format!(
+                    " ({} selected column{} not present in this snapshot)",
+                    self.missing_columns,
+                    if self.missing_columns == 1 { "" } else { "s" }
+                ),
+                Theme::status_warning(),
+            ));
+        }
 
-        if let Some(snap_id) = self
-            .selected_snapshot_id
-            .filter(|_| self.is_time_traveling())
-        {
+        if let Some(label) = self.historical_label(chrono::Utc::now().timestamp_millis()) {
+            spans.push(Span::styled(" | ", Theme::status_bar()));
             spans.push(Span::styled(
-                format!(" | Snapshot: {}", snap_id),
+                format!(" {} ", label),
                 Theme::status_time_travel(),
             ));
         }
@@ -143,26 +274,52 @@ impl Component for StatusBar {
             spans.push(Span::styled(format!(" | {}", label), Theme::status_bar()));
         }
 
+        if let Some(ref update) = self.table_update {
+            spans.push(Span::styled(format!(" | {}", update), Theme::status_info()));
+        }
+
+        if let Some(summary) = self.timing_summary().filter(|_| self.show_timings) {
+            spans.push(Span::styled(format!(" | {}", summary), Theme::status_bar()));
+        }
+
+        let used_width: usize = spans.iter().map(|s| s.width()).sum();
+        let message_width = (area.width as usize)
+            .saturating_sub(used_width + KEY_HINTS.len())
+            .max(MESSAGE_MIN_WIDTH);
         if let Some(ref err) = self.error_message {
-            let err_display = if err.len() > ERROR_DISPLAY_MAX_LEN {
-                format!(" | Error: {}...", &err[..ERROR_TRUNCATED_LEN])
-            } else {
-                format!(" | Error: {}", err)
-            };
-            spans.push(Span::styled(err_display, Theme::status_error()));
-        } else if let Some(ref msg) = self.loading_message {
+            spans.push(Span::styled(
+                truncate(&format!(" | Error: {}", err), message_width),
+                Theme::status_error(),
+            ));
+        } else if let Some(ref warning) = self.warning_message {
+            spans.push(Span::styled(
+                truncate(&format!(" | Warning: {}", warning), message_width),
+                Theme::status_warning(),
+            ));
+        } else if let Some(ref info) = self.info_message {
+            spans.push(Span::styled(
+                truncate(&format!(" | {}", info), message_width),
+                Theme::status_info(),
+            ));
+        } else if let Some(msg) = self.loading_message() {
             spans.push(Span::styled(
                 format!(" | Loading: {}", msg),
                 Theme::status_loading(),
             ));
         }
 
-        // Right-aligned key hints
-        let hints = " q:quit ?:help ";
+        // Right-aligned key hints; the active tab's keys are dropped first when space is short.
         let used_width: usize = spans.iter().map(|s| s.width()).sum();
         let remaining = area.width as usize - used_width.min(area.width as usize);
-        if remaining > hints.len() {
-            let padding = " ".repeat(remaining - hints.len());
+        let with_context = format!(" {} │{}", self.context_hints, KEY_HINTS);
+        let hints = if !self.context_hints.is_empty() && remaining >= with_context.chars().count() {
+            with_context
+        } else {
+            KEY_HINTS.to_string()
+        };
+        let hints_width = hints.chars().count();
+        if remaining >= hints_width {
+            let padding = " ".repeat(remaining - hints_width);
             spans.push(Span::styled(padding, Theme::status_bar()));
             spans.push(Span::styled(hints, Theme::status_key_hint()));
         }
@@ -182,19 +339,61 @@ mod tests {
     fn new_status_bar_defaults() {
         let bar = StatusBar::new();
         assert_eq!(bar.loaded_rows, 0);
-        assert!(bar.loading_message.is_none());
+        assert!(bar.loading_message().is_none());
         assert!(bar.error_message.is_none());
     }
 
+    #[test]
+    fn table_updates_stay_in_the_status_bar() {
+        let mut bar = StatusBar::new();
+        bar.handle_message(&AppMessage::TableAdvanced {
+            from: "v4".into(),
+            to: "v5".into(),
+        });
+        bar.handle_message(&AppMessage::LoadingStarted(
+            LoadTask::Data,
+            "Scanning...".into(),
+        ));
+        assert!(bar
+            .table_update
+            .as_deref()
+            .is_some_and(|u| u.starts_with("table v4 → v5 at ")));
+    }
+
     #[test]
     fn handle_loading_messages() {
         let mut bar = StatusBar::new();
-        bar.handle_message(&AppMessage::LoadingStarted("scanning...".into()));
-        assert_eq!(bar.loading_message.as_deref(), Some("scanning..."));
+        bar.handle_message(&AppMessage::LoadingStarted(
+            LoadTask::Data,
+            "scanning...".into(),
+        ));
+        assert_eq!(bar.loading_message().as_deref(), Some("scanning..."));
         assert!(bar.error_message.is_none());
 
-        bar.handle_message(&AppMessage::LoadingFinished);
-        assert!(bar.loading_message.is_none());
+        bar.handle_message(&AppMessage::LoadingFinished(LoadTask::Data));
+        assert!(bar.loading_message().is_none());
+    }
+
+    #[test]
+    fn loading_is_tracked_per_task() {
+        let mut bar = StatusBar::new();
+        bar.handle_message(&AppMessage::LoadingStarted(
+            LoadTask::Data,
+            "Scanning...".into(),
+        ));
+        bar.handle_message(&AppMessage::LoadingStarted(
+            LoadTask::Manifests,
+            "Loading manifests...".into(),
+        ));
+        assert_eq!(
+            bar.loading_message().as_deref(),
+            Some("Loading manifests... (+1 more)")
+        );
+
+        bar.handle_message(&AppMessage::LoadingFinished(LoadTask::Manifests));
+        assert_eq!(bar.loading_message().as_deref(), Some("Scanning..."));
+        assert!(bar.is_loading(LoadTask::Data));
+        assert!(!bar.is_loading(LoadTask::Manifests));
     }
 
     #[test]
@@ -202,27 +401,47 @@ mod tests {
         let mut bar = StatusBar::new();
         assert!(!bar.is_time_traveling());
 
-        bar.set_snapshot_view(Some(100), Some(200));
+        bar.set_snapshot_view(Some(100), Some(200), None);
         assert!(bar.is_time_traveling());
     }
 
     #[test]
     fn same_snapshot_is_not_time_traveling() {
         let mut bar = StatusBar::new();
-        bar.set_snapshot_view(Some(100), Some(100));
+        bar.set_snapshot_view(Some(100), Some(100), None);
         assert!(!bar.is_time_traveling());
     }
 
     #[test]
     fn clear_snapshot_view_stops_time_travel() {
         let mut bar = StatusBar::new();
-        bar.set_snapshot_view(Some(100), Some(200));
+        bar.set_snapshot_view(Some(100), Some(200), None);
         assert!(bar.is_time_traveling());
 
-        bar.set_snapshot_view(None, Some(200));
+        bar.set_snapshot_view(None, Some(200), None);
         assert!(!bar.is_time_traveling());
     }
 
+    #[test]
+    fn historical_label_includes_snapshot_age() {
+        let mut bar = StatusBar::new();
+        let now = 1_700_000_000_000;
+        bar.set_snapshot_view(Some(100), Some(200), Some(now - 3 * 86_400_000 - 5_000));
+        assert_eq!(
+            bar.historical_label(now).as_deref(),
+            Some("Viewing historical data (snapshot 100, 3 days old)")
+        );
+
+        bar.set_snapshot_view(Some(100), Some(200), None);
+        assert_eq!(
+            bar.historical_label(now).as_deref(),
+            Some("Viewing historical data (snapshot 100)")
+        );
+
+        bar.set_snapshot_view(Some(200), Some(200), Some(now));
+        assert!(bar.historical_label(now).is_none());
+    }
+
     #[test]
     fn set_highlighted_snapshot() {
         let mut bar = StatusBar::new();
@@ -239,12 +458,72 @@ mod tests {
     }
 
     #[test]
-    fn handle_error_clears_loading() {
+    fn handle_error_keeps_task_until_finished() {
         let mut bar = StatusBar::new();
-        bar.handle_message(&AppMessage::LoadingStarted("loading".into()));
+        bar.handle_message(&AppMessage::LoadingStarted(
+            LoadTask::Table,
+            "loading".into(),
+        ));
         bar.handle_message(&AppMessage::Error("table not found".into()));
-        assert!(bar.loading_message.is_none());
         assert_eq!(bar.error_message.as_deref(), Some("table not found"));
+        assert!(bar.is_loading(LoadTask::Table));
+
+        bar.handle_message(&AppMessage::LoadingFinished(LoadTask::Table));
+        assert!(bar.loading_message().is_none());
+    }
+
+    #[test]
+    fn warning_cleared_by_next_load() {
+        let mut bar = StatusBar::new();
+        bar.handle_message(&AppMessage::Warning(
+            "columns not in snapshot schema: email".into(),
+        ));
+        assert!(bar.warning_message.is_some());
+
+        bar.handle_message(&AppMessage::LoadingStarted(
+            LoadTask::Data,
+            "Scanning...".into(),
+        ));
+        assert!(bar.warning_message.is_none());
+    }
+
+    #[test]
+    fn timing_summary_in_load_order() {
+        let mut bar = StatusBar::new();
+        assert!(bar.timing_summary().is_none());
+
+        bar.handle_message(&AppMessage::PhaseTimed(
+            LoadTask::Count,
+            Duration::from_millis(250),
+        ));
+        bar.handle_message(&AppMessage::PhaseTimed(
+            LoadTask::Table,
+            Duration::from_millis(1200),
+        ));
+        bar.handle_message(&AppMessage::PhaseTimed(
+            LoadTask::Data,
+            Duration::from_millis(800),
+        ));
+        assert_eq!(
+            bar.timing_summary().as_deref(),
+            Some("loaded 1.2s, scanned 800ms, counted 250ms")
+        );
+
+        // A rescan replaces the previous scan time.
+        bar.handle_message(&AppMessage::PhaseTimed(
+            LoadTask::Data,
+            Duration::from_secs(3),
+        ));
+        assert_eq!(
+            bar.timing_summary().as_deref(),
+            Some("loaded 1.2s, scanned 3.0s, counted 250ms")
+        );
+    }
+
+    #[test]
+    fn truncate_is_char_safe() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("héllo wörld", 8), "héllo...");
     }
 
     #[test]
@@ -1,16 +1,17 @@
 use arrow_array::RecordBatch;
 use crossterm::event::{self, Event, KeyEvent};
+use std::collections::HashMap;
 use std::time::Duration;
 use tokio::sync::mpsc;
 
+use crate::loader::scan::ScanPlan;
+use crate::model::file_diagnostics::FileDiagnostics;
 use crate::model::table_info::{DataFileInfo, ManifestInfo, TableMetadata};
 
 #[derive(Debug, PartialEq)]
 pub enum Action {
     Quit,
     SwitchTab(usize),
-    FocusNext,
-    FocusPrev,
     ToggleHelp,
     ToggleColumnSelector,
     FocusFilter,
@@ -19,6 +20,41 @@ pub enum Action {
     SubmitFilter(String),
     ToggleColumn(String),
     SelectSnapshot(i64),
+    /// Reload one manifest by index, or the whole manifest list when `None`.
+    RetryManifest(Option<usize>),
+    ExplainScan,
+    /// Summarize the focused Data tab column over the loaded rows.
+    ShowColumnStats,
+    /// Switch numbers between raw digits and thousands separators.
+    ToggleNumberGrouping,
+    /// Copy text to the clipboard; `label` names what was copied in the status bar.
+    CopyToClipboard {
+        text: String,
+        label: String,
+    },
+    /// Write the Files tab's data file inventory to a CSV file.
+    ExportFiles,
+    /// Show all columns, drop the filter and return to the current snapshot, then rescan.
+    ResetView,
+    /// Open the recent table at this index in the startup picker.
+    OpenRecent(usize),
+    /// Forget the recent table at this index in the startup picker.
+    RemoveRecent(usize),
+    /// Open the SQL prompt on the Data tab.
+    #[cfg(feature = "sql")]
+    FocusSql,
+    /// Rescan with this SQL query over the table, or with no query when `None`.
+    #[cfg(feature = "sql")]
+    SubmitSql(Option<String>),
+}
+
+/// A background concern that reports its own loading state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadTask {
+    Table,
+    Data,
+    Manifests,
+    Count,
 }
 
 /// Messages sent from background loader tasks back to the main UI thread.
@@ -30,11 +66,48 @@ pub enum AppMessage {
         has_more: bool,
     },
     MetadataReady(Box<TableMetadata>),
+    /// `--ref` resolved to this snapshot; the initial scan reads it instead of the current one.
+    RefResolved(i64),
+    /// Arrow types of each schema's fields, by schema id and then field id.
+    ArrowTypesReady(HashMap<i32, HashMap<i32, String>>),
     ManifestsReady(Vec<ManifestInfo>),
     DataFileStatsReady(Vec<Vec<DataFileInfo>>),
+    /// The snapshot's manifest list could not be read.
+    ManifestListFailed(String),
+    /// A single manifest was reloaded after a retry.
+    ManifestReloaded {
+        index: usize,
+        manifest: ManifestInfo,
+        files: Vec<DataFileInfo>,
+    },
+    /// Result of the `diagnose` run started for the given generation of the Files tab.
+    FileDiagnosticsReady {
+        generation: u64,
+        diagnostics: FileDiagnostics,
+    },
     TotalRowCount(usize),
-    LoadingStarted(String),
-    LoadingFinished,
+    /// `--watch` found a newer metadata file and reloaded the table; labels like `v4` and `v5`.
+    TableAdvanced {
+        from: String,
+        to: String,
+    },
+    /// Position of the first row of the next `DataReady` among all matching rows.
+    RowOffset(usize),
+    ScanPlanReady(ScanPlan),
+    LoadingStarted(LoadTask, String),
+    LoadingFinished(LoadTask),
+    /// How long a task took to complete successfully.
+    PhaseTimed(LoadTask, Duration),
+    /// A message from the data scan of this generation; ones from scans a newer scan replaced
+    /// are dropped.
+    Scan {
+        generation: u64,
+        message: Box<AppMessage>,
+    },
+    /// A startup flag can't be honored, e.g. an unknown `--ref`; icepeek exits with this error.
+    StartupFailed(String),
+    /// Non-fatal problem worth surfacing in the status bar.
+    Warning(String),
     Error(String),
 }
 
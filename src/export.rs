@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Where an export of `kind` for `snapshot_id` goes: a file in the working directory, e.g.
+/// `icepeek-files-3051729675574597004.csv`.
+pub fn csv_path(kind: &str, snapshot_id: Option<i64>) -> PathBuf {
+    let name = match snapshot_id {
+        Some(id) => format!("icepeek-{}-{}.csv", kind, id),
+        None => format!("icepeek-{}.csv", kind),
+    };
+    std::env::current_dir().unwrap_or_default().join(name)
+}
+
+/// Write `header` and `rows` to `path` as CSV, replacing any existing file.
+pub fn write_csv(path: &Path, header: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    let file =
+        std::fs::File::create(path).with_context(|| format!("cannot create {}", path.display()))?;
+    let mut out = std::io::BufWriter::new(file);
+    write_record(&mut out, header.iter().copied())
+        .and_then(|_| {
+            rows.iter()
+                .try_for_each(|row| write_record(&mut out, row.iter().map(String::as_str)))
+        })
+        .and_then(|_| out.flush())
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn write_record<'a>(
+    out: &mut impl Write,
+    fields: impl Iterator<Item = &'a str>,
+) -> std::io::Result<()> {
+    let line: Vec<Cow<str>> = fields.map(csv_field).collect();
+    writeln!(out, "{}", line.join(","))
+}
+
+/// Quote a field that contains a separator, quote or line break, doubling inner quotes.
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn writes_header_and_rows() {
+        let path = std::env::temp_dir().join(format!("icepeek-export-{}.csv", std::process::id()));
+        let rows = vec![
+            vec!["/t/a.parquet".to_string(), "day=2024-01-01".to_string()],
+            vec![
+                "/t/b.parquet".to_string(),
+                "day=2024-01-02;region=eu,us".to_string(),
+            ],
+        ];
+        write_csv(&path, &["path", "partition"], &rows).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            written,
+            "path,partition\n\
+             /t/a.parquet,day=2024-01-01\n\
+             /t/b.parquet,\"day=2024-01-02;region=eu,us\"\n"
+        );
+    }
+
+    #[test]
+    fn path_names_the_snapshot() {
+        assert!(csv_path("files", Some(42)).ends_with("icepeek-files-42.csv"));
+        assert!(csv_path("files", None).ends_with("icepeek-files.csv"));
+    }
+}
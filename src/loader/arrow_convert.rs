@@ -1,25 +1,112 @@
-use anyhow::Result;
-use arrow_array::RecordBatch;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float64Type;
+use arrow_array::{make_array, Array, ArrayRef, RecordBatch};
+use arrow_buffer::NullBuffer;
 use arrow_cast::display::ArrayFormatter;
+use arrow_schema::{DataType, Field, FieldRef, Schema};
+
+use crate::util::encoding;
+
+/// A displayable row: one formatted cell per column, `None` for nulls.
+pub type StringRow = Vec<Option<String>>;
+
+/// How top-level `Binary`/`FixedSizeBinary` columns are rendered as text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BinaryFormat {
+    /// Lowercase hex, two digits per byte
+    #[default]
+    Hex,
+    /// Standard padded base64
+    Base64,
+    /// Decode as UTF-8, replacing invalid bytes with U+FFFD
+    Utf8Lossy,
+}
+
+impl BinaryFormat {
+    pub fn format(self, bytes: &[u8]) -> String {
+        match self {
+            BinaryFormat::Hex => encoding::hex_encode(bytes),
+            BinaryFormat::Base64 => encoding::base64_encode(bytes),
+            BinaryFormat::Utf8Lossy => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+}
+
+/// Formats one column's cells, rendering binary columns with the chosen `BinaryFormat`.
+enum CellFormatter<'a> {
+    Arrow(ArrayFormatter<'a>),
+    Binary(&'a dyn Array, BinaryFormat),
+}
+
+impl<'a> CellFormatter<'a> {
+    fn try_new(array: &'a dyn Array, binary: BinaryFormat) -> Result<Self> {
+        match array.data_type() {
+            DataType::Binary
+            | DataType::LargeBinary
+            | DataType::BinaryView
+            | DataType::FixedSizeBinary(_) => Ok(Self::Binary(array, binary)),
+            _ => Ok(Self::Arrow(ArrayFormatter::try_new(
+                array,
+                &Default::default(),
+            )?)),
+        }
+    }
+
+    fn value(&self, row: usize) -> String {
+        match self {
+            Self::Arrow(fmt) => fmt.value(row).to_string(),
+            Self::Binary(array, binary) => {
+                let bytes = match array.data_type() {
+                    DataType::Binary => array.as_binary::<i32>().value(row),
+                    DataType::LargeBinary => array.as_binary::<i64>().value(row),
+                    DataType::BinaryView => array.as_binary_view().value(row),
+                    _ => array.as_fixed_size_binary().value(row),
+                };
+                binary.format(bytes)
+            }
+        }
+    }
+}
+
+/// Columns across all batches, matched by name: the first batch's columns in order, then
+/// any column only later batches have. Batches can differ when a scan spans schema changes.
+fn unified_fields(batches: &[RecordBatch]) -> Vec<FieldRef> {
+    let mut fields: Vec<FieldRef> = Vec::new();
+    for batch in batches {
+        for field in batch.schema().fields() {
+            if !fields.iter().any(|f| f.name() == field.name()) {
+                fields.push(field.clone());
+            }
+        }
+    }
+    fields
+}
 
 /// Convert a list of RecordBatches to displayable string rows.
 ///
-/// Returns (column_names, rows) where each row is a Vec<String> of cell values.
+/// Returns (column_names, rows) where each row holds one cell per column, `None` for nulls.
+/// Columns are matched by name across batches; a batch without a column shows nulls for it.
 /// Applies offset/limit for pagination.
 pub fn batches_to_string_rows(
     batches: &[RecordBatch],
     offset: usize,
     limit: usize,
-) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+    binary: BinaryFormat,
+) -> Result<(Vec<String>, Vec<StringRow>)> {
     if batches.is_empty() {
         return Ok((vec![], vec![]));
     }
 
-    let schema = batches[0].schema();
-    let column_names: Vec<String> = schema.fields().iter().map(|f| f.name().clone()).collect();
+    let column_names: Vec<String> = unified_fields(batches)
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
 
-    let num_columns = column_names.len();
-    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut rows: Vec<StringRow> = Vec::new();
     let mut current_offset = 0;
 
     for batch in batches {
@@ -32,21 +119,29 @@ pub fn batches_to_string_rows(
 
         let start_in_batch = offset.saturating_sub(current_offset);
 
-        let formatters: Vec<ArrayFormatter> = (0..num_columns)
-            .map(|col_idx| {
-                let array = batch.column(col_idx);
-                ArrayFormatter::try_new(array.as_ref(), &Default::default())
+        let schema = batch.schema();
+        let columns: Vec<Option<(&dyn Array, CellFormatter)>> = column_names
+            .iter()
+            .map(|name| match schema.index_of(name) {
+                Ok(idx) => {
+                    let array = batch.column(idx).as_ref();
+                    Ok(Some((array, CellFormatter::try_new(array, binary)?)))
+                }
+                Err(_) => Ok(None),
             })
-            .collect::<std::result::Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>>>()?;
 
         for row_idx in start_in_batch..batch_rows {
             if rows.len() >= limit {
                 return Ok((column_names, rows));
             }
 
-            let row: Vec<String> = formatters
+            let row: StringRow = columns
                 .iter()
-                .map(|fmt| fmt.value(row_idx).to_string())
+                .map(|column| match column {
+                    Some((array, fmt)) if !array.is_null(row_idx) => Some(fmt.value(row_idx)),
+                    _ => None,
+                })
                 .collect();
             rows.push(row);
         }
@@ -62,25 +157,294 @@ pub fn total_row_count(batches: &[RecordBatch]) -> usize {
     batches.iter().map(|b| b.num_rows()).sum()
 }
 
-/// Extract column names from record batches.
-pub fn column_names(batches: &[RecordBatch]) -> Vec<String> {
-    if batches.is_empty() {
-        return vec![];
+/// Numeric summary of one column over the loaded batches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    /// Finite, non-null values summarized below.
+    pub count: usize,
+    pub nulls: usize,
+    /// Smallest and largest value, or `None` when the column has no values.
+    pub range: Option<(f64, f64)>,
+    pub mean: Option<f64>,
+    /// Value counts in equal-width buckets spanning `range`. A single bucket when every
+    /// value is the same, empty when there are no values.
+    pub histogram: Vec<usize>,
+}
+
+/// Summarize a numeric column across batches, bucketing its values into `buckets` bins.
+///
+/// Returns `Ok(None)` when the column is missing or not numeric. Batches without the column
+/// are skipped; NaN and infinite values are left out of the summary.
+pub fn column_stats(
+    batches: &[RecordBatch],
+    column: &str,
+    buckets: usize,
+) -> Result<Option<ColumnStats>> {
+    let Some(field) = unified_fields(batches)
+        .into_iter()
+        .find(|f| f.name() == column)
+    else {
+        return Ok(None);
+    };
+    if !field.data_type().is_numeric() {
+        return Ok(None);
     }
-    batches[0]
-        .schema()
-        .fields()
+
+    let mut values: Vec<f64> = Vec::new();
+    let mut nulls = 0;
+    for batch in batches {
+        let Some(array) = batch.column_by_name(column) else {
+            continue;
+        };
+        let floats = arrow_cast::cast(array, &DataType::Float64)?;
+        let floats = floats.as_primitive::<Float64Type>();
+        nulls += floats.null_count();
+        values.extend(floats.iter().flatten().filter(|v| v.is_finite()));
+    }
+
+    let range = values.iter().fold(None, |acc: Option<(f64, f64)>, &v| {
+        Some(acc.map_or((v, v), |(lo, hi)| (lo.min(v), hi.max(v))))
+    });
+    let mean = (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64);
+
+    let histogram = match range {
+        None => vec![],
+        Some((lo, hi)) if lo == hi => vec![values.len()],
+        Some((lo, hi)) => {
+            let buckets = buckets.max(1);
+            let width = (hi - lo) / buckets as f64;
+            let mut counts = vec![0; buckets];
+            for v in &values {
+                let idx = ((v - lo) / width) as usize;
+                counts[idx.min(buckets - 1)] += 1;
+            }
+            counts
+        }
+    };
+
+    Ok(Some(ColumnStats {
+        count: values.len(),
+        nulls,
+        range,
+        mean,
+        histogram,
+    }))
+}
+
+/// Extract column names from record batches, in the order `batches_to_string_rows` uses.
+pub fn column_names(batches: &[RecordBatch]) -> Vec<String> {
+    unified_fields(batches)
         .iter()
         .map(|f| f.name().clone())
         .collect()
 }
 
+/// Extract the Arrow data type of each column from record batches.
+pub fn column_types(batches: &[RecordBatch]) -> Vec<DataType> {
+    unified_fields(batches)
+        .iter()
+        .map(|f| f.data_type().clone())
+        .collect()
+}
+
+/// Field metadata key iceberg uses to carry field ids on converted Arrow fields.
+const FIELD_ID_META_KEY: &str = "PARQUET:field_id";
+
+/// Arrow type of every field (at any depth) in a schema converted from iceberg, keyed by
+/// iceberg field id. Nested types are named without their children, which have entries of
+/// their own.
+pub fn arrow_types_by_field_id(schema: &Schema) -> HashMap<i32, String> {
+    fn collect(field: &Field, types: &mut HashMap<i32, String>) {
+        let id = field
+            .metadata()
+            .get(FIELD_ID_META_KEY)
+            .and_then(|id| id.parse().ok());
+        if let Some(id) = id {
+            types.insert(id, arrow_type_label(field.data_type()));
+        }
+        match field.data_type() {
+            DataType::Struct(children) => children.iter().for_each(|f| collect(f, types)),
+            DataType::List(child)
+            | DataType::LargeList(child)
+            | DataType::FixedSizeList(child, _)
+            | DataType::Map(child, _) => collect(child, types),
+            _ => {}
+        }
+    }
+    let mut types = HashMap::new();
+    schema.fields().iter().for_each(|f| collect(f, &mut types));
+    types
+}
+
+fn arrow_type_label(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Struct(_) => "Struct".to_string(),
+        DataType::List(_) => "List".to_string(),
+        DataType::LargeList(_) => "LargeList".to_string(),
+        DataType::FixedSizeList(_, size) => format!("FixedSizeList({})", size),
+        DataType::Map(_, _) => "Map".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Match the start of a dotted path against field `names`: the longest name equal to the
+/// path or followed by `.` in it wins, so names containing dots still resolve. Returns the
+/// field's index and the rest of the path, if any.
+pub fn match_path_segment<'a, 'p>(
+    names: impl IntoIterator<Item = &'a str>,
+    path: &'p str,
+) -> Option<(usize, Option<&'p str>)> {
+    names
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, name)| {
+            let rest = path.strip_prefix(name)?;
+            match rest.strip_prefix('.') {
+                _ if rest.is_empty() => Some((i, name.len(), None)),
+                Some(rest) if !rest.is_empty() => Some((i, name.len(), Some(rest))),
+                _ => None,
+            }
+        })
+        .max_by_key(|&(_, len, _)| len)
+        .map(|(i, _, rest)| (i, rest))
+}
+
+/// Add a flat column named by each dotted `path` into struct columns, e.g. `metrics.cpu.p99`,
+/// so nested leaves show in the grid like any other column. Paths naming an existing column,
+/// or whose top-level column a batch lacks, are skipped for that batch.
+pub fn flatten_nested_columns(
+    batches: Vec<RecordBatch>,
+    paths: &[String],
+) -> Result<Vec<RecordBatch>> {
+    if paths.is_empty() {
+        return Ok(batches);
+    }
+    batches
+        .into_iter()
+        .map(|batch| {
+            let schema = batch.schema();
+            let mut fields: Vec<FieldRef> = schema.fields().iter().cloned().collect();
+            let mut columns = batch.columns().to_vec();
+            for path in paths {
+                if schema.index_of(path).is_ok() {
+                    continue;
+                }
+                if let Some((field, array)) = nested_column(&batch, path)? {
+                    fields.push(Arc::new(Field::new(path, field.data_type().clone(), true)));
+                    columns.push(array);
+                }
+            }
+            RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+                .context("failed to flatten nested columns")
+        })
+        .collect()
+}
+
+/// The field at `path` inside a struct column, null wherever an enclosing struct is null.
+fn nested_column(batch: &RecordBatch, path: &str) -> Result<Option<(FieldRef, ArrayRef)>> {
+    let schema = batch.schema();
+    let names = schema.fields().iter().map(|f| f.name().as_str());
+    let Some((index, mut rest)) = match_path_segment(names, path) else {
+        return Ok(None);
+    };
+    let mut field = schema.fields()[index].clone();
+    let mut array = batch.column(index).clone();
+    let mut resolved = field.name().clone();
+
+    while let Some(remaining) = rest {
+        let (DataType::Struct(children), Some(parent)) = (field.data_type(), array.as_struct_opt())
+        else {
+            bail!(
+                "cannot resolve `{}`: `{}` is {}, not a struct",
+                path,
+                resolved,
+                field.data_type()
+            );
+        };
+        let names = children.iter().map(|f| f.name().as_str());
+        let Some((i, next)) = match_path_segment(names, remaining) else {
+            bail!(
+                "cannot resolve `{}`: `{}` has no field `{}`",
+                path,
+                resolved,
+                remaining
+            );
+        };
+        let child = parent.column(i);
+        let nulls = NullBuffer::union(parent.nulls(), child.nulls());
+        let data = child
+            .to_data()
+            .into_builder()
+            .nulls(nulls)
+            .build()
+            .with_context(|| format!("failed to extract `{}`", path))?;
+        array = make_array(data);
+        field = children[i].clone();
+        resolved = format!("{}.{}", resolved, field.name());
+        rest = next;
+    }
+    Ok(Some((field, array)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow_array::{Int32Array, StringArray};
-    use arrow_schema::{DataType, Field, Schema};
-    use std::sync::Arc;
+    use arrow_array::{
+        BinaryArray, FixedSizeBinaryArray, Float64Array, Int32Array, StringArray, StructArray,
+    };
+
+    #[test]
+    fn arrow_types_are_keyed_by_field_id() {
+        use arrow_schema::{Fields, TimeUnit};
+
+        let with_id = |field: Field, id: i32| {
+            field.with_metadata(HashMap::from([(
+                FIELD_ID_META_KEY.to_string(),
+                id.to_string(),
+            )]))
+        };
+        let schema = Schema::new(vec![
+            with_id(
+                Field::new(
+                    "ts",
+                    DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into())),
+                    true,
+                ),
+                1,
+            ),
+            with_id(
+                Field::new(
+                    "tags",
+                    DataType::List(Arc::new(with_id(
+                        Field::new("element", DataType::Utf8, false),
+                        3,
+                    ))),
+                    true,
+                ),
+                2,
+            ),
+            with_id(
+                Field::new(
+                    "point",
+                    DataType::Struct(Fields::from(vec![with_id(
+                        Field::new("x", DataType::Float64, false),
+                        5,
+                    )])),
+                    false,
+                ),
+                4,
+            ),
+            Field::new("no_id", DataType::Int32, false),
+        ]);
+
+        let types = arrow_types_by_field_id(&schema);
+        assert_eq!(types.len(), 5);
+        assert_eq!(types[&1], "Timestamp(Microsecond, Some(\"+00:00\"))");
+        assert_eq!(types[&2], "List");
+        assert_eq!(types[&3], "Utf8");
+        assert_eq!(types[&4], "Struct");
+        assert_eq!(types[&5], "Float64");
+    }
 
     fn make_test_batch() -> RecordBatch {
         let schema = Arc::new(Schema::new(vec![
@@ -97,9 +461,32 @@ mod tests {
         .unwrap()
     }
 
+    fn cells(values: &[&str]) -> Vec<Option<String>> {
+        values.iter().map(|v| Some(v.to_string())).collect()
+    }
+
+    #[test]
+    fn nulls_are_reported_as_none() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("age", DataType::Int32, true),
+            Field::new("title", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![Some(30), None])),
+                Arc::new(StringArray::from(vec![None, Some("")])),
+            ],
+        )
+        .unwrap();
+        let (_, rows) = batches_to_string_rows(&[batch], 0, 100, BinaryFormat::Hex).unwrap();
+        assert_eq!(rows[0], vec![Some("30".to_string()), None]);
+        assert_eq!(rows[1], vec![None, Some(String::new())]);
+    }
+
     #[test]
     fn empty_batches() {
-        let (cols, rows) = batches_to_string_rows(&[], 0, 100).unwrap();
+        let (cols, rows) = batches_to_string_rows(&[], 0, 100, BinaryFormat::Hex).unwrap();
         assert!(cols.is_empty());
         assert!(rows.is_empty());
     }
@@ -107,28 +494,72 @@ mod tests {
     #[test]
     fn basic_conversion() {
         let batch = make_test_batch();
-        let (cols, rows) = batches_to_string_rows(&[batch], 0, 100).unwrap();
+        let (cols, rows) = batches_to_string_rows(&[batch], 0, 100, BinaryFormat::Hex).unwrap();
         assert_eq!(cols, vec!["id", "name"]);
         assert_eq!(rows.len(), 3);
-        assert_eq!(rows[0], vec!["1", "Alice"]);
-        assert_eq!(rows[1], vec!["2", "Bob"]);
-        assert_eq!(rows[2], vec!["3", "Charlie"]);
+        assert_eq!(rows[0], cells(&["1", "Alice"]));
+        assert_eq!(rows[1], cells(&["2", "Bob"]));
+        assert_eq!(rows[2], cells(&["3", "Charlie"]));
     }
 
     #[test]
     fn pagination_offset() {
         let batch = make_test_batch();
-        let (_, rows) = batches_to_string_rows(&[batch], 1, 100).unwrap();
+        let (_, rows) = batches_to_string_rows(&[batch], 1, 100, BinaryFormat::Hex).unwrap();
         assert_eq!(rows.len(), 2);
-        assert_eq!(rows[0], vec!["2", "Bob"]);
+        assert_eq!(rows[0], cells(&["2", "Bob"]));
     }
 
     #[test]
     fn pagination_limit() {
         let batch = make_test_batch();
-        let (_, rows) = batches_to_string_rows(&[batch], 0, 2).unwrap();
+        let (_, rows) = batches_to_string_rows(&[batch], 0, 2, BinaryFormat::Hex).unwrap();
         assert_eq!(rows.len(), 2);
-        assert_eq!(rows[1], vec!["2", "Bob"]);
+        assert_eq!(rows[1], cells(&["2", "Bob"]));
+    }
+
+    #[test]
+    fn batches_with_differing_schemas_align_by_name() {
+        // Written before `email` was added, with columns in a different order.
+        let old = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("name", DataType::Utf8, false),
+                Field::new("id", DataType::Int32, false),
+            ])),
+            vec![
+                Arc::new(StringArray::from(vec!["Dora"])),
+                Arc::new(Int32Array::from(vec![4])),
+            ],
+        )
+        .unwrap();
+        let new = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("id", DataType::Int32, false),
+                Field::new("name", DataType::Utf8, false),
+                Field::new("email", DataType::Utf8, true),
+            ])),
+            vec![
+                Arc::new(Int32Array::from(vec![5])),
+                Arc::new(StringArray::from(vec!["Eve"])),
+                Arc::new(StringArray::from(vec!["eve@example.com"])),
+            ],
+        )
+        .unwrap();
+        let batches = [make_test_batch(), old, new];
+
+        let (cols, rows) = batches_to_string_rows(&batches, 2, 100, BinaryFormat::Hex).unwrap();
+        assert_eq!(cols, vec!["id", "name", "email"]);
+        assert_eq!(column_names(&batches), cols);
+        assert_eq!(
+            column_types(&batches),
+            vec![DataType::Int32, DataType::Utf8, DataType::Utf8]
+        );
+        assert_eq!(
+            rows[0],
+            vec![Some("3".into()), Some("Charlie".into()), None]
+        );
+        assert_eq!(rows[1], vec![Some("4".into()), Some("Dora".into()), None]);
+        assert_eq!(rows[2], cells(&["5", "Eve", "eve@example.com"]));
     }
 
     #[test]
@@ -144,4 +575,207 @@ mod tests {
         assert_eq!(column_names(&[batch]), vec!["id", "name"]);
         assert!(column_names(&[]).is_empty());
     }
+
+    #[test]
+    fn column_types_works() {
+        let batch = make_test_batch();
+        assert_eq!(
+            column_types(&[batch]),
+            vec![DataType::Int32, DataType::Utf8]
+        );
+        assert!(column_types(&[]).is_empty());
+    }
+
+    fn float_batch(values: Vec<Option<f64>>) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "salary",
+            DataType::Float64,
+            true,
+        )]));
+        RecordBatch::try_new(schema, vec![Arc::new(Float64Array::from(values))]).unwrap()
+    }
+
+    #[test]
+    fn column_stats_buckets_values_across_batches() {
+        let batches = [
+            float_batch(vec![Some(0.0), Some(1.0), None]),
+            float_batch(vec![Some(2.0), Some(9.0), Some(10.0)]),
+        ];
+        let stats = column_stats(&batches, "salary", 5).unwrap().unwrap();
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.nulls, 1);
+        assert_eq!(stats.range, Some((0.0, 10.0)));
+        assert_eq!(stats.mean, Some(4.4));
+        assert_eq!(stats.histogram, vec![2, 1, 0, 0, 2]);
+    }
+
+    #[test]
+    fn column_stats_handles_degenerate_columns() {
+        let nulls = column_stats(&[float_batch(vec![None, None])], "salary", 5)
+            .unwrap()
+            .unwrap();
+        assert_eq!(nulls.nulls, 2);
+        assert_eq!(nulls.range, None);
+        assert!(nulls.histogram.is_empty());
+
+        let single = column_stats(&[float_batch(vec![Some(3.0), Some(3.0)])], "salary", 5)
+            .unwrap()
+            .unwrap();
+        assert_eq!(single.range, Some((3.0, 3.0)));
+        assert_eq!(single.histogram, vec![2]);
+
+        assert_eq!(column_stats(&[make_test_batch()], "name", 5).unwrap(), None);
+        assert_eq!(
+            column_stats(&[make_test_batch()], "missing", 5).unwrap(),
+            None
+        );
+    }
+
+    fn binary_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("payload", DataType::Binary, true),
+            Field::new("digest", DataType::FixedSizeBinary(2), false),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(BinaryArray::from(vec![Some(b"caf\xe9".as_ref()), None])),
+                Arc::new(
+                    FixedSizeBinaryArray::try_from_iter(
+                        vec![[0xab, 0x01], [0x00, 0xff]].into_iter(),
+                    )
+                    .unwrap(),
+                ),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn decimals_keep_their_scale() {
+        use arrow_array::Decimal128Array;
+
+        let prices = Decimal128Array::from(vec![
+            Some(1999),
+            Some(10),
+            Some(-5),
+            Some(12_345_678_901_234_567_890),
+            None,
+        ])
+        .with_precision_and_scale(38, 2)
+        .unwrap();
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "price",
+            DataType::Decimal128(38, 2),
+            true,
+        )]));
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(prices)]).unwrap();
+
+        let (_, rows) = batches_to_string_rows(&[batch], 0, 100, BinaryFormat::Hex).unwrap();
+        let cells: Vec<Option<String>> = rows.into_iter().flatten().collect();
+        assert_eq!(
+            cells,
+            vec![
+                Some("19.99".to_string()),
+                Some("0.10".to_string()),
+                Some("-0.05".to_string()),
+                Some("123456789012345678.90".to_string()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn binary_as_hex() {
+        let (_, rows) =
+            batches_to_string_rows(&[binary_batch()], 0, 100, BinaryFormat::Hex).unwrap();
+        assert_eq!(rows[0], cells(&["636166e9", "ab01"]));
+        assert_eq!(rows[1], vec![None, Some("00ff".to_string())]);
+    }
+
+    #[test]
+    fn binary_as_base64() {
+        let (_, rows) =
+            batches_to_string_rows(&[binary_batch()], 0, 100, BinaryFormat::Base64).unwrap();
+        assert_eq!(rows[0], cells(&["Y2Fm6Q==", "qwE="]));
+    }
+
+    #[test]
+    fn binary_as_utf8_lossy() {
+        let (_, rows) =
+            batches_to_string_rows(&[binary_batch()], 0, 100, BinaryFormat::Utf8Lossy).unwrap();
+        assert_eq!(rows[0][0].as_deref(), Some("caf\u{fffd}"));
+    }
+
+    /// `metrics: {cpu: {p99: double}, host: utf8}` over three rows; the second `metrics` is null.
+    fn nested_batch() -> RecordBatch {
+        let p99 = Field::new("p99", DataType::Float64, true);
+        let cpu = StructArray::from(vec![(
+            Arc::new(p99.clone()),
+            Arc::new(Float64Array::from(vec![0.5, 0.7, 0.9])) as ArrayRef,
+        )]);
+        let cpu_field = Field::new("cpu", cpu.data_type().clone(), true);
+        let host = Field::new("host", DataType::Utf8, true);
+        let metrics = StructArray::try_new(
+            vec![cpu_field, host].into(),
+            vec![
+                Arc::new(cpu),
+                Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            ],
+            Some(NullBuffer::from(vec![true, false, true])),
+        )
+        .unwrap();
+        let schema = Schema::new(vec![Field::new(
+            "metrics",
+            metrics.data_type().clone(),
+            true,
+        )]);
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(metrics)]).unwrap()
+    }
+
+    #[test]
+    fn path_segments_prefer_the_longest_name() {
+        let names = ["a", "a.b", "ab"];
+        assert_eq!(match_path_segment(names, "a.b.c"), Some((1, Some("c"))));
+        assert_eq!(match_path_segment(names, "a.x"), Some((0, Some("x"))));
+        assert_eq!(match_path_segment(names, "ab"), Some((2, None)));
+        assert_eq!(match_path_segment(names, "abc"), None);
+        assert_eq!(match_path_segment(names, "a."), None);
+    }
+
+    #[test]
+    fn nested_leaves_become_flat_columns() {
+        let paths = vec!["metrics.cpu.p99".to_string(), "metrics".to_string()];
+        let batches = flatten_nested_columns(vec![nested_batch()], &paths).unwrap();
+        assert_eq!(column_names(&batches), ["metrics", "metrics.cpu.p99"]);
+        assert_eq!(column_types(&batches)[1], DataType::Float64);
+
+        let (_, rows) = batches_to_string_rows(&batches, 0, 10, BinaryFormat::Hex).unwrap();
+        let p99: Vec<Option<String>> = rows.into_iter().map(|r| r[1].clone()).collect();
+        // Null under a null parent struct.
+        assert_eq!(
+            p99,
+            [Some("0.5".to_string()), None, Some("0.9".to_string())]
+        );
+    }
+
+    #[test]
+    fn unresolvable_nested_paths_are_errors() {
+        let err = |path: &str| {
+            flatten_nested_columns(vec![nested_batch()], &[path.to_string()])
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("metrics.cpu.p999"),
+            "cannot resolve `metrics.cpu.p999`: `metrics.cpu` has no field `p999`"
+        );
+        assert_eq!(
+            err("metrics.host.len"),
+            "cannot resolve `metrics.host.len`: `metrics.host` is Utf8, not a struct"
+        );
+        // Columns the batch doesn't have are left to the schema-level checks.
+        let batches = flatten_nested_columns(vec![nested_batch()], &["other.x".into()]).unwrap();
+        assert_eq!(column_names(&batches), ["metrics"]);
+    }
 }
@@ -1,48 +1,166 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use anyhow::{bail, Context, Result};
+use clap::Args;
 use iceberg::Catalog;
-use iceberg_catalog_rest::RestCatalogBuilder;
+use iceberg_catalog_rest::{RestCatalog, RestCatalogBuilder};
 
-use super::file_io::{storage_props, StorageConfig};
+use super::file_io::{storage_props, LocationOverride, StorageConfig};
 use super::TableHandle;
 
-/// Load an Iceberg table from a REST catalog.
-pub async fn load_from_catalog(
-    uri: &str,
-    table_name: &str,
-    config: &StorageConfig,
-) -> Result<TableHandle> {
-    let parts: Vec<&str> = table_name.split('.').collect();
+/// REST catalog connection options beyond the URI: authentication and table resolution.
+#[derive(Args, Clone, Debug, Default)]
+pub struct CatalogOptions {
+    /// Namespace for a --table given without one, e.g. "db" or "db.schema"
+    #[arg(long)]
+    pub namespace: Option<String>,
+
+    /// Warehouse to request from the catalog, required by multi-tenant catalogs
+    #[arg(long, value_parser = parse_non_empty)]
+    pub warehouse: Option<String>,
+
+    /// Path prefix inserted after /v1/ in catalog requests, e.g. a catalog name
+    #[arg(long, value_parser = parse_prefix)]
+    pub prefix: Option<String>,
+
+    /// Bearer token sent with every catalog request
+    #[arg(long, env = "ICEBERG_CATALOG_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// OAuth2 client credentials as "client_id:client_secret", exchanged for a token
+    #[arg(long, env = "ICEBERG_CATALOG_CREDENTIAL", hide_env_values = true)]
+    pub credential: Option<String>,
+
+    /// OAuth2 token endpoint, if not the catalog's own /v1/oauth/tokens
+    #[arg(long)]
+    pub oauth2_server_uri: Option<String>,
+}
+
+fn parse_non_empty(value: &str) -> Result<String> {
+    let value = value.trim();
+    if value.is_empty() {
+        bail!("value must not be empty");
+    }
+    Ok(value.to_string())
+}
+
+/// Accept "name" as well as "/name/", since the prefix is joined with slashes on both sides.
+fn parse_prefix(value: &str) -> Result<String> {
+    let prefix = value.trim().trim_matches('/');
+    if prefix.is_empty() {
+        bail!("prefix must not be empty");
+    }
+    Ok(prefix.to_string())
+}
+
+/// Build the REST catalog's warehouse, prefix and auth properties.
+pub fn catalog_props(options: &CatalogOptions) -> HashMap<String, String> {
+    let mut props = HashMap::new();
+
+    if let Some(ref warehouse) = options.warehouse {
+        props.insert("warehouse".to_string(), warehouse.clone());
+    }
+    if let Some(ref prefix) = options.prefix {
+        props.insert("prefix".to_string(), prefix.clone());
+    }
+    if let Some(ref token) = options.token {
+        props.insert("token".to_string(), token.clone());
+    }
+    if let Some(ref credential) = options.credential {
+        props.insert("credential".to_string(), credential.clone());
+    }
+    if let Some(ref uri) = options.oauth2_server_uri {
+        props.insert("oauth2-server-uri".to_string(), uri.clone());
+    }
+
+    props
+}
+
+/// Resolve `--table` into a table identifier. A dotted name is fully qualified
+/// (`db.schema.table`); a bare name is looked up in `--namespace`.
+fn table_ident(table_name: &str, namespace: Option<&str>) -> Result<iceberg::TableIdent> {
+    let mut parts: Vec<&str> = table_name.split('.').collect();
     if parts.len() < 2 {
+        match namespace {
+            Some(ns) => parts = ns.split('.').chain([table_name]).collect(),
+            None => bail!(
+                "table name must be fully qualified (e.g., 'database.table') or paired with \
+                 --namespace, got: {}",
+                table_name
+            ),
+        }
+    }
+    if parts.iter().any(|p| p.is_empty()) {
         bail!(
-            "table name must be fully qualified (e.g., 'database.table'), got: {}",
-            table_name
+            "table name has an empty namespace or table part: {}",
+            parts.join(".")
         );
     }
 
-    let namespace = &parts[..parts.len() - 1];
-    let table = parts[parts.len() - 1];
+    let (table, namespace) = parts.split_last().expect("at least two parts");
+    Ok(iceberg::TableIdent::new(
+        iceberg::NamespaceIdent::from_strs(namespace)?,
+        table.to_string(),
+    ))
+}
+
+/// A table in a REST catalog together with the catalog client, so the table can be loaded
+/// again to pick up new commits without reconnecting: the client keeps its HTTP connections,
+/// catalog config and OAuth2 token.
+#[derive(Clone)]
+pub struct CatalogTable {
+    catalog: Arc<RestCatalog>,
+    ident: iceberg::TableIdent,
+    /// `--table` as given, for messages.
+    table_name: String,
+    uri: String,
+    location_overrides: Vec<LocationOverride>,
+}
 
-    let mut props = storage_props(config);
-    props.insert("uri".to_string(), uri.to_string());
+impl CatalogTable {
+    /// Build the catalog client for `table_name`.
+    pub async fn connect(
+        uri: &str,
+        table_name: &str,
+        config: &StorageConfig,
+        options: &CatalogOptions,
+    ) -> Result<Self> {
+        let ident = table_ident(table_name, options.namespace.as_deref())?;
 
-    let catalog =
-        iceberg::CatalogBuilder::load(RestCatalogBuilder::default(), "rest_catalog", props)
-            .await
-            .with_context(|| format!("failed to connect to REST catalog at {}", uri))?;
+        let mut props = storage_props(config);
+        props.extend(catalog_props(options));
+        props.insert("uri".to_string(), uri.to_string());
 
-    let table_ident = iceberg::TableIdent::new(
-        iceberg::NamespaceIdent::from_strs(namespace)?,
-        table.to_string(),
-    );
+        let catalog =
+            iceberg::CatalogBuilder::load(RestCatalogBuilder::default(), "rest_catalog", props)
+                .await
+                .with_context(|| format!("failed to connect to REST catalog at {}", uri))?;
 
-    let loaded_table = catalog.load_table(&table_ident).await.with_context(|| {
-        format!(
-            "failed to load table '{}' from catalog at {}",
-            table_name, uri
-        )
-    })?;
+        Ok(Self {
+            catalog: Arc::new(catalog),
+            ident,
+            table_name: table_name.to_string(),
+            uri: uri.to_string(),
+            location_overrides: config.location_overrides.clone(),
+        })
+    }
 
-    Ok(TableHandle::new(loaded_table))
+    /// Fetch the table's latest metadata from the catalog.
+    #[tracing::instrument(skip(self), fields(table = %self.table_name))]
+    pub async fn load(&self) -> Result<TableHandle> {
+        let table = self
+            .catalog
+            .load_table(&self.ident)
+            .await
+            .with_context(|| {
+                format!(
+                    "failed to load table '{}' from catalog at {}",
+                    self.table_name, self.uri
+                )
+            })?;
+        Ok(TableHandle::new(table).with_location_overrides(self.location_overrides.clone()))
+    }
 }
 
 #[cfg(test)]
@@ -52,9 +170,137 @@ mod tests {
     #[tokio::test]
     async fn invalid_table_name_errors() {
         let config = StorageConfig::default();
-        let result = load_from_catalog("http://localhost:8181", "no_namespace", &config).await;
+        let result = CatalogTable::connect(
+            "http://localhost:8181",
+            "no_namespace",
+            &config,
+            &CatalogOptions::default(),
+        )
+        .await;
         assert!(result.is_err());
         let err = format!("{}", result.err().unwrap());
         assert!(err.contains("fully qualified"));
     }
+
+    /// Times reloading the table through a new client against reloading through a kept one.
+    /// Start the catalog with `make generate-catalog-data` in `examples/`, then run
+    /// `cargo test reload_reconnect_vs_reuse -- --ignored --nocapture`.
+    #[tokio::test]
+    #[ignore = "requires a REST catalog"]
+    async fn reload_reconnect_vs_reuse() {
+        use std::time::Instant;
+
+        const ROUNDS: u32 = 10;
+        let uri = std::env::var("CATALOG_URI").unwrap_or("http://localhost:8181".to_string());
+        let config = StorageConfig {
+            s3_endpoint: Some(
+                std::env::var("S3_ENDPOINT").unwrap_or("http://localhost:9000".to_string()),
+            ),
+            s3_access_key_id: Some("minioadmin".to_string()),
+            s3_secret_access_key: Some("minioadmin".to_string()),
+            ..Default::default()
+        };
+        let options = CatalogOptions::default();
+        let connect = || CatalogTable::connect(&uri, "demo.sample_data", &config, &options);
+
+        let started = Instant::now();
+        for _ in 0..ROUNDS {
+            connect().await.unwrap().load().await.unwrap();
+        }
+        let reconnect = started.elapsed() / ROUNDS;
+
+        let catalog_table = connect().await.unwrap();
+        catalog_table.load().await.unwrap();
+        let started = Instant::now();
+        for _ in 0..ROUNDS {
+            catalog_table.load().await.unwrap();
+        }
+        let reuse = started.elapsed() / ROUNDS;
+
+        eprintln!(
+            "reload with reconnect: {:?}, reusing the client: {:?}",
+            reconnect, reuse
+        );
+    }
+
+    #[test]
+    fn table_ident_from_dotted_name() {
+        let ident = table_ident("db.schema.events", None).unwrap();
+        assert_eq!(
+            ident,
+            iceberg::TableIdent::from_strs(["db", "schema", "events"]).unwrap()
+        );
+
+        // A dotted name stays fully qualified even when --namespace is set.
+        let ident = table_ident("db.events", Some("other")).unwrap();
+        assert_eq!(
+            ident,
+            iceberg::TableIdent::from_strs(["db", "events"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn table_ident_from_bare_name_and_namespace() {
+        let ident = table_ident("events", Some("db.schema")).unwrap();
+        assert_eq!(
+            ident,
+            iceberg::TableIdent::from_strs(["db", "schema", "events"]).unwrap()
+        );
+    }
+
+    #[test]
+    fn table_ident_bare_name_without_namespace_errors() {
+        let err = table_ident("events", None).unwrap_err().to_string();
+        assert!(err.contains("--namespace"));
+        assert!(table_ident("db..events", None).is_err());
+        assert!(table_ident("events", Some("")).is_err());
+    }
+
+    #[test]
+    fn catalog_props_forward_auth() {
+        let options = CatalogOptions {
+            token: Some("tok".into()),
+            credential: Some("client:secret".into()),
+            oauth2_server_uri: Some("https://auth.example.com/token".into()),
+            ..Default::default()
+        };
+        let props = catalog_props(&options);
+        assert_eq!(props.get("token").map(String::as_str), Some("tok"));
+        assert_eq!(
+            props.get("credential").map(String::as_str),
+            Some("client:secret")
+        );
+        assert_eq!(
+            props.get("oauth2-server-uri").map(String::as_str),
+            Some("https://auth.example.com/token")
+        );
+    }
+
+    #[test]
+    fn catalog_props_forward_warehouse_and_prefix() {
+        let options = CatalogOptions {
+            warehouse: Some("analytics".into()),
+            prefix: Some("prod".into()),
+            ..Default::default()
+        };
+        let props = catalog_props(&options);
+        assert_eq!(
+            props.get("warehouse").map(String::as_str),
+            Some("analytics")
+        );
+        assert_eq!(props.get("prefix").map(String::as_str), Some("prod"));
+    }
+
+    #[test]
+    fn catalog_props_empty_by_default() {
+        assert!(catalog_props(&CatalogOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn prefix_is_trimmed_of_slashes() {
+        assert_eq!(parse_prefix("/prod/").unwrap(), "prod");
+        assert_eq!(parse_prefix("a/b").unwrap(), "a/b");
+        assert!(parse_prefix("//").is_err());
+        assert!(parse_non_empty("  ").is_err());
+    }
 }
@@ -1,7 +1,9 @@
+use std::future::Future;
+
 use anyhow::{bail, Context, Result};
 use iceberg::io::FileIO;
 
-use super::file_io::{build_file_io, StorageConfig};
+use super::file_io::{build_file_io, list_files, StorageConfig};
 use super::TableHandle;
 
 /// Load an Iceberg table by resolving its metadata directly from storage.
@@ -11,18 +13,25 @@ use super::TableHandle;
 ///
 /// Auto-discovery logic:
 /// 1. If path ends in `.json` → use directly as metadata file
-/// 2. Try `{path}/metadata/version-hint.text` → read version → `v{N}.metadata.json`
-/// 3. (Local FS only) Scan `metadata/` for highest-numbered `v*.metadata.json`
+/// 2. Find the table directory: a path ending in `metadata` stands for its parent, and a
+///    local path without a `metadata/` subdirectory falls back to its parent or to its only
+///    subdirectory that has one
+/// 3. Try `{path}/metadata/version-hint.text` → read version → `v{N}.metadata.json`
+/// 4. Find the highest-numbered metadata file in `metadata/` by listing the directory,
+///    accepting both `v{N}.metadata.json` and `{NNNNN}-{uuid}.metadata.json`. On remote
+///    storage that can't be listed, probe for `v{N}.metadata.json` instead
+#[tracing::instrument(skip(config))]
 pub async fn load_direct(path: &str, config: &StorageConfig) -> Result<TableHandle> {
     let path = &normalize_local_path(path);
     let file_io = build_file_io(path, config)?;
-    let metadata_location = resolve_metadata_path(path, &file_io)
+    let metadata_location = resolve_metadata_path(path, &file_io, config)
         .await
         .context("failed to locate metadata file")?;
+    tracing::info!(%metadata_location, "resolved metadata file");
 
     let input = file_io
         .new_input(&metadata_location)
-        .context("failed to create input for metadata")?;
+        .with_context(|| format!("failed to open metadata file: {}", metadata_location))?;
     let bytes = input
         .read()
         .await
@@ -38,29 +47,109 @@ pub async fn load_direct(path: &str, config: &StorageConfig) -> Result<TableHand
         .metadata_location(metadata_location)
         .build()?;
 
-    Ok(TableHandle::new(table))
+    Ok(TableHandle::new(table).with_location_overrides(config.location_overrides.clone()))
+}
+
+/// A table's `version-hint.text`, re-read to notice commits by other writers.
+pub struct VersionHint {
+    base: String,
+    file_io: FileIO,
+}
+
+impl VersionHint {
+    pub fn new(path: &str, config: &StorageConfig) -> Result<Self> {
+        let path = normalize_local_path(path);
+        let file_io = build_file_io(&path, config)?;
+        Ok(Self {
+            base: table_root(path.trim_end_matches('/')),
+            file_io,
+        })
+    }
+
+    /// The metadata file the hint points at, resolved the way `load_direct` resolves it, or
+    /// `None` when the table has no hint.
+    pub async fn read(&self) -> Result<Option<String>> {
+        let hint_path = format!("{}/metadata/version-hint.text", self.base);
+        if !self.file_io.exists(&hint_path).await? {
+            return Ok(None);
+        }
+        let bytes = self.file_io.new_input(&hint_path)?.read().await?;
+        metadata_path_from_hint(&self.base, &bytes)
+            .map(Some)
+            .with_context(|| format!("invalid version hint: {}", hint_path))
+    }
+}
+
+/// Short name of a metadata file for messages: `v12` for versioned files, the file name
+/// otherwise.
+pub fn metadata_version_label(location: &str) -> String {
+    let name = location.rsplit('/').next().unwrap_or(location);
+    match metadata_file_version(name) {
+        Some(version) => format!("v{}", version),
+        None => name.to_string(),
+    }
 }
 
-async fn resolve_metadata_path(path: &str, file_io: &FileIO) -> Result<String> {
+/// Whether the metadata file at `candidate` is newer than the one at `loaded`. Versions are
+/// compared when both file names carry one, so a hint lagging behind the loaded file is not
+/// taken for a new commit; other names only differ or not.
+pub fn is_newer_metadata(candidate: &str, loaded: &str) -> bool {
+    let version = |location: &str| metadata_file_version(location.rsplit('/').next()?);
+    match (version(candidate), version(loaded)) {
+        (Some(candidate), Some(loaded)) => candidate > loaded,
+        _ => candidate != loaded,
+    }
+}
+
+async fn resolve_metadata_path(
+    path: &str,
+    file_io: &FileIO,
+    config: &StorageConfig,
+) -> Result<String> {
     if path.ends_with(".json") {
         return Ok(path.to_string());
     }
 
-    let base = path.trim_end_matches('/');
+    let base = &table_root(path.trim_end_matches('/'));
+    if base != path.trim_end_matches('/') {
+        tracing::info!(%base, "resolved table directory");
+    }
 
     // Try version-hint.text first (works on all backends)
     let hint_path = format!("{}/metadata/version-hint.text", base);
+    tracing::debug!(%hint_path, "reading version hint");
     if let Ok(input) = file_io.new_input(&hint_path) {
         if let Ok(bytes) = input.read().await {
-            let hint = String::from_utf8(bytes.to_vec())
-                .context("version-hint.text is not valid UTF-8")?;
-            let version = hint.trim();
-            return Ok(format!("{}/metadata/v{}.metadata.json", base, version));
+            return metadata_path_from_hint(base, &bytes)
+                .with_context(|| format!("invalid version hint: {}", hint_path));
         }
     }
 
-    // Fallback: scan directory (local filesystem only)
-    if !is_remote_path(base) {
+    // Fallback: find the newest metadata file without a hint
+    if is_remote_path(base) {
+        let metadata_dir = format!("{}/metadata", base);
+        match list_files(&metadata_dir, config).await {
+            Ok(Some(names)) => {
+                tracing::debug!(
+                    files = names.len(),
+                    "no version hint, listed remote metadata"
+                );
+                if let Some(name) = newest_metadata_file(&names) {
+                    return Ok(format!("{}/{}", metadata_dir, name));
+                }
+            }
+            listed => {
+                if let Err(e) = listed {
+                    tracing::debug!(error = %e, "listing failed, falling back to probing");
+                }
+                tracing::debug!("no version hint, probing remote metadata files");
+                if let Some(p) = probe_remote_metadata(base, file_io).await {
+                    return Ok(p);
+                }
+            }
+        }
+    } else {
+        tracing::debug!("no version hint, scanning local metadata directory");
         if let Some(p) = scan_local_metadata_dir(base).await {
             return Ok(p);
         }
@@ -68,15 +157,71 @@ async fn resolve_metadata_path(path: &str, file_io: &FileIO) -> Result<String> {
 
     bail!(
         "no Iceberg metadata found at: {}\n\
-         Tried: {}/metadata/version-hint.text\n\
+         Tried: {}/metadata/version-hint.text and {}/metadata/*.metadata.json\n\
          \n\
-         Hint: ensure the table has a version-hint.text file, or pass the \
-         full path to the metadata JSON file directly",
+         Hint: point at the table directory (the one containing metadata/), make sure \
+         the table has a version-hint.text file, or pass the full path to the metadata \
+         JSON file directly",
         path,
+        base,
         base
     )
 }
 
+/// The table directory `base` refers to. Pointing at the `metadata/` directory itself is a
+/// common mistake, so a trailing `metadata` component is dropped. Local paths that have no
+/// `metadata/` subdirectory are also tried one level up (e.g. the table's `data/`
+/// directory) and one level down (a directory holding a single table). Anything else is
+/// returned unchanged, so the "no metadata found" error names the path that was given.
+fn table_root(base: &str) -> String {
+    if let Some(parent) = base.strip_suffix("/metadata") {
+        return parent.to_string();
+    }
+    if is_remote_path(base) {
+        return base.to_string();
+    }
+
+    let has_metadata = |dir: &std::path::Path| dir.join("metadata").is_dir();
+    let dir = std::path::Path::new(base);
+    if has_metadata(dir) {
+        return base.to_string();
+    }
+    if let Some(parent) = dir.parent().filter(|p| has_metadata(p)) {
+        return parent.to_string_lossy().to_string();
+    }
+    let tables: Vec<std::path::PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|child| has_metadata(child))
+        .collect();
+    match tables.as_slice() {
+        [table] => table.to_string_lossy().to_string(),
+        _ => base.to_string(),
+    }
+}
+
+/// Turn the contents of `version-hint.text` into a metadata file location.
+/// Tolerates a UTF-8 BOM, CRLF line endings and stray invalid bytes. Most writers store
+/// just the version number, but some store a file name or a full path instead.
+fn metadata_path_from_hint(base: &str, bytes: &[u8]) -> Result<String> {
+    let hint = String::from_utf8_lossy(bytes);
+    let hint = hint
+        .trim_start_matches('\u{feff}')
+        .trim_matches(|c: char| c.is_whitespace() || c == '\u{fffd}');
+    if hint.is_empty() {
+        bail!("version hint is empty");
+    }
+
+    if hint.contains('/') {
+        Ok(hint.to_string())
+    } else if hint.ends_with(".json") {
+        Ok(format!("{}/metadata/{}", base, hint))
+    } else {
+        Ok(format!("{}/metadata/v{}.metadata.json", base, hint))
+    }
+}
+
 /// Iceberg's FileIO requires absolute paths for local files.
 /// Canonicalize relative paths; leave remote URLs untouched.
 fn normalize_local_path(path: &str) -> String {
@@ -93,35 +238,172 @@ fn is_remote_path(path: &str) -> bool {
     path.starts_with("s3://") || path.starts_with("gs://")
 }
 
-async fn scan_local_metadata_dir(base: &str) -> Option<String> {
-    let metadata_dir = std::path::PathBuf::from(base).join("metadata");
-    let mut entries = tokio::fs::read_dir(&metadata_dir).await.ok()?;
+/// Highest metadata version probed on remote storage.
+const MAX_PROBED_VERSION: i64 = 1 << 20;
 
-    let mut max_version: Option<i64> = None;
-    let mut best_path: Option<String> = None;
+/// When the store can't be listed, find the newest `v{N}.metadata.json` by checking
+/// which versions exist. Storage errors count as missing files, so backends that reject
+/// the checks fall through to the "no metadata found" error.
+async fn probe_remote_metadata(base: &str, file_io: &FileIO) -> Option<String> {
+    let location = |n: i64| format!("{}/metadata/v{}.metadata.json", base, n);
+    let version = highest_version(|n| {
+        let path = location(n);
+        async move { file_io.exists(&path).await.unwrap_or(false) }
+    })
+    .await?;
+    Some(location(version))
+}
 
-    while let Ok(Some(entry)) = entries.next_entry().await {
-        let name = entry.file_name().to_string_lossy().to_string();
-        if !name.starts_with('v') || !name.ends_with(".metadata.json") {
-            continue;
+/// Find the highest `n` for which `exists(n)` holds with a logarithmic number of probes.
+/// Assumes the existing versions form one contiguous run that contains a power of two,
+/// which holds unless old metadata files have been expired.
+async fn highest_version<F, Fut>(mut exists: F) -> Option<i64>
+where
+    F: FnMut(i64) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let mut low = 1;
+    while !exists(low).await {
+        low *= 2;
+        if low > MAX_PROBED_VERSION {
+            return None;
         }
-        let version_str = &name[1..name.len() - ".metadata.json".len()];
-        let Ok(v) = version_str.parse::<i64>() else {
+    }
+
+    // Gallop upward until a version is missing, then binary search the gap.
+    let mut high = low * 2;
+    while high <= MAX_PROBED_VERSION && exists(high).await {
+        low = high;
+        high *= 2;
+    }
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if exists(mid).await {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some(low)
+}
+
+/// Version number of a metadata file name, in either the `v{N}.metadata.json` style or the
+/// `{NNNNN}-{uuid}.metadata.json` style that some engines write.
+fn metadata_file_version(name: &str) -> Option<i64> {
+    let stem = name.strip_suffix(".metadata.json")?;
+    if let Some(version) = stem.strip_prefix('v') {
+        return version.parse().ok();
+    }
+    let (version, uuid) = stem.split_once('-')?;
+    if uuid.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    version.parse().ok()
+}
+
+/// The metadata file with the highest version among `names`; the first one wins a tie.
+fn newest_metadata_file(names: &[String]) -> Option<&str> {
+    let mut best: Option<(i64, &str)> = None;
+    for name in names {
+        let Some(v) = metadata_file_version(name) else {
             continue;
         };
-        if max_version.is_none_or(|mv| v > mv) {
-            max_version = Some(v);
-            best_path = Some(entry.path().to_string_lossy().to_string());
+        if best.is_none_or(|(mv, _)| v > mv) {
+            best = Some((v, name));
         }
     }
+    best.map(|(_, name)| name)
+}
+
+async fn scan_local_metadata_dir(base: &str) -> Option<String> {
+    let metadata_dir = std::path::PathBuf::from(base).join("metadata");
+    let mut entries = tokio::fs::read_dir(&metadata_dir).await.ok()?;
+
+    let mut names = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        names.push(entry.file_name().to_string_lossy().to_string());
+    }
 
-    best_path
+    let name = newest_metadata_file(&names)?;
+    Some(metadata_dir.join(name).to_string_lossy().to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn version_labels_name_metadata_files() {
+        assert_eq!(
+            metadata_version_label("/t/metadata/v12.metadata.json"),
+            "v12"
+        );
+        assert_eq!(
+            metadata_version_label("s3://b/t/metadata/00003-6a1f.metadata.json"),
+            "v3"
+        );
+        assert_eq!(
+            metadata_version_label("/t/metadata/current.json"),
+            "current.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn version_hint_follows_new_commits() {
+        let dir = std::env::temp_dir().join(format!("icepeek-hint-{}", std::process::id()));
+        let path = dir.display().to_string();
+        let hint = VersionHint::new(&path, &StorageConfig::default()).unwrap();
+        assert_eq!(hint.read().await.unwrap(), None);
+
+        std::fs::create_dir_all(dir.join("metadata")).unwrap();
+        let hint_file = dir.join("metadata").join("version-hint.text");
+        std::fs::write(&hint_file, "3\n").unwrap();
+        let first = hint.read().await.unwrap().unwrap();
+        std::fs::write(&hint_file, "4").unwrap();
+        let second = hint.read().await.unwrap().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(first.ends_with("/metadata/v3.metadata.json"));
+        assert!(second.ends_with("/metadata/v4.metadata.json"));
+    }
+
+    #[test]
+    fn table_root_adjusts_paths_near_the_table() {
+        let root = std::env::temp_dir().join(format!("icepeek-root-{}", std::process::id()));
+        let table = root.join("warehouse").join("orders");
+        std::fs::create_dir_all(table.join("metadata")).unwrap();
+        std::fs::create_dir_all(table.join("data")).unwrap();
+        let path = |p: &std::path::Path| p.to_string_lossy().to_string();
+        let expected = path(&table);
+
+        let given = table_root(&path(&table));
+        let metadata_dir = table_root(&path(&table.join("metadata")));
+        let data_dir = table_root(&path(&table.join("data")));
+        let warehouse = path(&root.join("warehouse"));
+        std::fs::create_dir_all(root.join("warehouse").join("customers")).unwrap();
+        let with_one_table = table_root(&warehouse);
+        std::fs::create_dir_all(root.join("warehouse").join("customers").join("metadata")).unwrap();
+        let with_two_tables = table_root(&warehouse);
+        let unrelated = table_root(&path(&root));
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(given, expected);
+        assert_eq!(metadata_dir, expected);
+        assert_eq!(data_dir, expected);
+        assert_eq!(with_one_table, expected);
+        assert_eq!(with_two_tables, warehouse);
+        assert_eq!(unrelated, path(&root));
+    }
+
+    #[test]
+    fn table_root_strips_remote_metadata_dir_only() {
+        assert_eq!(
+            table_root("s3://bucket/table/metadata"),
+            "s3://bucket/table"
+        );
+        assert_eq!(table_root("s3://bucket/table"), "s3://bucket/table");
+    }
+
     #[test]
     fn remote_path_detection() {
         assert!(is_remote_path("s3://bucket/table"));
@@ -130,6 +412,139 @@ mod tests {
         assert!(!is_remote_path("./relative/path"));
     }
 
+    async fn highest_in(range: std::ops::RangeInclusive<i64>) -> Option<i64> {
+        highest_version(|n| {
+            let found = range.contains(&n);
+            async move { found }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn probing_finds_highest_version() {
+        assert_eq!(highest_in(1..=1).await, Some(1));
+        assert_eq!(highest_in(1..=13).await, Some(13));
+        assert_eq!(highest_in(1..=64).await, Some(64));
+        assert_eq!(highest_in(5..=300).await, Some(300));
+    }
+
+    #[tokio::test]
+    async fn probing_without_metadata_gives_up() {
+        assert_eq!(highest_version(|_| async { false }).await, None);
+    }
+
+    #[test]
+    fn newest_metadata_file_ignores_other_files() {
+        let names: Vec<String> = [
+            "version-hint.text",
+            "v3.metadata.json",
+            "00012-1f0e.metadata.json",
+            "vx.metadata.json",
+            "snap-1-1-abc.avro",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(
+            newest_metadata_file(&names),
+            Some("00012-1f0e.metadata.json")
+        );
+        assert_eq!(newest_metadata_file(&names[..1]), None);
+    }
+
+    #[test]
+    fn stale_hints_are_not_newer() {
+        let loaded = "/t/metadata/v5.metadata.json";
+        assert!(is_newer_metadata("/t/metadata/v6.metadata.json", loaded));
+        assert!(!is_newer_metadata("/t/metadata/v4.metadata.json", loaded));
+        assert!(!is_newer_metadata(loaded, loaded));
+        assert!(is_newer_metadata(
+            "/t/metadata/00006-6a1f.metadata.json",
+            loaded
+        ));
+        assert!(is_newer_metadata("/t/metadata/current.json", loaded));
+        assert!(!is_newer_metadata(
+            "/t/metadata/current.json",
+            "/t/metadata/current.json"
+        ));
+    }
+
+    #[test]
+    fn metadata_file_version_accepts_both_naming_styles() {
+        assert_eq!(metadata_file_version("v3.metadata.json"), Some(3));
+        assert_eq!(
+            metadata_file_version("00012-6f1c2a9e-8c1b-4d2e-9a7f-0b3c5d7e9f11.metadata.json"),
+            Some(12)
+        );
+        assert_eq!(metadata_file_version("version-hint.text"), None);
+        assert_eq!(metadata_file_version("vx.metadata.json"), None);
+        assert_eq!(metadata_file_version("00001-.metadata.json"), None);
+        assert_eq!(metadata_file_version("abc-def.metadata.json"), None);
+    }
+
+    #[tokio::test]
+    async fn local_scan_picks_newest_across_naming_styles() {
+        let base = std::env::temp_dir().join(format!("icepeek-meta-{}", std::process::id()));
+        let metadata_dir = base.join("metadata");
+        std::fs::create_dir_all(&metadata_dir).unwrap();
+        for name in [
+            "v1.metadata.json",
+            "v2.metadata.json",
+            "00003-0b7e6f2a-1c4d-4e8f-9a0b-2c3d4e5f6a7b.metadata.json",
+            "00010-5d6e7f80-91a2-4b3c-8d4e-5f6a7b8c9d0e.metadata.json",
+            "snap-123.avro",
+        ] {
+            std::fs::write(metadata_dir.join(name), b"{}").unwrap();
+        }
+
+        let found = scan_local_metadata_dir(&base.to_string_lossy()).await;
+        std::fs::remove_dir_all(&base).unwrap();
+        assert!(found
+            .unwrap()
+            .ends_with("00010-5d6e7f80-91a2-4b3c-8d4e-5f6a7b8c9d0e.metadata.json"));
+    }
+
+    #[test]
+    fn hint_with_version_number() {
+        assert_eq!(
+            metadata_path_from_hint("/t", b"3\n").unwrap(),
+            "/t/metadata/v3.metadata.json"
+        );
+    }
+
+    #[test]
+    fn hint_with_bom_prefix() {
+        assert_eq!(
+            metadata_path_from_hint("/t", b"\xef\xbb\xbf7").unwrap(),
+            "/t/metadata/v7.metadata.json"
+        );
+    }
+
+    #[test]
+    fn hint_with_crlf_terminator() {
+        assert_eq!(
+            metadata_path_from_hint("/t", b"12\r\n").unwrap(),
+            "/t/metadata/v12.metadata.json"
+        );
+    }
+
+    #[test]
+    fn hint_with_file_name_or_full_path() {
+        assert_eq!(
+            metadata_path_from_hint("/t", b"00005-abc.metadata.json").unwrap(),
+            "/t/metadata/00005-abc.metadata.json"
+        );
+        assert_eq!(
+            metadata_path_from_hint("s3://b/t", b"s3://b/t/metadata/v2.metadata.json\n").unwrap(),
+            "s3://b/t/metadata/v2.metadata.json"
+        );
+    }
+
+    #[test]
+    fn empty_hint_errors() {
+        assert!(metadata_path_from_hint("/t", b" \r\n").is_err());
+    }
+
     #[tokio::test]
     async fn load_from_nonexistent_path_errors() {
         let config = StorageConfig::default();
@@ -154,6 +569,46 @@ mod tests {
         assert!(result.is_err(), "expected error for nonexistent S3 path");
     }
 
+    /// Needs MinIO with a `warehouse` bucket (`make -C examples setup-minio`):
+    /// `S3_ENDPOINT=http://localhost:9000 cargo test -- --ignored`
+    #[tokio::test]
+    #[ignore = "requires MinIO"]
+    async fn s3_listing_picks_newest_without_hint() {
+        let config = StorageConfig {
+            s3_endpoint: Some(
+                std::env::var("S3_ENDPOINT").unwrap_or("http://localhost:9000".to_string()),
+            ),
+            s3_access_key_id: Some("minioadmin".to_string()),
+            s3_secret_access_key: Some("minioadmin".to_string()),
+            ..Default::default()
+        };
+        let base = format!("s3://warehouse/icepeek-listing-test-{}", std::process::id());
+        let file_io = build_file_io(&base, &config).unwrap();
+        let files: Vec<String> = [
+            "00001-a1b2.metadata.json",
+            "00002-c3d4.metadata.json",
+            "notes.txt",
+        ]
+        .iter()
+        .map(|name| format!("{}/metadata/{}", base, name))
+        .collect();
+        for file in &files {
+            let output = file_io.new_output(file).unwrap();
+            output.write("{}".into()).await.unwrap();
+        }
+
+        let resolved = resolve_metadata_path(&base, &file_io, &config)
+            .await
+            .unwrap();
+        for file in &files {
+            file_io.delete(file).await.unwrap();
+        }
+        assert_eq!(
+            resolved,
+            format!("{}/metadata/00002-c3d4.metadata.json", base)
+        );
+    }
+
     #[test]
     fn normalize_leaves_remote_paths_unchanged() {
         assert_eq!(
@@ -17,6 +17,49 @@ pub struct StorageConfig {
 
     #[arg(long, env = "AWS_SECRET_ACCESS_KEY", hide = true)]
     pub s3_secret_access_key: Option<String>,
+
+    /// Read data files under OLD from NEW instead, for tables whose files were moved without
+    /// rewriting their metadata, e.g. `s3://old-bucket/=s3://new-bucket/` (repeatable)
+    #[arg(
+        long = "location-override",
+        value_name = "OLD=NEW",
+        value_parser = parse_location_override
+    )]
+    pub location_overrides: Vec<LocationOverride>,
+}
+
+/// A data file location prefix and the prefix the files now live under.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocationOverride {
+    pub from: String,
+    pub to: String,
+}
+
+fn parse_location_override(s: &str) -> Result<LocationOverride, String> {
+    let (from, to) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid location override `{}`: expected OLD=NEW", s))?;
+    if from.is_empty() || to.is_empty() {
+        return Err(format!(
+            "invalid location override `{}`: both locations are required",
+            s
+        ));
+    }
+    Ok(LocationOverride {
+        from: from.to_string(),
+        to: to.to_string(),
+    })
+}
+
+/// Where to read `path` from: the longest matching override's prefix is swapped for its
+/// new location, and paths no override matches are read as recorded.
+pub fn relocate(path: &str, overrides: &[LocationOverride]) -> String {
+    overrides
+        .iter()
+        .filter(|o| path.starts_with(&o.from))
+        .max_by_key(|o| o.from.len())
+        .map(|o| format!("{}{}", o.to, &path[o.from.len()..]))
+        .unwrap_or_else(|| path.to_string())
 }
 
 impl Default for StorageConfig {
@@ -26,6 +69,7 @@ impl Default for StorageConfig {
             s3_region: "us-east-1".to_string(),
             s3_access_key_id: None,
             s3_secret_access_key: None,
+            location_overrides: vec![],
         }
     }
 }
@@ -88,6 +132,46 @@ fn build_s3_file_io(config: &StorageConfig) -> Result<FileIO> {
         .context("failed to build S3 FileIO — check credentials and endpoint config")
 }
 
+/// List the names of the files directly inside `dir`.
+///
+/// FileIO has no listing API, so this builds an OpenDAL operator from the same settings
+/// FileIO uses. Returns `Ok(None)` for backends where listing isn't supported.
+pub async fn list_files(dir: &str, config: &StorageConfig) -> Result<Option<Vec<String>>> {
+    let Some(rest) = dir.strip_prefix("s3://") else {
+        return Ok(None);
+    };
+    let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+    let prefix = prefix.trim_matches('/');
+
+    let mut builder = opendal::services::S3::default()
+        .bucket(bucket)
+        .region(&config.s3_region);
+    if let Some(ref ep) = config.s3_endpoint {
+        builder = builder.endpoint(ep);
+    }
+    if let Some(ref key) = config.s3_access_key_id {
+        builder = builder.access_key_id(key);
+    }
+    if let Some(ref key) = config.s3_secret_access_key {
+        builder = builder.secret_access_key(key);
+    }
+    let op = opendal::Operator::new(builder)
+        .context("failed to build S3 client for listing")?
+        .finish();
+
+    let entries = op
+        .list(&format!("{}/", prefix))
+        .await
+        .with_context(|| format!("failed to list: {}", dir))?;
+    Ok(Some(
+        entries
+            .into_iter()
+            .filter(|e| e.metadata().is_file())
+            .map(|e| e.name().to_string())
+            .collect(),
+    ))
+}
+
 fn build_gcs_file_io(_config: &StorageConfig) -> Result<FileIO> {
     // TODO: Implement Google Cloud Storage support
     // Will need StorageConfig fields like:
@@ -165,6 +249,43 @@ mod tests {
         assert!(!props.contains_key("s3.secret-access-key"));
     }
 
+    #[tokio::test]
+    async fn listing_unsupported_for_local_paths() {
+        let config = StorageConfig::default();
+        let listed = list_files("/some/local/path", &config).await.unwrap();
+        assert!(listed.is_none());
+    }
+
+    #[test]
+    fn location_override_parses_old_and_new() {
+        let o = parse_location_override("s3://old/wh/=s3://new/wh/").unwrap();
+        assert_eq!(o.from, "s3://old/wh/");
+        assert_eq!(o.to, "s3://new/wh/");
+        assert!(parse_location_override("s3://old").is_err());
+        assert!(parse_location_override("=s3://new").is_err());
+    }
+
+    #[test]
+    fn relocate_swaps_the_longest_matching_prefix() {
+        let overrides = vec![
+            parse_location_override("s3://old/=s3://new/").unwrap(),
+            parse_location_override("s3://old/archive/=/mnt/archive/").unwrap(),
+        ];
+        assert_eq!(
+            relocate("s3://old/db/t/data/a.parquet", &overrides),
+            "s3://new/db/t/data/a.parquet"
+        );
+        assert_eq!(
+            relocate("s3://old/archive/b.parquet", &overrides),
+            "/mnt/archive/b.parquet"
+        );
+        assert_eq!(
+            relocate("s3://other/c.parquet", &overrides),
+            "s3://other/c.parquet"
+        );
+        assert_eq!(relocate("s3://old/d.parquet", &[]), "s3://old/d.parquet");
+    }
+
     #[test]
     fn gcs_not_yet_implemented() {
         let config = StorageConfig::default();
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::model::table_info::{DataFileInfo, ManifestInfo};
+
+/// Number of snapshots whose manifests are kept by default.
+pub const DEFAULT_MANIFEST_CACHE_SIZE: usize = 16;
+
+/// Loaded manifests of one snapshot, grouped the same way as the Files and Stats tabs.
+/// Manifests that failed to load carry their error and no files.
+#[derive(Debug, Clone)]
+pub struct CachedManifests {
+    pub manifests: Vec<ManifestInfo>,
+    pub files: Vec<Vec<DataFileInfo>>,
+}
+
+impl CachedManifests {
+    fn is_complete(&self) -> bool {
+        self.manifests.iter().all(|m| m.load_error.is_none())
+    }
+}
+
+struct CacheEntry {
+    value: CachedManifests,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<i64, CacheEntry>,
+    clock: u64,
+}
+
+/// Least-recently-used cache of loaded manifests keyed by snapshot id.
+/// Clones share the same storage, so background tasks can fill it.
+#[derive(Clone)]
+pub struct ManifestCache {
+    state: Arc<Mutex<CacheState>>,
+    capacity: usize,
+}
+
+impl ManifestCache {
+    /// A capacity of 0 disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(CacheState::default())),
+            capacity,
+        }
+    }
+
+    /// The snapshot's manifests when all of them loaded. Entries with failed manifests are
+    /// kept for `update` but not served, so the next visit loads the snapshot again.
+    pub fn get(&self, snapshot_id: i64) -> Option<CachedManifests> {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+        let entry = state.entries.get_mut(&snapshot_id)?;
+        entry.last_used = clock;
+        entry.value.is_complete().then(|| entry.value.clone())
+    }
+
+    /// Replace the manifest at `index` of a cached snapshot, e.g. after retrying it.
+    pub fn update(
+        &self,
+        snapshot_id: i64,
+        index: usize,
+        manifest: ManifestInfo,
+        files: Vec<DataFileInfo>,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        let Some(entry) = state.entries.get_mut(&snapshot_id) else {
+            return;
+        };
+        if let (Some(slot), Some(files_slot)) = (
+            entry.value.manifests.get_mut(index),
+            entry.value.files.get_mut(index),
+        ) {
+            *slot = manifest;
+            *files_slot = files;
+        }
+    }
+
+    pub fn insert(&self, snapshot_id: i64, value: CachedManifests) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let last_used = state.clock;
+        state
+            .entries
+            .insert(snapshot_id, CacheEntry { value, last_used });
+
+        while state.entries.len() > self.capacity {
+            let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(id, _)| *id)
+            else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.state.lock().unwrap().entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached(n: usize) -> CachedManifests {
+        CachedManifests {
+            manifests: vec![],
+            files: vec![vec![]; n],
+        }
+    }
+
+    #[test]
+    fn get_returns_inserted_value() {
+        let cache = ManifestCache::new(4);
+        assert!(cache.get(1).is_none());
+        cache.insert(1, cached(3));
+        assert_eq!(cache.get(1).unwrap().files.len(), 3);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = ManifestCache::new(2);
+        cache.insert(1, cached(1));
+        cache.insert(2, cached(2));
+        cache.get(1);
+        cache.insert(3, cached(3));
+
+        assert!(cache.get(1).is_some());
+        assert!(cache.get(2).is_none());
+        assert!(cache.get(3).is_some());
+    }
+
+    #[test]
+    fn zero_capacity_disables_cache() {
+        let cache = ManifestCache::new(0);
+        cache.insert(1, cached(1));
+        assert!(cache.get(1).is_none());
+    }
+
+    fn manifest(load_error: Option<&str>) -> ManifestInfo {
+        ManifestInfo {
+            path: "/m.avro".into(),
+            content_type: "data".into(),
+            added_data_files_count: None,
+            added_rows_count: None,
+            existing_data_files_count: None,
+            existing_rows_count: None,
+            deleted_data_files_count: None,
+            deleted_rows_count: None,
+            sequence_number: 1,
+            partition_spec_id: 0,
+            load_error: load_error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn partial_entries_are_served_once_updated() {
+        let cache = ManifestCache::new(4);
+        cache.insert(
+            1,
+            CachedManifests {
+                manifests: vec![manifest(None), manifest(Some("timed out"))],
+                files: vec![vec![], vec![]],
+            },
+        );
+        assert!(cache.get(1).is_none());
+
+        cache.update(1, 1, manifest(None), vec![]);
+        assert!(cache.get(1).is_some());
+        // Snapshots that aren't cached are left alone.
+        cache.update(2, 0, manifest(None), vec![]);
+        assert!(cache.get(2).is_none());
+    }
+
+    #[test]
+    fn clones_share_entries() {
+        let cache = ManifestCache::new(4);
+        let shared = cache.clone();
+        shared.insert(7, cached(1));
+        assert!(cache.get(7).is_some());
+
+        cache.clear();
+        assert!(shared.get(7).is_none());
+    }
+}
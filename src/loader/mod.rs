@@ -2,23 +2,55 @@ pub mod arrow_convert;
 pub mod catalog_loader;
 pub mod direct_loader;
 pub mod file_io;
+pub mod manifest_cache;
+pub mod projection;
 pub mod scan;
+#[cfg(feature = "sql")]
+pub mod sql;
+#[cfg(test)]
+pub mod test_support;
+
+use std::collections::HashMap;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use futures::{StreamExt, TryStreamExt};
+use iceberg::spec::DataContentType;
 use iceberg::table::Table;
 
 use crate::model::table_info::TableMetadata;
 
+/// Rows and data files in a snapshot, summed from its manifests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TableCounts {
+    /// Rows in live data files before deletes are applied, so an upper bound when the
+    /// snapshot has delete files.
+    pub rows: usize,
+    /// Live data files; delete files aren't counted.
+    pub files: usize,
+    /// Live position and equality delete files.
+    pub delete_files: usize,
+}
+
 /// Abstraction over a loaded Iceberg table.
 /// The Table is Clone (wraps Arc), so it can be shared with background tasks.
 #[derive(Clone)]
 pub struct TableHandle {
     pub table: Table,
+    /// Prefixes rewritten in data and delete file paths before they are read.
+    pub location_overrides: Vec<file_io::LocationOverride>,
 }
 
 impl TableHandle {
     pub fn new(table: Table) -> Self {
-        Self { table }
+        Self {
+            table,
+            location_overrides: vec![],
+        }
+    }
+
+    pub fn with_location_overrides(mut self, overrides: Vec<file_io::LocationOverride>) -> Self {
+        self.location_overrides = overrides;
+        self
     }
 
     /// Extract metadata from the table into our display-friendly structs.
@@ -26,34 +58,113 @@ impl TableHandle {
         extract_metadata_from_table(&self.table)
     }
 
-    /// Count total rows by summing `record_count` from live data files in manifests.
-    pub async fn count_total_rows(&self, snapshot_id: Option<i64>) -> Result<usize> {
+    /// Arrow types the table's schemas convert to, by schema id and then field id.
+    pub fn arrow_types(&self) -> Result<HashMap<i32, HashMap<i32, String>>> {
+        self.table
+            .metadata()
+            .schemas_iter()
+            .map(|schema| {
+                let arrow = iceberg::arrow::schema_to_arrow_schema(schema).with_context(|| {
+                    format!("cannot convert schema {} to Arrow", schema.schema_id())
+                })?;
+                Ok((
+                    schema.schema_id(),
+                    arrow_convert::arrow_types_by_field_id(&arrow),
+                ))
+            })
+            .collect()
+    }
+
+    /// Count total rows by summing `record_count` from live data files in manifests,
+    /// loading up to `max_concurrency` manifests at once.
+    pub async fn count_total_rows(
+        &self,
+        snapshot_id: Option<i64>,
+        max_concurrency: usize,
+    ) -> Result<usize> {
+        Ok(self.count(snapshot_id, max_concurrency).await?.rows)
+    }
+
+    /// The snapshot that was current at `timestamp_ms`, from the table's snapshot log.
+    pub fn snapshot_as_of(&self, timestamp_ms: i64) -> Result<i64> {
+        self.table
+            .metadata()
+            .history()
+            .iter()
+            .filter(|entry| entry.timestamp_ms <= timestamp_ms)
+            .max_by_key(|entry| entry.timestamp_ms)
+            .map(|entry| entry.snapshot_id)
+            .with_context(|| {
+                format!(
+                    "no snapshot was current at {}",
+                    crate::util::format::format_timestamp_ms(timestamp_ms)
+                )
+            })
+    }
+
+    /// The snapshot a branch or tag points at.
+    pub fn ref_snapshot_id(&self, name: &str) -> Result<i64> {
+        resolve_ref(&extract_refs(self.table.metadata()), name)
+    }
+
+    /// Count rows and live data files in a snapshot (the current one when `None`), loading
+    /// up to `max_concurrency` manifests at once.
+    #[tracing::instrument(skip(self))]
+    pub async fn count(
+        &self,
+        snapshot_id: Option<i64>,
+        max_concurrency: usize,
+    ) -> Result<TableCounts> {
         let metadata = self.table.metadata();
         let snapshot = match snapshot_id {
-            Some(id) => metadata.snapshot_by_id(id),
-            None => metadata.current_snapshot(),
-        }
-        .context("no snapshot found")?;
+            Some(id) => metadata
+                .snapshot_by_id(id)
+                .with_context(|| format!("snapshot {} not found", id))?,
+            None => metadata
+                .current_snapshot()
+                .context("table has no snapshot")?,
+        };
 
         let file_io = self.table.file_io().clone();
         let manifest_list = snapshot
             .load_manifest_list(&file_io, metadata)
             .await
-            .context("failed to load manifest list")?;
-
-        let mut total = 0usize;
-        for mf in manifest_list.entries() {
-            let manifest = mf
-                .load_manifest(&file_io)
-                .await
-                .context("failed to load manifest")?;
-            for entry in manifest.entries() {
-                if entry.is_alive() {
-                    total += entry.data_file().record_count() as usize;
+            .with_context(|| {
+                format!("failed to load manifest list {}", snapshot.manifest_list())
+            })?;
+
+        // Addition commutes, so manifests can finish in any order.
+        futures::stream::iter(manifest_list.entries().to_vec())
+            .map(move |mf| {
+                let file_io = file_io.clone();
+                async move {
+                    tracing::debug!(path = %mf.manifest_path, "loading manifest");
+                    let manifest = mf
+                        .load_manifest(&file_io)
+                        .await
+                        .with_context(|| format!("failed to load manifest {}", mf.manifest_path))?;
+                    let mut counts = TableCounts::default();
+                    for entry in manifest.entries().iter().filter(|e| e.is_alive()) {
+                        let df = entry.data_file();
+                        if df.content_type() == DataContentType::Data {
+                            counts.rows += df.record_count() as usize;
+                            counts.files += 1;
+                        } else {
+                            counts.delete_files += 1;
+                        }
+                    }
+                    Ok::<_, anyhow::Error>(counts)
                 }
-            }
-        }
-        Ok(total)
+            })
+            .buffer_unordered(max_concurrency.max(1))
+            .try_fold(TableCounts::default(), |total, counts| async move {
+                Ok(TableCounts {
+                    rows: total.rows + counts.rows,
+                    files: total.files + counts.files,
+                    delete_files: total.delete_files + counts.delete_files,
+                })
+            })
+            .await
     }
 }
 
@@ -89,6 +200,8 @@ fn extract_metadata_from_table(table: &Table) -> Result<TableMetadata> {
         })
         .collect();
 
+    let refs = extract_refs(metadata);
+
     let partition_specs: Vec<PartitionSpecInfo> = metadata
         .partition_specs_iter()
         .map(|spec| {
@@ -128,6 +241,31 @@ fn extract_metadata_from_table(table: &Table) -> Result<TableMetadata> {
         })
         .collect();
 
+    let statistics: Vec<StatisticsFileInfo> = metadata
+        .statistics_iter()
+        .map(|file| StatisticsFileInfo {
+            snapshot_id: file.snapshot_id,
+            path: file.statistics_path.clone(),
+            blobs: file
+                .blob_metadata
+                .iter()
+                .map(|blob| StatisticsBlobInfo {
+                    blob_type: blob.r#type.clone(),
+                    field_ids: blob.fields.clone(),
+                    properties: blob.properties.clone(),
+                })
+                .collect(),
+        })
+        .collect();
+
+    let partition_statistics: Vec<PartitionStatisticsInfo> = metadata
+        .partition_statistics_iter()
+        .map(|file| PartitionStatisticsInfo {
+            snapshot_id: file.snapshot_id,
+            path: file.statistics_path.clone(),
+        })
+        .collect();
+
     let properties: std::collections::HashMap<String, String> = metadata
         .properties()
         .iter()
@@ -136,11 +274,17 @@ fn extract_metadata_from_table(table: &Table) -> Result<TableMetadata> {
 
     Ok(TableMetadata {
         location: metadata.location().to_string(),
+        metadata_location: table.metadata_location().map(String::from),
         current_schema,
         schemas,
         snapshots,
+        refs,
         partition_specs,
+        default_spec_id: metadata.default_partition_spec().spec_id(),
         sort_orders,
+        default_sort_order_id: metadata.default_sort_order_id(),
+        statistics,
+        partition_statistics,
         properties,
         current_snapshot_id: metadata.current_snapshot().map(|s| s.snapshot_id()),
         format_version: match metadata.format_version() {
@@ -150,9 +294,51 @@ fn extract_metadata_from_table(table: &Table) -> Result<TableMetadata> {
         },
         table_uuid: metadata.uuid().to_string(),
         last_updated_ms: metadata.last_updated_ms(),
+        last_column_id: metadata.last_column_id(),
+        last_partition_id: metadata.last_partition_id(),
     })
 }
 
+/// Branches and tags, read back from the serialized metadata, which lists them under `refs`.
+/// v1 metadata has no refs; its only branch is the current snapshot.
+fn extract_refs(metadata: &iceberg::spec::TableMetadata) -> Vec<crate::model::table_info::RefInfo> {
+    use crate::model::table_info::RefInfo;
+
+    let Ok(json) = serde_json::to_value(metadata) else {
+        return vec![];
+    };
+    let Some(refs) = json.get("refs").and_then(|r| r.as_object()) else {
+        return vec![];
+    };
+    let mut refs: Vec<RefInfo> = refs
+        .iter()
+        .filter_map(|(name, r)| {
+            Some(RefInfo {
+                name: name.clone(),
+                snapshot_id: r.get("snapshot-id")?.as_i64()?,
+            })
+        })
+        .collect();
+    refs.sort_by(|a, b| a.name.cmp(&b.name));
+    refs
+}
+
+/// The snapshot `name` points at, or an error listing the refs the table does have.
+fn resolve_ref(refs: &[crate::model::table_info::RefInfo], name: &str) -> Result<i64> {
+    if let Some(r) = refs.iter().find(|r| r.name == name) {
+        return Ok(r.snapshot_id);
+    }
+    let available: Vec<&str> = refs.iter().map(|r| r.name.as_str()).collect();
+    if available.is_empty() {
+        bail!("unknown ref `{}`: the table has no branches or tags", name);
+    }
+    bail!(
+        "unknown ref `{}`; available: {}",
+        name,
+        available.join(", ")
+    )
+}
+
 fn schema_to_info(schema: &iceberg::spec::Schema) -> crate::model::table_info::SchemaInfo {
     use crate::model::table_info::SchemaInfo;
 
@@ -184,6 +370,14 @@ fn nested_field_to_info(
                 field_type: l.element_field.field_type.to_string(),
                 required: l.element_field.required,
                 doc: l.element_field.doc.clone(),
+                initial_default: default_to_string(
+                    &l.element_field.initial_default,
+                    &l.element_field.field_type,
+                ),
+                write_default: default_to_string(
+                    &l.element_field.write_default,
+                    &l.element_field.field_type,
+                ),
                 children: vec![],
             }]
         }
@@ -195,6 +389,14 @@ fn nested_field_to_info(
                     field_type: m.key_field.field_type.to_string(),
                     required: m.key_field.required,
                     doc: m.key_field.doc.clone(),
+                    initial_default: default_to_string(
+                        &m.key_field.initial_default,
+                        &m.key_field.field_type,
+                    ),
+                    write_default: default_to_string(
+                        &m.key_field.write_default,
+                        &m.key_field.field_type,
+                    ),
                     children: vec![],
                 },
                 FieldInfo {
@@ -203,6 +405,14 @@ fn nested_field_to_info(
                     field_type: m.value_field.field_type.to_string(),
                     required: m.value_field.required,
                     doc: m.value_field.doc.clone(),
+                    initial_default: default_to_string(
+                        &m.value_field.initial_default,
+                        &m.value_field.field_type,
+                    ),
+                    write_default: default_to_string(
+                        &m.value_field.write_default,
+                        &m.value_field.field_type,
+                    ),
                     children: vec![],
                 },
             ]
@@ -216,10 +426,24 @@ fn nested_field_to_info(
         field_type: field.field_type.to_string(),
         required: field.required,
         doc: field.doc.clone(),
+        initial_default: default_to_string(&field.initial_default, &field.field_type),
+        write_default: default_to_string(&field.write_default, &field.field_type),
         children,
     }
 }
 
+/// Render a field default as it appears in the table metadata JSON, e.g. `"n/a"` or `0`.
+fn default_to_string(
+    value: &Option<iceberg::spec::Literal>,
+    field_type: &iceberg::spec::Type,
+) -> Option<String> {
+    let literal = value.as_ref()?;
+    Some(match literal.clone().try_into_json(field_type) {
+        Ok(json) => json.to_string(),
+        Err(_) => format!("{:?}", literal),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,26 +454,47 @@ mod tests {
         assert_clone::<TableHandle>();
     }
 
+    #[test]
+    fn default_to_string_renders_json() {
+        use iceberg::spec::{Literal, PrimitiveLiteral, PrimitiveType, Type};
+
+        let string_type = Type::Primitive(PrimitiveType::String);
+        let value = Some(Literal::Primitive(PrimitiveLiteral::String("n/a".into())));
+        assert_eq!(
+            default_to_string(&value, &string_type).as_deref(),
+            Some("\"n/a\"")
+        );
+
+        let int_type = Type::Primitive(PrimitiveType::Int);
+        let value = Some(Literal::Primitive(PrimitiveLiteral::Int(0)));
+        assert_eq!(default_to_string(&value, &int_type).as_deref(), Some("0"));
+        assert_eq!(default_to_string(&None, &int_type), None);
+    }
+
+    #[test]
+    fn resolve_ref_finds_branches_and_lists_the_rest() {
+        use crate::model::table_info::RefInfo;
+
+        let refs = vec![
+            RefInfo {
+                name: "main".into(),
+                snapshot_id: 2,
+            },
+            RefInfo {
+                name: "staging".into(),
+                snapshot_id: 3,
+            },
+        ];
+        assert_eq!(resolve_ref(&refs, "staging").unwrap(), 3);
+        let err = resolve_ref(&refs, "audit").unwrap_err().to_string();
+        assert_eq!(err, "unknown ref `audit`; available: main, staging");
+        assert!(resolve_ref(&[], "main").is_err());
+    }
+
     /// Integration test: loads the sample table and runs a full scan.
     #[tokio::test]
     async fn load_and_scan_sample_table() {
-        // Only run if sample table exists
-        let table_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("examples")
-            .join("sample_table");
-        if !table_path.exists() {
-            eprintln!("Skipping integration test: sample table not found");
-            return;
-        }
-
-        let path_str = table_path.to_string_lossy().to_string();
-        eprintln!("Loading table from: {}", path_str);
-
-        let config = file_io::StorageConfig::default();
-        let handle = direct_loader::load_direct(&path_str, &config)
-            .await
-            .unwrap();
-        eprintln!("Table loaded successfully");
+        let handle = test_support::sample_table().await;
 
         let metadata = handle.extract_metadata().unwrap();
         eprintln!(
@@ -266,5 +511,61 @@ mod tests {
         let total_rows = arrow_convert::total_row_count(&result.batches);
         eprintln!("Total rows: {}", total_rows);
         assert_eq!(total_rows, 200, "Expected 200 rows in sample table");
+
+        let sequential = handle.count_total_rows(None, 1).await.unwrap();
+        let concurrent = handle.count_total_rows(None, 8).await.unwrap();
+        assert_eq!(sequential, concurrent);
+        assert!(sequential >= total_rows);
+    }
+
+    #[tokio::test]
+    async fn count_and_as_of_follow_snapshots() {
+        let handle = test_support::sample_table().await;
+        assert_eq!(
+            handle.count(Some(1), 4).await.unwrap(),
+            TableCounts {
+                rows: 50,
+                files: 1,
+                delete_files: 0
+            }
+        );
+        assert_eq!(
+            handle.count(None, 4).await.unwrap(),
+            TableCounts {
+                rows: 200,
+                files: 3,
+                delete_files: 0
+            }
+        );
+
+        let history = handle.table.metadata().history().to_vec();
+        assert_eq!(handle.snapshot_as_of(history[1].timestamp_ms).unwrap(), 2);
+        assert_eq!(
+            handle.snapshot_as_of(history[1].timestamp_ms + 1).unwrap(),
+            2
+        );
+        assert!(handle.snapshot_as_of(history[0].timestamp_ms - 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn zero_limit_scan_reads_nothing() {
+        let handle = test_support::sample_table().await;
+
+        // An unknown snapshot fails as soon as the scan is built, so success means the
+        // scan was never built.
+        let request = scan::ScanRequest {
+            limit: Some(0),
+            snapshot_id: Some(-1),
+            ..Default::default()
+        };
+        let result = scan::execute_scan(&handle, &request).await.unwrap();
+        assert!(result.batches.is_empty());
+        assert!(result.has_more);
+
+        let request = scan::ScanRequest {
+            limit: Some(1),
+            ..request
+        };
+        assert!(scan::execute_scan(&handle, &request).await.is_err());
     }
 }
@@ -0,0 +1,502 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Context, Result};
+use arrow_array::{Array, ArrayRef, Float64Array, Int64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+
+/// A list of output columns computed from the scanned batches.
+///
+/// Supports bare column references, `expr AS alias`, numeric literals and
+/// `+ - * /` arithmetic with parentheses over numeric columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    items: Vec<ProjectionItem>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ProjectionItem {
+    expr: Expr,
+    alias: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Column(String),
+    Int(i64),
+    Float(f64),
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, BinOp, Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Op(char),
+    LParen,
+    RParen,
+}
+
+/// Values produced while evaluating an arithmetic expression.
+enum Values {
+    Int(Vec<Option<i64>>),
+    Float(Vec<Option<f64>>),
+}
+
+/// Parse a comma-separated projection such as `salary, salary * 12 AS annual`.
+pub fn parse_projection(input: &str) -> Result<Projection> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("empty projection");
+    }
+
+    let items = split_items(input)
+        .into_iter()
+        .map(parse_item)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Projection { items })
+}
+
+impl Projection {
+    /// Column names the projection reads, in first-use order.
+    pub fn source_columns(&self) -> Vec<String> {
+        let mut columns = Vec::new();
+        for item in &self.items {
+            item.expr.collect_columns(&mut columns);
+        }
+        columns
+    }
+
+    /// Evaluate the projection over each batch.
+    pub fn apply(&self, batches: &[RecordBatch]) -> Result<Vec<RecordBatch>> {
+        batches.iter().map(|b| self.apply_batch(b)).collect()
+    }
+
+    fn apply_batch(&self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let mut fields = Vec::with_capacity(self.items.len());
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(self.items.len());
+
+        for item in &self.items {
+            let array = match &item.expr {
+                Expr::Column(name) => column_by_name(batch, name)?.clone(),
+                expr => match expr.evaluate(batch)? {
+                    Values::Int(v) => Arc::new(Int64Array::from(v)) as ArrayRef,
+                    Values::Float(v) => Arc::new(Float64Array::from(v)) as ArrayRef,
+                },
+            };
+            fields.push(Field::new(&item.alias, array.data_type().clone(), true));
+            columns.push(array);
+        }
+
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+            .context("failed to build projected batch")
+    }
+}
+
+impl Expr {
+    fn collect_columns(&self, out: &mut Vec<String>) {
+        match self {
+            Expr::Column(name) => {
+                if !out.contains(name) {
+                    out.push(name.clone());
+                }
+            }
+            Expr::Int(_) | Expr::Float(_) => {}
+            Expr::Neg(inner) => inner.collect_columns(out),
+            Expr::Binary(lhs, _, rhs) => {
+                lhs.collect_columns(out);
+                rhs.collect_columns(out);
+            }
+        }
+    }
+
+    fn evaluate(&self, batch: &RecordBatch) -> Result<Values> {
+        let rows = batch.num_rows();
+        match self {
+            Expr::Int(v) => Ok(Values::Int(vec![Some(*v); rows])),
+            Expr::Float(v) => Ok(Values::Float(vec![Some(*v); rows])),
+            Expr::Column(name) => numeric_values(column_by_name(batch, name)?, name),
+            Expr::Neg(inner) => Ok(match inner.evaluate(batch)? {
+                Values::Int(v) => Values::Int(
+                    v.into_iter()
+                        .map(|x| x.and_then(i64::checked_neg))
+                        .collect(),
+                ),
+                Values::Float(v) => Values::Float(v.into_iter().map(|x| x.map(|f| -f)).collect()),
+            }),
+            Expr::Binary(lhs, op, rhs) => {
+                let lhs = lhs.evaluate(batch)?;
+                let rhs = rhs.evaluate(batch)?;
+                Ok(apply_binary(lhs, *op, rhs))
+            }
+        }
+    }
+}
+
+fn column_by_name<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a ArrayRef> {
+    batch
+        .column_by_name(name)
+        .ok_or_else(|| anyhow!("unknown column in projection: {}", name))
+}
+
+fn numeric_values(array: &ArrayRef, name: &str) -> Result<Values> {
+    let data_type = array.data_type();
+    if !data_type.is_numeric() {
+        bail!(
+            "column '{}' has type {} and cannot be used in arithmetic",
+            name,
+            data_type
+        );
+    }
+
+    if data_type.is_integer() {
+        let cast = arrow_cast::cast(array, &DataType::Int64)
+            .with_context(|| format!("failed to cast column '{}' to Int64", name))?;
+        let ints = cast
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .context("expected Int64 array after cast")?;
+        Ok(Values::Int(ints.iter().collect()))
+    } else {
+        let cast = arrow_cast::cast(array, &DataType::Float64)
+            .with_context(|| format!("failed to cast column '{}' to Float64", name))?;
+        let floats = cast
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .context("expected Float64 array after cast")?;
+        Ok(Values::Float(floats.iter().collect()))
+    }
+}
+
+/// Integer arithmetic stays integral (overflow and division by zero yield null);
+/// `/` and any float operand promote the result to Float64.
+fn apply_binary(lhs: Values, op: BinOp, rhs: Values) -> Values {
+    match (lhs, rhs) {
+        (Values::Int(l), Values::Int(r)) if op != BinOp::Div => Values::Int(
+            l.into_iter()
+                .zip(r)
+                .map(|(a, b)| match (a, b, op) {
+                    (Some(a), Some(b), BinOp::Add) => a.checked_add(b),
+                    (Some(a), Some(b), BinOp::Sub) => a.checked_sub(b),
+                    (Some(a), Some(b), BinOp::Mul) => a.checked_mul(b),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        (l, r) => Values::Float(
+            to_floats(l)
+                .into_iter()
+                .zip(to_floats(r))
+                .map(|(a, b)| match (a, b, op) {
+                    (Some(a), Some(b), BinOp::Add) => Some(a + b),
+                    (Some(a), Some(b), BinOp::Sub) => Some(a - b),
+                    (Some(a), Some(b), BinOp::Mul) => Some(a * b),
+                    (Some(_), Some(0.0), BinOp::Div) => None,
+                    (Some(a), Some(b), BinOp::Div) => Some(a / b),
+                    _ => None,
+                })
+                .collect(),
+        ),
+    }
+}
+
+fn to_floats(values: Values) -> Vec<Option<f64>> {
+    match values {
+        Values::Int(v) => v.into_iter().map(|x| x.map(|i| i as f64)).collect(),
+        Values::Float(v) => v,
+    }
+}
+
+/// Split on top-level commas (commas inside parentheses are kept).
+fn split_items(input: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, ch) in input.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(input[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(input[start..].trim());
+    items
+}
+
+fn parse_item(item: &str) -> Result<ProjectionItem> {
+    if item.is_empty() {
+        bail!("empty projection item");
+    }
+
+    let tokens = tokenize(item)?;
+    let (expr_tokens, alias) = split_alias(&tokens);
+
+    let mut parser = ExprParser {
+        tokens: expr_tokens,
+        pos: 0,
+    };
+    let expr = parser
+        .parse_expr()
+        .with_context(|| format!("unsupported projection: {}", item))?;
+    if parser.pos != parser.tokens.len() {
+        bail!("unsupported projection: {}", item);
+    }
+
+    let alias = match (alias, &expr) {
+        (Some(alias), _) => alias,
+        (None, Expr::Column(name)) => name.clone(),
+        (None, _) => item.to_string(),
+    };
+
+    Ok(ProjectionItem { expr, alias })
+}
+
+/// Separate a trailing `AS alias` from the expression tokens. Only the last two tokens can
+/// form the alias, so a column named `as` is still read as a column.
+fn split_alias(tokens: &[Token]) -> (&[Token], Option<String>) {
+    match tokens {
+        [expr @ .., Token::Ident(keyword), Token::Ident(alias)]
+            if !expr.is_empty() && keyword.eq_ignore_ascii_case("AS") =>
+        {
+            (expr, Some(alias.clone()))
+        }
+        _ => (tokens, None),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch.is_whitespace() {
+            i += 1;
+        } else if ch.is_ascii_alphabetic() || ch == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if ch.is_ascii_digit() || ch == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(Token::Number(chars[start..i].iter().collect()));
+        } else if matches!(ch, '+' | '-' | '*' | '/') {
+            tokens.push(Token::Op(ch));
+            i += 1;
+        } else if ch == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if ch == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else {
+            bail!("unsupported character '{}' in projection: {}", ch, input);
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser: expr := term (('+'|'-') term)*, term := factor (('*'|'/') factor)*.
+struct ExprParser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+}
+
+impl ExprParser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.parse_term()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek() {
+            let op = if *op == '+' { BinOp::Add } else { BinOp::Sub };
+            self.pos += 1;
+            let rhs = self.parse_term()?;
+            lhs = Expr::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_term(&mut self) -> Result<Expr> {
+        let mut lhs = self.parse_factor()?;
+        while let Some(Token::Op(op @ ('*' | '/'))) = self.peek() {
+            let op = if *op == '*' { BinOp::Mul } else { BinOp::Div };
+            self.pos += 1;
+            let rhs = self.parse_factor()?;
+            lhs = Expr::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_factor(&mut self) -> Result<Expr> {
+        match self.next().cloned() {
+            Some(Token::Ident(name)) => Ok(Expr::Column(name)),
+            Some(Token::Number(text)) => parse_number(&text),
+            Some(Token::Op('-')) => Ok(Expr::Neg(Box::new(self.parse_factor()?))),
+            Some(Token::LParen) => {
+                let inner = self.parse_expr()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => bail!("missing closing parenthesis"),
+                }
+            }
+            Some(token) => bail!("unexpected token {:?}", token),
+            None => bail!("unexpected end of expression"),
+        }
+    }
+}
+
+fn parse_number(text: &str) -> Result<Expr> {
+    if let Ok(i) = text.parse::<i64>() {
+        return Ok(Expr::Int(i));
+    }
+    text.parse::<f64>()
+        .map(Expr::Float)
+        .map_err(|_| anyhow!("invalid number: {}", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Float64Array, Int32Array, StringArray};
+
+    fn make_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, false),
+            Field::new("salary", DataType::Int32, true),
+            Field::new("bonus", DataType::Float64, true),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["Alice", "Bob"])),
+                Arc::new(Int32Array::from(vec![Some(100), None])),
+                Arc::new(Float64Array::from(vec![Some(0.5), Some(1.5)])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_plain_columns() {
+        let p = parse_projection("name, salary").unwrap();
+        assert_eq!(p.source_columns(), vec!["name", "salary"]);
+    }
+
+    #[test]
+    fn parse_alias_and_precedence() {
+        let p = parse_projection("salary + bonus * 2 AS total").unwrap();
+        assert_eq!(p.items[0].alias, "total");
+        assert_eq!(
+            p.items[0].expr,
+            Expr::Binary(
+                Box::new(Expr::Column("salary".into())),
+                BinOp::Add,
+                Box::new(Expr::Binary(
+                    Box::new(Expr::Column("bonus".into())),
+                    BinOp::Mul,
+                    Box::new(Expr::Int(2)),
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn parse_default_alias_is_expression_text() {
+        let p = parse_projection("salary*12").unwrap();
+        assert_eq!(p.items[0].alias, "salary*12");
+    }
+
+    #[test]
+    fn parse_columns_named_as() {
+        let p = parse_projection("as, as * 2 AS twice, as AS a").unwrap();
+        assert_eq!(p.items[0].expr, Expr::Column("as".into()));
+        assert_eq!(p.items[0].alias, "as");
+        assert_eq!(p.items[1].alias, "twice");
+        assert_eq!(p.items[2].expr, Expr::Column("as".into()));
+        assert_eq!(p.items[2].alias, "a");
+    }
+
+    #[test]
+    fn source_columns_deduplicated() {
+        let p = parse_projection("salary, salary * 12 AS annual").unwrap();
+        assert_eq!(p.source_columns(), vec!["salary"]);
+    }
+
+    #[test]
+    fn parse_rejects_unsupported_syntax() {
+        assert!(parse_projection("").is_err());
+        assert!(parse_projection("upper(name)").is_err());
+        assert!(parse_projection("salary %2").is_err());
+        assert!(parse_projection("salary AS").is_err());
+        assert!(parse_projection("(salary + 1").is_err());
+        assert!(parse_projection("salary,").is_err());
+    }
+
+    #[test]
+    fn apply_integer_arithmetic_keeps_int() {
+        let p = parse_projection("name, salary * 12 AS annual").unwrap();
+        let out = p.apply(&[make_batch()]).unwrap();
+        let batch = &out[0];
+        assert_eq!(batch.schema().field(1).name(), "annual");
+        let annual = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(annual.value(0), 1200);
+        assert!(annual.is_null(1));
+    }
+
+    #[test]
+    fn apply_mixed_arithmetic_promotes_to_float() {
+        let p = parse_projection("salary / 2 + bonus AS x").unwrap();
+        let out = p.apply(&[make_batch()]).unwrap();
+        let x = out[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(x.value(0), 50.5);
+    }
+
+    #[test]
+    fn apply_unknown_column_errors() {
+        let p = parse_projection("missing + 1").unwrap();
+        let err = p.apply(&[make_batch()]).unwrap_err();
+        assert!(err.to_string().contains("unknown column"));
+    }
+
+    #[test]
+    fn apply_arithmetic_on_string_errors() {
+        let p = parse_projection("name * 2").unwrap();
+        let err = p.apply(&[make_batch()]).unwrap_err();
+        assert!(err.to_string().contains("cannot be used in arithmetic"));
+    }
+}
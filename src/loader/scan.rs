@@ -1,46 +1,317 @@
-use anyhow::{Context, Result};
-use arrow_array::RecordBatch;
+use std::ops::Range;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, FieldRef, Schema};
 use futures::TryStreamExt;
+use iceberg::arrow::ArrowReaderBuilder;
 use iceberg::expr::Predicate;
+use iceberg::scan::{ArrowRecordBatchStream, FileScanTask, TableScan};
+use iceberg::spec::Type;
 
+use super::arrow_convert::{flatten_nested_columns, match_path_segment};
+use super::file_io::{relocate, LocationOverride};
+use super::projection::Projection;
 use super::TableHandle;
+use crate::model::filter::{type_decimal_literals, MetadataCondition};
+
+/// Virtual column holding the data file each row was read from.
+pub const FILE_COLUMN: &str = "_file";
+/// Virtual column holding each row's position within its data file.
+pub const POS_COLUMN: &str = "_pos";
 
 /// Configuration for a scan request.
 #[derive(Debug, Clone, Default)]
 pub struct ScanRequest {
     pub columns: Option<Vec<String>>,
+    /// Dotted paths into struct columns, e.g. `metrics.cpu.p99`, added to the rows as flat
+    /// columns named by the path. Paths in `columns` are flattened too.
+    pub nested_columns: Vec<String>,
     pub filter: Option<Predicate>,
     pub snapshot_id: Option<i64>,
     pub limit: Option<usize>,
+    /// Post-scan projection. When set, the scan reads the projection's source
+    /// columns instead of `columns`.
+    pub projection: Option<Projection>,
+    /// SQL query run over the scanned rows (registered as table `t`). The scan
+    /// itself is unlimited; `limit` applies to the query result instead.
+    pub sql: Option<String>,
+    /// Append the `_file` and `_pos` virtual columns to every row.
+    pub metadata_columns: bool,
+    /// Conditions on `_file` and `_pos`; these also add the virtual columns.
+    pub metadata_filter: Vec<MetadataCondition>,
+    /// Rows to skip before collecting. Files wholly inside the skipped range are not read
+    /// when their row count is exact.
+    pub offset: usize,
 }
 
 pub struct ScanResult {
     pub batches: Vec<RecordBatch>,
+    /// Position of the first returned row among all matching rows.
+    pub offset: usize,
     pub has_more: bool,
+    /// Requested columns missing from the scanned snapshot's schema.
+    pub dropped_columns: Vec<String>,
+    /// Set when none of the requested columns exist in the snapshot, so all of its columns
+    /// were read instead.
+    pub read_all_columns: bool,
+    /// Why reading stopped early when it failed after some rows had been read; `batches`
+    /// then holds the rows read before the failure.
+    pub error: Option<String>,
+}
+
+/// How a filter is applied: which files survive pruning and what is evaluated per row.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ScanPlan {
+    /// Snapshot the plan was made for; `None` when the table has no snapshot.
+    pub snapshot_id: Option<i64>,
+    pub filter: Option<String>,
+    /// Live data files in the snapshot before any pruning.
+    pub total_files: usize,
+    pub tasks: Vec<PlannedTask>,
+}
+
+/// A file that survived manifest and statistics pruning.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PlannedTask {
+    pub file_path: String,
+    pub record_count: Option<u64>,
+    /// Predicate still evaluated against each row while reading the file.
+    pub residual: Option<String>,
+    pub delete_files: usize,
+}
+
+impl ScanPlan {
+    pub fn pruned_files(&self) -> usize {
+        self.total_files.saturating_sub(self.tasks.len())
+    }
 }
 
 /// Execute a scan against an Iceberg table with early termination when limit is reached.
+/// A zero limit returns no rows without planning or reading anything.
+#[tracing::instrument(skip_all, fields(snapshot = ?request.snapshot_id, limit = ?request.limit))]
 pub async fn execute_scan(handle: &TableHandle, request: &ScanRequest) -> Result<ScanResult> {
+    if request.limit == Some(0) {
+        return Ok(ScanResult {
+            batches: vec![],
+            offset: request.offset,
+            has_more: true,
+            dropped_columns: vec![],
+            read_all_columns: false,
+            error: None,
+        });
+    }
+
     let mut builder = handle.table.scan();
+    let mut dropped_columns = Vec::new();
+    let mut read_all_columns = false;
+    let schema = scan_schema(handle, request.snapshot_id);
 
-    if let Some(ref cols) = request.columns {
-        builder = builder.select(cols.iter().map(|s| s.as_str()));
+    // Nested fields can't be selected directly, so their top-level columns are read instead.
+    // Explicitly requested paths must resolve; displayed columns missing from an older
+    // snapshot's schema are dropped below like any other column.
+    let mut nested: Vec<(String, String)> = Vec::new();
+    let requested = request.nested_columns.iter().map(|p| (p, true));
+    let displayed = request.columns.iter().flatten().map(|p| (p, false));
+    for (path, required) in requested.chain(displayed) {
+        if nested.iter().any(|(p, _)| p == path) {
+            continue;
+        }
+        let root = match nested_column_root(schema, path) {
+            Err(e) if required => return Err(e),
+            result => result.ok().flatten(),
+        };
+        if let Some(root) = root {
+            nested.push((path.clone(), root));
+        }
+    }
+
+    if let Some(ref projection) = request.projection {
+        let mut columns = projection.source_columns();
+        // Literals alone still need one column read to know how many rows there are.
+        if columns.is_empty() {
+            columns.extend(schema.as_struct().fields().first().map(|f| f.name.clone()));
+        }
+        builder = builder.select(columns);
+    } else if let (Some(cols), None) = (&request.columns, &request.sql) {
+        let table_cols: Vec<String> = cols
+            .iter()
+            .filter(|c| !is_metadata_column(c))
+            .cloned()
+            .collect();
+        let (kept, dropped) =
+            split_columns(&table_cols, |name| schema.field_by_name(name).is_some());
+        dropped_columns = dropped;
+        let mut selected: Vec<String> = Vec::new();
+        for name in kept {
+            let column = match nested.iter().find(|(path, _)| *path == name) {
+                Some((_, root)) => root.clone(),
+                None => name,
+            };
+            if !selected.contains(&column) {
+                selected.push(column);
+            }
+        }
+        // Selecting nothing would read zero-column batches.
+        if selected.is_empty() && !dropped_columns.is_empty() {
+            tracing::warn!(dropped = ?dropped_columns, "no requested column in snapshot schema");
+            read_all_columns = true;
+        } else {
+            builder = builder.select(selected);
+        }
     }
 
     if let Some(ref filter) = request.filter {
-        builder = builder.with_filter(filter.clone());
+        builder = builder.with_filter(type_decimal_literals(filter, schema)?);
     }
 
     if let Some(snapshot_id) = request.snapshot_id {
         builder = builder.snapshot_id(snapshot_id);
     }
 
-    let scan = builder.build().context("failed to build table scan")?;
+    let scan = builder.build().with_context(|| {
+        format!(
+            "failed to build scan of {}",
+            snapshot_label(request.snapshot_id)
+        )
+    })?;
 
-    let stream = scan.to_arrow().await.context("failed to execute scan")?;
+    #[cfg(not(feature = "sql"))]
+    if request.sql.is_some() {
+        anyhow::bail!("SQL queries require icepeek to be built with the `sql` feature");
+    }
+    #[cfg(feature = "sql")]
+    if let Some(ref query) = request.sql {
+        if request.offset > 0 {
+            anyhow::bail!("row offsets cannot be combined with a SQL query");
+        }
+        return execute_sql_scan(handle, &scan, request, query).await;
+    }
 
     let mut batches = Vec::new();
-    let mut collected = 0;
+    let collected =
+        if request.metadata_columns || !request.metadata_filter.is_empty() || request.offset > 0 {
+            collect_per_file(handle, &scan, request, &mut batches).await
+        } else {
+            let stream = read_scan(handle, &scan).await?;
+            collect_until_limit(stream, request.limit, &mut batches, Ok)
+                .await
+                .with_context(|| format!("failed to read {}", snapshot_label(request.snapshot_id)))
+        };
+    let (has_more, error) = keep_partial(collected.map_err(explain_missing_file), &batches)?;
+
+    if let Some(limit) = request.limit {
+        batches = limit_batches(batches, limit);
+    }
+
+    if !nested.is_empty() {
+        let paths: Vec<String> = nested.into_iter().map(|(path, _)| path).collect();
+        batches = flatten_nested_columns(batches, &paths)?;
+    }
+
+    if let Some(ref projection) = request.projection {
+        batches = projection.apply(&batches)?;
+    }
+
+    tracing::info!(
+        rows = batches.iter().map(|b| b.num_rows()).sum::<usize>(),
+        has_more,
+        "scan finished"
+    );
+    Ok(ScanResult {
+        batches,
+        offset: request.offset,
+        has_more,
+        dropped_columns,
+        read_all_columns,
+        error,
+    })
+}
+
+/// Stream the scan's rows, reading each file from where `--location-override` says it is now.
+async fn read_scan(handle: &TableHandle, scan: &TableScan) -> Result<ArrowRecordBatchStream> {
+    if handle.location_overrides.is_empty() {
+        return scan.to_arrow().await.context("failed to execute scan");
+    }
+    let overrides = handle.location_overrides.clone();
+    let tasks = scan
+        .plan_files()
+        .await
+        .context("failed to plan scan")?
+        .map_ok(move |task| relocate_task(task, &overrides));
+    ArrowReaderBuilder::new(handle.table.file_io().clone())
+        .build()
+        .read(Box::pin(tasks))
+        .context("failed to execute scan")
+}
+
+/// `task` with its data and delete file paths rewritten by `overrides`.
+fn relocate_task(mut task: FileScanTask, overrides: &[LocationOverride]) -> FileScanTask {
+    task.data_file_path = relocate(&task.data_file_path, overrides);
+    for delete in &mut task.deletes {
+        delete.file_path = relocate(&delete.file_path, overrides);
+    }
+    task
+}
+
+/// Name the missing file when a read failed because a file isn't where the metadata says,
+/// typically after a storage migration, and point at `--location-override`.
+fn explain_missing_file(e: anyhow::Error) -> anyhow::Error {
+    let Some(missing) = e
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<opendal::Error>())
+        .filter(|cause| cause.kind() == opendal::ErrorKind::NotFound)
+    else {
+        return e;
+    };
+    let message = missing.to_string();
+    let file = missing_path(&message).unwrap_or("a data file");
+    e.context(format!(
+        "{} was not found; if the table's files were moved, pass --location-override OLD=NEW",
+        file
+    ))
+}
+
+/// The path opendal records in an error's context, e.g. `path: db/t/data/a.parquet`.
+fn missing_path(message: &str) -> Option<&str> {
+    let start = message.find("path: ")? + "path: ".len();
+    message[start..]
+        .split([',', ' ', '}'])
+        .next()
+        .filter(|path| !path.is_empty())
+}
+
+/// Turn a failure after some rows were read into a partial result, so one unreadable data
+/// file doesn't hide the rows before it. Partial results report no more rows to load, since
+/// asking for more would fail at the same place. A failure before any row is an error.
+fn keep_partial(
+    collected: Result<bool>,
+    batches: &[RecordBatch],
+) -> Result<(bool, Option<String>)> {
+    match collected {
+        Ok(has_more) => Ok((has_more, None)),
+        Err(e) if !batches.is_empty() => {
+            tracing::warn!(
+                rows = batches.iter().map(|b| b.num_rows()).sum::<usize>(),
+                "scan failed partway: {:#}",
+                e
+            );
+            Ok((false, Some(format!("{:#}", e))))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Append batches from `stream` (after `map`) until `limit` rows are collected in total.
+/// Returns whether the limit was reached.
+async fn collect_until_limit(
+    stream: ArrowRecordBatchStream,
+    limit: Option<usize>,
+    batches: &mut Vec<RecordBatch>,
+    mut map: impl FnMut(RecordBatch) -> Result<RecordBatch>,
+) -> Result<bool> {
+    let mut collected: usize = batches.iter().map(|b| b.num_rows()).sum();
 
     futures::pin_mut!(stream);
     while let Some(batch) = stream
@@ -48,20 +319,370 @@ pub async fn execute_scan(handle: &TableHandle, request: &ScanRequest) -> Result
         .await
         .context("failed to collect scan results")?
     {
+        let batch = map(batch)?;
+        if batch.num_rows() == 0 {
+            continue;
+        }
         collected += batch.num_rows();
         batches.push(batch);
-        if request.limit.is_some_and(|lim| collected >= lim) {
-            break;
+        if limit.is_some_and(|lim| collected >= lim) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Read planned files one at a time, so each row can be tagged with its source file and
+/// position and leading files can be skipped to reach `request.offset`.
+async fn collect_per_file(
+    handle: &TableHandle,
+    scan: &TableScan,
+    request: &ScanRequest,
+    batches: &mut Vec<RecordBatch>,
+) -> Result<bool> {
+    let conditions = &request.metadata_filter;
+    let add_metadata_columns = request.metadata_columns || !conditions.is_empty();
+    let mut skip = request.offset;
+
+    let tasks: Vec<FileScanTask> = scan
+        .plan_files()
+        .await
+        .context("failed to plan scan")?
+        .try_collect()
+        .await
+        .context("failed to collect scan plan")?;
+
+    let pos_range = conditions
+        .iter()
+        .filter_map(|c| match c {
+            MetadataCondition::Pos(range) => Some(range.clone()),
+            MetadataCondition::File { .. } => None,
+        })
+        .reduce(|a, b| a.start.max(b.start)..a.end.min(b.end));
+
+    for task in tasks {
+        let file_path = task.data_file_path.clone();
+        if !conditions.iter().all(|c| c.matches_file(&file_path)) {
+            continue;
+        }
+        // Row filters and deletes drop rows inside the file, so positions can only be
+        // derived by counting when every row is read.
+        let exact = task.predicate.is_none() && task.deletes.is_empty();
+        let mut next_pos = exact.then_some(0);
+        if pos_range.is_some() && next_pos.is_none() {
+            anyhow::bail!(
+                "cannot filter on _pos in {}: it has deletes or a row filter",
+                file_path
+            );
+        }
+        if exact && pos_range.is_none() {
+            if let Some(rows) = task.record_count.map(|n| n as usize).filter(|n| *n <= skip) {
+                skip -= rows;
+                continue;
+            }
+        }
+
+        let task = relocate_task(task, &handle.location_overrides);
+        let read_path = task.data_file_path.clone();
+        tracing::debug!(file = %read_path, "reading data file");
+        let reader = ArrowReaderBuilder::new(handle.table.file_io().clone()).build();
+        let stream = reader
+            .read(Box::pin(futures::stream::iter([Ok(task)])))
+            .with_context(|| format!("failed to read {}", read_path))?;
+
+        let limit_reached = collect_until_limit(stream, request.limit, batches, |batch| {
+            let batch = if add_metadata_columns {
+                let start = next_pos;
+                next_pos = next_pos.map(|p| p + batch.num_rows() as i64);
+                let batch = append_metadata_columns(&batch, &file_path, start)?;
+                match (&pos_range, start) {
+                    (Some(range), Some(start)) => slice_positions(&batch, start, range),
+                    _ => batch,
+                }
+            } else {
+                batch
+            };
+            Ok(skip_rows(batch, &mut skip))
+        })
+        .await
+        .with_context(|| format!("failed to read {}", read_path))?;
+        if limit_reached {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Rows in a snapshot once deletes are applied. Files without deletes are counted from their
+/// manifest entries; only the files deletes apply to are read.
+pub async fn count_live_rows(handle: &TableHandle, snapshot_id: Option<i64>) -> Result<usize> {
+    let mut builder = handle.table.scan().select_empty();
+    if let Some(snapshot_id) = snapshot_id {
+        builder = builder.snapshot_id(snapshot_id);
+    }
+    let scan = builder
+        .build()
+        .with_context(|| format!("failed to build scan of {}", snapshot_label(snapshot_id)))?;
+    let tasks: Vec<FileScanTask> = scan
+        .plan_files()
+        .await
+        .context("failed to plan scan")?
+        .try_collect()
+        .await
+        .context("failed to collect scan plan")?;
+
+    let mut rows = 0;
+    for task in tasks {
+        rows += match task.record_count {
+            Some(n) if task.deletes.is_empty() => n as usize,
+            _ => read_task_rows(handle, task)
+                .await
+                .map_err(explain_missing_file)?,
+        };
+    }
+    Ok(rows)
+}
+
+/// Read one file scan task to the end and count the rows that survive its deletes.
+async fn read_task_rows(handle: &TableHandle, task: FileScanTask) -> Result<usize> {
+    let task = relocate_task(task, &handle.location_overrides);
+    let read_path = task.data_file_path.clone();
+    let reader = ArrowReaderBuilder::new(handle.table.file_io().clone()).build();
+    let stream = reader
+        .read(Box::pin(futures::stream::iter([Ok(task)])))
+        .with_context(|| format!("failed to read {}", read_path))?;
+    stream
+        .try_fold(0, |rows, batch| async move { Ok(rows + batch.num_rows()) })
+        .await
+        .with_context(|| format!("failed to read {}", read_path))
+}
+
+/// Drop up to `skip` leading rows of a batch, counting them off `skip`.
+fn skip_rows(batch: RecordBatch, skip: &mut usize) -> RecordBatch {
+    let skipped = (*skip).min(batch.num_rows());
+    *skip -= skipped;
+    batch.slice(skipped, batch.num_rows() - skipped)
+}
+
+/// Keep the rows of a batch starting at file position `start` whose positions fall in `range`.
+fn slice_positions(batch: &RecordBatch, start: i64, range: &Range<i64>) -> RecordBatch {
+    let end = start + batch.num_rows() as i64;
+    let from = range.start.clamp(start, end);
+    let to = range.end.clamp(from, end);
+    batch.slice((from - start) as usize, (to - from) as usize)
+}
+
+/// Whether a column name refers to a virtual column added by icepeek rather than the table schema.
+pub fn is_metadata_column(name: &str) -> bool {
+    name == FILE_COLUMN || name == POS_COLUMN
+}
+
+/// Add `_file` and `_pos` columns. `start_pos` is the position of the batch's first row
+/// in its file, or `None` when positions are unknown.
+fn append_metadata_columns(
+    batch: &RecordBatch,
+    file_path: &str,
+    start_pos: Option<i64>,
+) -> Result<RecordBatch> {
+    let rows = batch.num_rows();
+    let file: ArrayRef = Arc::new(StringArray::from(vec![file_path; rows]));
+    let pos: ArrayRef = Arc::new(match start_pos {
+        Some(start) => Int64Array::from_iter_values(start..start + rows as i64),
+        None => Int64Array::new_null(rows),
+    });
+
+    let mut fields: Vec<FieldRef> = batch.schema().fields().iter().cloned().collect();
+    fields.push(Arc::new(Field::new(FILE_COLUMN, DataType::Utf8, false)));
+    fields.push(Arc::new(Field::new(POS_COLUMN, DataType::Int64, true)));
+    let mut columns = batch.columns().to_vec();
+    columns.push(file);
+    columns.push(pos);
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+        .context("failed to add metadata columns")
+}
+
+/// Human-readable name of the snapshot a scan reads, for error messages.
+fn snapshot_label(snapshot_id: Option<i64>) -> String {
+    match snapshot_id {
+        Some(id) => format!("snapshot {}", id),
+        None => "current snapshot".to_string(),
+    }
+}
+
+/// Schema of the snapshot a scan reads, falling back to the table's current schema.
+fn scan_schema(handle: &TableHandle, snapshot_id: Option<i64>) -> &iceberg::spec::SchemaRef {
+    let metadata = handle.table.metadata();
+    snapshot_id
+        .and_then(|id| metadata.snapshot_by_id(id))
+        .and_then(|s| s.schema_id())
+        .and_then(|id| metadata.schema_by_id(id))
+        .unwrap_or_else(|| metadata.current_schema())
+}
+
+/// The top-level column holding the struct field at a dotted `path`, e.g. `metrics` for
+/// `metrics.cpu.p99`. `None` when `path` is a top-level column itself or starts with no column
+/// of the schema (left to the missing-column handling); an error when a column matches but the
+/// rest of the path doesn't resolve through its structs.
+fn nested_column_root(schema: &iceberg::spec::Schema, path: &str) -> Result<Option<String>> {
+    let top = schema.as_struct().fields();
+    let Some((index, Some(mut rest))) =
+        match_path_segment(top.iter().map(|f| f.name.as_str()), path)
+    else {
+        return Ok(None);
+    };
+    let root = top[index].name.clone();
+    let mut field = &top[index];
+    let mut resolved = root.clone();
+    loop {
+        let Type::Struct(children) = field.field_type.as_ref() else {
+            bail!(
+                "cannot resolve `{}`: `{}` is {}, not a struct",
+                path,
+                resolved,
+                field.field_type
+            );
+        };
+        let children = children.fields();
+        let Some((i, next)) = match_path_segment(children.iter().map(|f| f.name.as_str()), rest)
+        else {
+            bail!(
+                "cannot resolve `{}`: `{}` has no field `{}`",
+                path,
+                resolved,
+                rest
+            );
+        };
+        field = &children[i];
+        resolved = format!("{}.{}", resolved, field.name);
+        match next {
+            Some(next) => rest = next,
+            None => return Ok(Some(root)),
         }
     }
+}
+
+/// Split requested columns into those present in the schema and those that are not.
+fn split_columns(
+    requested: &[String],
+    exists: impl Fn(&str) -> bool,
+) -> (Vec<String>, Vec<String>) {
+    requested.iter().cloned().partition(|name| exists(name))
+}
+
+/// Plan a scan without reading data, reporting file pruning and per-file residual filters.
+#[tracing::instrument(skip_all, fields(snapshot = ?request.snapshot_id))]
+pub async fn plan_scan(handle: &TableHandle, request: &ScanRequest) -> Result<ScanPlan> {
+    let mut tasks = plan_tasks(handle, request.snapshot_id, request.filter.clone()).await?;
+    // Conditions on `_file` skip whole files when reading, so the plan leaves them out too.
+    tasks.retain(|task| {
+        request
+            .metadata_filter
+            .iter()
+            .all(|c| c.matches_file(&task.file_path))
+    });
+    let total_files = if request.filter.is_some() || !request.metadata_filter.is_empty() {
+        plan_tasks(handle, request.snapshot_id, None).await?.len()
+    } else {
+        tasks.len()
+    };
+
+    Ok(ScanPlan {
+        snapshot_id: request
+            .snapshot_id
+            .or_else(|| handle.table.metadata().current_snapshot_id()),
+        filter: request.filter.as_ref().map(|f| f.to_string()),
+        total_files,
+        tasks,
+    })
+}
+
+async fn plan_tasks(
+    handle: &TableHandle,
+    snapshot_id: Option<i64>,
+    filter: Option<Predicate>,
+) -> Result<Vec<PlannedTask>> {
+    let mut builder = handle.table.scan();
+    if let Some(filter) = filter {
+        let schema = scan_schema(handle, snapshot_id);
+        builder = builder.with_filter(type_decimal_literals(&filter, schema)?);
+    }
+    if let Some(snapshot_id) = snapshot_id {
+        builder = builder.snapshot_id(snapshot_id);
+    }
+    let scan = builder
+        .build()
+        .with_context(|| format!("failed to build scan of {}", snapshot_label(snapshot_id)))?;
+
+    let tasks: Vec<_> = scan
+        .plan_files()
+        .await
+        .with_context(|| format!("failed to plan scan of {}", snapshot_label(snapshot_id)))?
+        .try_collect()
+        .await
+        .context("failed to collect scan plan")?;
+
+    Ok(tasks
+        .into_iter()
+        .map(|task| PlannedTask {
+            residual: task.predicate.as_ref().map(|p| p.to_string()),
+            delete_files: task.deletes.len(),
+            record_count: task.record_count,
+            file_path: task.data_file_path,
+        })
+        .collect())
+}
 
-    let has_more = request.limit.is_some_and(|lim| collected >= lim);
+/// Run the request's SQL query over the scan's rows, streamed in as the query reads them.
+#[cfg(feature = "sql")]
+#[tracing::instrument(skip(handle, scan, request))]
+async fn execute_sql_scan(
+    handle: &TableHandle,
+    scan: &TableScan,
+    request: &ScanRequest,
+    query: &str,
+) -> Result<ScanResult> {
+    use futures::StreamExt;
 
+    let label = snapshot_label(request.snapshot_id);
+    let projection = request.projection.clone();
+    let mut input = read_scan(handle, scan)
+        .await?
+        .map(move |batch| {
+            let batch = batch.with_context(|| format!("failed to read {}", label))?;
+            match projection {
+                Some(ref projection) => Ok(projection.apply(&[batch])?.remove(0)),
+                None => Ok(batch),
+            }
+        })
+        .boxed();
+
+    // The first batch gives the schema the query sees, projection included.
+    let first = input.try_next().await.map_err(explain_missing_file)?;
+    let schema = match first {
+        Some(ref batch) => batch.schema(),
+        None => Arc::new(
+            iceberg::arrow::schema_to_arrow_schema(scan_schema(handle, request.snapshot_id))
+                .context("failed to convert table schema to Arrow")?,
+        ),
+    };
+    let input = futures::stream::iter(first.map(Ok)).chain(input).boxed();
+
+    let (mut batches, has_more) = super::sql::run_query(schema, input, query, request.limit)
+        .await
+        .map_err(explain_missing_file)?;
     if let Some(limit) = request.limit {
         batches = limit_batches(batches, limit);
     }
 
-    Ok(ScanResult { batches, has_more })
+    Ok(ScanResult {
+        batches,
+        offset: 0,
+        has_more,
+        dropped_columns: vec![],
+        read_all_columns: false,
+        error: None,
+    })
 }
 
 /// Limit the total number of rows across batches.
@@ -92,6 +713,130 @@ mod tests {
         assert!(req.filter.is_none());
         assert!(req.snapshot_id.is_none());
         assert!(req.limit.is_none());
+        assert!(req.projection.is_none());
+        assert!(req.sql.is_none());
+        assert!(!req.metadata_columns);
+    }
+
+    fn id_batch(ids: Vec<i32>) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        RecordBatch::try_new(schema, vec![Arc::new(arrow_array::Int32Array::from(ids))]).unwrap()
+    }
+
+    #[test]
+    fn metadata_columns_tag_file_and_position() {
+        let batch =
+            append_metadata_columns(&id_batch(vec![7, 8, 9]), "s3://b/data/a.parquet", Some(100))
+                .unwrap();
+        assert_eq!(batch.num_columns(), 3);
+        assert_eq!(batch.schema().field(1).name(), FILE_COLUMN);
+
+        let file = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(file.value(2), "s3://b/data/a.parquet");
+        let pos = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(pos.values().to_vec(), vec![100, 101, 102]);
+    }
+
+    #[test]
+    fn metadata_columns_unknown_position_is_null() {
+        let batch = append_metadata_columns(&id_batch(vec![1, 2]), "/a.parquet", None).unwrap();
+        assert_eq!(batch.column(2).null_count(), 2);
+    }
+
+    #[test]
+    fn slice_positions_keeps_matching_rows() {
+        let batch = id_batch(vec![0, 1, 2, 3, 4]);
+        // Batch covers file positions 10..15.
+        assert_eq!(slice_positions(&batch, 10, &(12..i64::MAX)).num_rows(), 3);
+        assert_eq!(slice_positions(&batch, 10, &(i64::MIN..11)).num_rows(), 1);
+        assert_eq!(slice_positions(&batch, 10, &(20..30)).num_rows(), 0);
+        assert_eq!(slice_positions(&batch, 10, &(0..5)).num_rows(), 0);
+    }
+
+    #[test]
+    fn skip_rows_carries_remainder_across_batches() {
+        let mut skip = 4;
+        assert_eq!(skip_rows(id_batch(vec![0, 1, 2]), &mut skip).num_rows(), 0);
+        assert_eq!(skip, 1);
+        assert_eq!(skip_rows(id_batch(vec![3, 4, 5]), &mut skip).num_rows(), 2);
+        assert_eq!(skip, 0);
+    }
+
+    #[test]
+    fn metadata_column_names() {
+        assert!(is_metadata_column("_file"));
+        assert!(is_metadata_column("_pos"));
+        assert!(!is_metadata_column("file"));
+    }
+
+    #[test]
+    fn scan_plan_counts_pruned_files() {
+        let task = PlannedTask {
+            file_path: "/data/a.parquet".into(),
+            record_count: Some(10),
+            residual: None,
+            delete_files: 0,
+        };
+        let plan = ScanPlan {
+            snapshot_id: Some(1),
+            filter: Some("age > 30".into()),
+            total_files: 5,
+            tasks: vec![task.clone(), task],
+        };
+        assert_eq!(plan.pruned_files(), 3);
+    }
+
+    #[test]
+    fn nested_paths_resolve_to_their_top_level_column() {
+        use iceberg::spec::{NestedField, NestedFieldRef, PrimitiveType, Schema, StructType};
+
+        let leaf = |id: i32, name: &str, ty: PrimitiveType| -> NestedFieldRef {
+            NestedField::optional(id, name, Type::Primitive(ty)).into()
+        };
+        let cpu = StructType::new(vec![leaf(4, "p99", PrimitiveType::Double)]);
+        let metrics = StructType::new(vec![
+            NestedField::optional(3, "cpu", Type::Struct(cpu)).into(),
+            leaf(5, "host", PrimitiveType::String),
+        ]);
+        let schema = Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)).into(),
+                NestedField::optional(2, "metrics", Type::Struct(metrics)).into(),
+            ])
+            .build()
+            .unwrap();
+
+        let root = |path| nested_column_root(&schema, path);
+        assert_eq!(root("metrics.cpu.p99").unwrap().as_deref(), Some("metrics"));
+        assert_eq!(root("metrics.host").unwrap().as_deref(), Some("metrics"));
+        assert_eq!(root("metrics").unwrap(), None);
+        assert_eq!(root("other.cpu").unwrap(), None);
+        assert_eq!(
+            root("metrics.cpu.p999").unwrap_err().to_string(),
+            "cannot resolve `metrics.cpu.p999`: `metrics.cpu` has no field `p999`"
+        );
+        assert!(root("id.x")
+            .unwrap_err()
+            .to_string()
+            .starts_with("cannot resolve `id.x`: `id` is "));
+    }
+
+    #[test]
+    fn split_columns_drops_columns_absent_from_old_schema() {
+        // Snapshot written before `email` was added to the table.
+        let old_schema = ["id", "name"];
+        let requested = vec!["id".to_string(), "email".to_string(), "name".to_string()];
+        let (kept, dropped) = split_columns(&requested, |name| old_schema.contains(&name));
+        assert_eq!(kept, vec!["id", "name"]);
+        assert_eq!(dropped, vec!["email"]);
     }
 
     #[test]
@@ -143,4 +888,220 @@ mod tests {
         assert_eq!(limited[0].num_rows(), 3);
         assert_eq!(limited[1].num_rows(), 1);
     }
+
+    #[test]
+    fn missing_files_are_named_with_a_hint() {
+        let not_found = opendal::Error::new(opendal::ErrorKind::NotFound, "object not found")
+            .with_context("service", "s3")
+            .with_context("path", "db/t/data/a.parquet");
+        let e = anyhow::Error::new(not_found).context("failed to read current snapshot");
+        let message = format!("{:#}", explain_missing_file(e));
+        assert!(message.starts_with("db/t/data/a.parquet was not found;"));
+        assert!(message.contains("--location-override OLD=NEW"));
+
+        let other = anyhow::anyhow!("corrupt footer").context("failed to read current snapshot");
+        let message = format!("{:#}", explain_missing_file(other));
+        assert_eq!(message, "failed to read current snapshot: corrupt footer");
+    }
+
+    #[tokio::test]
+    async fn stream_failure_keeps_rows_read_before_it() {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(arrow_array::Int32Array::from(vec![1, 2]))],
+        )
+        .unwrap();
+        let corrupt = iceberg::Error::new(iceberg::ErrorKind::DataInvalid, "corrupt footer");
+        let stream: ArrowRecordBatchStream =
+            Box::pin(futures::stream::iter(vec![Ok(batch), Err(corrupt)]));
+
+        let mut batches = Vec::new();
+        let collected = collect_until_limit(stream, None, &mut batches, Ok).await;
+        let (has_more, error) = keep_partial(collected, &batches).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert!(!has_more);
+        assert!(error.unwrap().contains("corrupt footer"));
+
+        let failed = keep_partial(Err(anyhow::anyhow!("no rows")), &[]);
+        assert!(failed.is_err());
+        assert_eq!(keep_partial(Ok(true), &[]).unwrap(), (true, None));
+    }
+
+    #[tokio::test]
+    async fn live_rows_are_counted_by_reading_files_with_deletes() {
+        let handle = crate::loader::test_support::sample_table().await;
+        assert_eq!(count_live_rows(&handle, None).await.unwrap(), 200);
+
+        // The sample has no deletes, so read its files the way files with deletes are read.
+        let tasks: Vec<FileScanTask> = handle
+            .table
+            .scan()
+            .select_empty()
+            .build()
+            .unwrap()
+            .plan_files()
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        for task in tasks {
+            let expected = task.record_count.unwrap() as usize;
+            assert_eq!(read_task_rows(&handle, task).await.unwrap(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn plan_leaves_out_files_excluded_by_file_conditions() {
+        let handle = crate::loader::test_support::sample_table().await;
+        let plan = plan_scan(&handle, &ScanRequest::default()).await.unwrap();
+        assert!(plan.tasks.len() > 1);
+        let file = plan.tasks[0].file_path.rsplit('/').next().unwrap();
+
+        let filter = crate::model::filter::parse_scan_filter(&format!("_file = '{}'", file));
+        let request = ScanRequest {
+            metadata_filter: filter.unwrap().metadata,
+            ..Default::default()
+        };
+        let plan = plan_scan(&handle, &request).await.unwrap();
+        assert_eq!(plan.tasks.len(), 1);
+        assert!(plan.tasks[0].file_path.ends_with(file));
+        assert_eq!(plan.pruned_files(), plan.total_files - 1);
+    }
+
+    async fn scan_sample(request: ScanRequest) -> ScanResult {
+        let handle = crate::loader::test_support::sample_table().await;
+        execute_scan(&handle, &request).await.unwrap()
+    }
+
+    fn predicate(filter: &str) -> Option<Predicate> {
+        crate::model::filter::parse_scan_filter(filter)
+            .unwrap()
+            .predicate
+    }
+
+    fn rows(result: &ScanResult) -> usize {
+        result.batches.iter().map(|b| b.num_rows()).sum()
+    }
+
+    fn column_names(result: &ScanResult) -> Vec<String> {
+        result.batches[0]
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn sample_scan_applies_filter() {
+        let result = scan_sample(ScanRequest {
+            filter: predicate("department = 'Engineering'"),
+            ..Default::default()
+        })
+        .await;
+        // Every fifth employee is in Engineering.
+        assert_eq!(rows(&result), 40);
+        assert!(!result.has_more);
+
+        let result = scan_sample(ScanRequest {
+            filter: predicate("department = 'Engineering' AND id <= 100"),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(rows(&result), 20);
+    }
+
+    #[tokio::test]
+    async fn sample_scan_projects_columns() {
+        let result = scan_sample(ScanRequest {
+            columns: Some(vec!["name".into(), "id".into()]),
+            limit: Some(25),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(rows(&result), 25);
+        assert!(result.has_more);
+        let mut columns = column_names(&result);
+        columns.sort();
+        assert_eq!(columns, ["id", "name"]);
+    }
+
+    #[tokio::test]
+    async fn sample_scan_evaluates_literal_only_projections() {
+        let result = scan_sample(ScanRequest {
+            projection: Some(crate::loader::projection::parse_projection("1 AS one").unwrap()),
+            limit: Some(25),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(rows(&result), 25);
+        assert_eq!(column_names(&result), ["one"]);
+    }
+
+    #[tokio::test]
+    async fn sample_scan_time_travels() {
+        let result = scan_sample(ScanRequest {
+            snapshot_id: Some(1),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(rows(&result), 50);
+        assert!(!column_names(&result).contains(&"title".to_string()));
+
+        let result = scan_sample(ScanRequest {
+            snapshot_id: Some(2),
+            filter: predicate("department = 'Engineering'"),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(rows(&result), 30);
+
+        // `title` only exists from snapshot 2 on.
+        let result = scan_sample(ScanRequest {
+            snapshot_id: Some(1),
+            columns: Some(vec!["id".into(), "title".into()]),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(result.dropped_columns, ["title"]);
+        assert_eq!(column_names(&result), ["id"]);
+        assert!(!result.read_all_columns);
+    }
+
+    #[tokio::test]
+    async fn scan_reads_all_columns_when_none_requested_exist() {
+        let result = scan_sample(ScanRequest {
+            snapshot_id: Some(1),
+            columns: Some(vec!["title".into()]),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(result.dropped_columns, ["title"]);
+        assert!(result.read_all_columns);
+        assert_eq!(rows(&result), 50);
+        assert!(column_names(&result).contains(&"id".to_string()));
+    }
+
+    #[cfg(feature = "sql")]
+    #[tokio::test]
+    async fn sql_query_runs_over_the_scan() {
+        let result = scan_sample(ScanRequest {
+            sql: Some("SELECT department, COUNT(*) AS n FROM t GROUP BY department".into()),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(column_names(&result), ["department", "n"]);
+        assert!(!result.has_more);
+
+        let result = scan_sample(ScanRequest {
+            sql: Some("SELECT id FROM t".into()),
+            limit: Some(5),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(rows(&result), 5);
+        assert!(result.has_more);
+    }
 }
@@ -0,0 +1,194 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use arrow_array::RecordBatch;
+use arrow_schema::SchemaRef;
+use datafusion::catalog::streaming::StreamingTable;
+use datafusion::error::DataFusionError;
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream;
+use datafusion::prelude::SessionContext;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+
+/// Name the scanned table is registered under in SQL queries.
+pub const SQL_TABLE_NAME: &str = "t";
+
+/// Run a SQL query over scanned batches registered as table `t`, keeping at most `limit`
+/// result rows. The batches are pulled as the query asks for them, so memory holds what the
+/// query itself needs (groups, rows to sort) rather than the whole scan, and a query without
+/// aggregation or sorting stops reading once it has enough rows. Also returns whether the
+/// query had rows beyond `limit`.
+pub async fn run_query(
+    schema: SchemaRef,
+    batches: BoxStream<'static, Result<RecordBatch>>,
+    query: &str,
+    limit: Option<usize>,
+) -> Result<(Vec<RecordBatch>, bool)> {
+    let ctx = SessionContext::new();
+    let input = ScanInput {
+        schema: schema.clone(),
+        batches: Mutex::new(Some(batches)),
+    };
+    let table = StreamingTable::try_new(schema, vec![Arc::new(input)])
+        .context("failed to register batches")?;
+    ctx.register_table(SQL_TABLE_NAME, Arc::new(table))
+        .context("failed to register table")?;
+
+    let mut df = ctx
+        .sql(query)
+        .await
+        .with_context(|| format!("invalid SQL: {}", query))?;
+    if let Some(limit) = limit {
+        // One extra row tells whether there are more.
+        df = df.limit(0, Some(limit + 1))?;
+    }
+    let mut results = df
+        .execute_stream()
+        .await
+        .context("failed to execute SQL query")?;
+
+    let mut batches = Vec::new();
+    let mut rows = 0;
+    while let Some(batch) = results
+        .try_next()
+        .await
+        .context("failed to execute SQL query")?
+    {
+        rows += batch.num_rows();
+        batches.push(batch);
+        if limit.is_some_and(|limit| rows > limit) {
+            break;
+        }
+    }
+    Ok((batches, limit.is_some_and(|limit| rows > limit)))
+}
+
+/// The scan's batch stream as the single partition of table `t`. It can be read once, so a
+/// query that reads `t` twice, such as a self-join, fails instead of seeing an empty table.
+struct ScanInput {
+    schema: SchemaRef,
+    batches: Mutex<Option<BoxStream<'static, Result<RecordBatch>>>>,
+}
+
+impl std::fmt::Debug for ScanInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScanInput").finish_non_exhaustive()
+    }
+}
+
+impl PartitionStream for ScanInput {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let batches = match self.batches.lock().unwrap().take() {
+            Some(batches) => batches
+                .map_err(|e| DataFusionError::External(e.into()))
+                .boxed(),
+            None => futures::stream::once(async {
+                Err(DataFusionError::Execution(format!(
+                    "table `{}` can only be read once per query",
+                    SQL_TABLE_NAME
+                )))
+            })
+            .boxed(),
+        };
+        Box::pin(RecordBatchStreamAdapter::new(self.schema.clone(), batches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int64Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+
+    fn make_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("dept", DataType::Utf8, false),
+            Field::new("salary", DataType::Int64, false),
+        ]));
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(vec!["Eng", "Eng", "HR"])),
+                Arc::new(Int64Array::from(vec![10, 20, 30])),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn stream_of(batches: Vec<RecordBatch>) -> BoxStream<'static, Result<RecordBatch>> {
+        futures::stream::iter(batches.into_iter().map(Ok)).boxed()
+    }
+
+    fn row_count(batches: &[RecordBatch]) -> usize {
+        batches.iter().map(|b| b.num_rows()).sum()
+    }
+
+    #[tokio::test]
+    async fn group_by_query() {
+        let batch = make_batch();
+        let (out, has_more) = run_query(
+            batch.schema(),
+            stream_of(vec![batch]),
+            "SELECT dept, COUNT(*) AS n FROM t GROUP BY dept ORDER BY dept",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(row_count(&out), 2);
+        assert!(!has_more);
+    }
+
+    #[tokio::test]
+    async fn limit_stops_reading_the_input() {
+        let batch = make_batch();
+        let schema = batch.schema();
+        // The second batch is never needed, so its error is never seen.
+        let batches =
+            futures::stream::iter(vec![Ok(batch), Err(anyhow::anyhow!("read past the limit"))])
+                .boxed();
+        let (out, has_more) = run_query(schema, batches, "SELECT * FROM t", Some(2))
+            .await
+            .unwrap();
+        assert!(row_count(&out) > 2);
+        assert!(has_more);
+    }
+
+    #[tokio::test]
+    async fn input_errors_fail_the_query() {
+        let schema = make_batch().schema();
+        let batches = futures::stream::iter(vec![Err(anyhow::anyhow!("disk on fire"))]).boxed();
+        let err = run_query(schema, batches, "SELECT COUNT(*) FROM t", None)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("disk on fire"));
+    }
+
+    #[tokio::test]
+    async fn self_join_is_an_error() {
+        let batch = make_batch();
+        let err = run_query(
+            batch.schema(),
+            stream_of(vec![batch]),
+            "SELECT * FROM t a JOIN t b ON a.dept = b.dept",
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("only be read once"));
+    }
+
+    #[tokio::test]
+    async fn invalid_sql_errors() {
+        let batch = make_batch();
+        let err = run_query(batch.schema(), stream_of(vec![batch]), "SELEC nope", None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid SQL"));
+    }
+}
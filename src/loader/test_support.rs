@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+use tokio::sync::OnceCell;
+
+use super::file_io::StorageConfig;
+use super::{direct_loader, TableHandle};
+
+#[allow(dead_code)]
+#[path = "../../examples/create_sample_data.rs"]
+mod create_sample_data;
+
+static SAMPLE_TABLE: OnceCell<PathBuf> = OnceCell::const_new();
+
+/// Load `examples/sample_table`, generating it first with the `create_sample_data` example
+/// when it is missing: snapshots 1, 2 and 3 hold 50, 150 and 200 rows, and snapshot 2 adds
+/// the `title` column.
+pub async fn sample_table() -> TableHandle {
+    let path = SAMPLE_TABLE.get_or_init(generate_if_missing).await;
+    direct_loader::load_direct(&path.to_string_lossy(), &StorageConfig::default())
+        .await
+        .expect("failed to load the sample table")
+}
+
+async fn generate_if_missing() -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("examples")
+        .join("sample_table");
+    // The version hint is written last, so it marks a complete table.
+    if dir.join("metadata").join("version-hint.text").exists() {
+        return dir;
+    }
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    std::fs::create_dir_all(dir.join("metadata")).unwrap();
+    std::fs::create_dir_all(dir.join("data")).unwrap();
+
+    let location = dir.canonicalize().unwrap().to_string_lossy().to_string();
+    let file_io = iceberg::io::FileIOBuilder::new_fs_io().build().unwrap();
+    create_sample_data::run_manual(file_io, &location)
+        .await
+        .expect("failed to generate the sample table");
+    dir
+}
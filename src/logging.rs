@@ -0,0 +1,56 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+const DEFAULT_FILTER: &str = "icepeek=info";
+const VERBOSE_FILTER: &str = "icepeek=debug,iceberg=debug";
+
+/// Write tracing output to `log_file` while the TUI runs. Without a log file nothing is
+/// recorded, since the terminal belongs to the TUI. `RUST_LOG` overrides the level.
+pub fn init(log_file: Option<&Path>, verbose: bool) -> Result<()> {
+    let Some(path) = log_file else {
+        return Ok(());
+    };
+    let file =
+        File::create(path).with_context(|| format!("cannot create log file {}", path.display()))?;
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_filter(verbose)));
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        // Closing spans report how long each load and scan step took.
+        .with_span_events(FmtSpan::CLOSE)
+        .try_init()
+        .map_err(|e| anyhow!(e))
+        .context("failed to initialize logging")
+}
+
+fn default_filter(verbose: bool) -> &'static str {
+    if verbose {
+        VERBOSE_FILTER
+    } else {
+        DEFAULT_FILTER
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbose_raises_default_level() {
+        assert_eq!(default_filter(false), "icepeek=info");
+        assert!(default_filter(true).contains("icepeek=debug"));
+    }
+
+    #[test]
+    fn no_log_file_is_noop() {
+        assert!(init(None, true).is_ok());
+    }
+}
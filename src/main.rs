@@ -1,17 +1,22 @@
 mod app;
 mod cli;
+mod clipboard;
 mod components;
 mod event;
+mod export;
 mod loader;
+mod logging;
 mod model;
+mod recents;
 mod ui;
+mod util;
 
 use anyhow::Result;
-use clap::Parser;
 use cli::Cli;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let cli = Cli::parse_args();
+    logging::init(cli.log_file.as_deref(), cli.verbose)?;
     app::run(cli).await
 }
@@ -0,0 +1,288 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::model::table_info::DataFileInfo;
+
+/// Share of the narrower of two key ranges two data files must have in common to be reported.
+pub const OVERLAP_THRESHOLD: f64 = 0.5;
+
+/// Data-quality findings over the live files of a snapshot, e.g. left behind by a faulty
+/// compaction.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileDiagnostics {
+    /// Pairs of data files whose key ranges overlap by at least `OVERLAP_THRESHOLD`.
+    pub overlapping_pairs: usize,
+    /// Data files in at least one such pair.
+    pub overlapping_files: usize,
+    /// Paths referenced by more than one live manifest entry, with the number of entries.
+    pub duplicate_paths: Vec<(String, usize)>,
+}
+
+impl FileDiagnostics {
+    pub fn is_clean(&self) -> bool {
+        self.overlapping_pairs == 0 && self.duplicate_paths.is_empty()
+    }
+}
+
+/// Input of one background `diagnose` run. The generation comes back with the result, so a
+/// result computed for files that have since changed can be dropped.
+#[derive(Debug)]
+pub struct DiagnosticsRequest {
+    pub generation: u64,
+    pub groups: Vec<Vec<DataFileInfo>>,
+    pub key_field: Option<i32>,
+}
+
+/// How the bounds of one column compare: as numbers when every bound parses as one, otherwise
+/// as text. Deciding once for all the bounds keeps the order total, which sorting relies on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoundOrder {
+    Numeric,
+    Text,
+}
+
+impl BoundOrder {
+    pub fn of<'a>(bounds: impl IntoIterator<Item = &'a str>) -> Self {
+        if bounds.into_iter().all(|b| b.parse::<f64>().is_ok()) {
+            BoundOrder::Numeric
+        } else {
+            BoundOrder::Text
+        }
+    }
+
+    pub fn compare(self, a: &str, b: &str) -> Ordering {
+        match self {
+            BoundOrder::Numeric => number(a).total_cmp(&number(b)),
+            BoundOrder::Text => a.cmp(b),
+        }
+    }
+}
+
+fn number(bound: &str) -> f64 {
+    bound.parse().unwrap_or(f64::NAN)
+}
+
+/// Check the live files of a snapshot, grouped by manifest, for duplicate paths and for data
+/// files of the same partition whose bounds on `key_field` overlap significantly. This is
+/// quadratic in the worst case, so callers run it off the UI thread.
+pub fn diagnose(groups: &[Vec<DataFileInfo>], key_field: Option<i32>) -> FileDiagnostics {
+    let mut references: HashMap<&str, usize> = HashMap::new();
+    for file in groups.iter().flatten() {
+        *references.entry(file.file_path.as_str()).or_default() += 1;
+    }
+    let mut duplicate_paths: Vec<(String, usize)> = references
+        .into_iter()
+        .filter(|&(_, n)| n > 1)
+        .map(|(path, n)| (path.to_string(), n))
+        .collect();
+    duplicate_paths.sort();
+
+    let mut diagnostics = FileDiagnostics {
+        duplicate_paths,
+        ..FileDiagnostics::default()
+    };
+    let Some(field_id) = key_field else {
+        return diagnostics;
+    };
+
+    // Files of different partitions never hold the same rows, however their ranges compare.
+    let mut partitions: BTreeMap<Vec<(&str, &str)>, Vec<Range>> = BTreeMap::new();
+    for file in groups.iter().flatten().filter(|f| f.content == "Data") {
+        let (Some(lower), Some(upper)) = (
+            file.lower_bounds.get(&field_id),
+            file.upper_bounds.get(&field_id),
+        ) else {
+            continue;
+        };
+        let mut partition: Vec<(&str, &str)> = file
+            .partition_data
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        partition.sort();
+        partitions
+            .entry(partition)
+            .or_default()
+            .push((lower.as_str(), upper.as_str()));
+    }
+    for ranges in partitions.values_mut() {
+        let (pairs, files) = count_overlaps(ranges);
+        diagnostics.overlapping_pairs += pairs;
+        diagnostics.overlapping_files += files;
+    }
+    diagnostics
+}
+
+/// Lower and upper bound of a data file on the key column.
+type Range<'a> = (&'a str, &'a str);
+
+/// Count the pairs of `ranges` that overlap by at least `OVERLAP_THRESHOLD`, and the ranges
+/// in any such pair.
+fn count_overlaps(ranges: &mut [Range]) -> (usize, usize) {
+    let order = BoundOrder::of(ranges.iter().flat_map(|&(lower, upper)| [lower, upper]));
+    ranges.sort_by(|a, b| order.compare(a.0, b.0));
+
+    // Sorted by lower bound, so each range is only compared with the ones starting inside it.
+    let mut pairs = 0;
+    let mut flagged = vec![false; ranges.len()];
+    for i in 0..ranges.len() {
+        for j in i + 1..ranges.len() {
+            if order.compare(ranges[j].0, ranges[i].1) == Ordering::Greater {
+                break;
+            }
+            if overlap_fraction(order, ranges[i], ranges[j]) >= OVERLAP_THRESHOLD {
+                pairs += 1;
+                flagged[i] = true;
+                flagged[j] = true;
+            }
+        }
+    }
+    (pairs, flagged.iter().filter(|&&f| f).count())
+}
+
+/// How much of the narrower of two ranges lies inside the other, where `a` starts no later
+/// than `b`. Ranges that only touch at an end don't overlap; ranges of text can't be measured,
+/// so any overlap counts in full.
+fn overlap_fraction(order: BoundOrder, a: (&str, &str), b: (&str, &str)) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    if order.compare(b.0, a.1) != Ordering::Less {
+        return 0.0;
+    }
+    if order == BoundOrder::Text {
+        return 1.0;
+    }
+    let [a_lo, a_hi, b_lo, b_hi] = [a.0, a.1, b.0, b.1].map(number);
+    let narrower = (a_hi - a_lo).min(b_hi - b_lo);
+    if narrower <= 0.0 {
+        return 1.0;
+    }
+    (a_hi.min(b_hi) - b_lo) / narrower
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, bounds: Option<(&str, &str)>) -> DataFileInfo {
+        let mut df = DataFileInfo {
+            file_path: path.into(),
+            file_format: "Parquet".into(),
+            content: "Data".into(),
+            equality_ids: vec![],
+            record_count: 10,
+            file_size_bytes: 100,
+            null_value_counts: HashMap::new(),
+            lower_bounds: HashMap::new(),
+            upper_bounds: HashMap::new(),
+            partition_data: HashMap::new(),
+            entry_status: "Added".into(),
+            entry_snapshot_id: None,
+            sequence_number: None,
+            file_sequence_number: None,
+        };
+        if let Some((lower, upper)) = bounds {
+            df.lower_bounds.insert(1, lower.into());
+            df.upper_bounds.insert(1, upper.into());
+        }
+        df
+    }
+
+    #[test]
+    fn overlap_is_measured_against_the_narrower_range() {
+        let numeric = BoundOrder::Numeric;
+        assert_eq!(overlap_fraction(numeric, ("0", "10"), ("10", "20")), 0.0);
+        assert_eq!(overlap_fraction(numeric, ("0", "10"), ("5", "20")), 0.5);
+        assert_eq!(overlap_fraction(numeric, ("0", "100"), ("40", "50")), 1.0);
+        assert_eq!(
+            overlap_fraction(numeric, ("0", "10"), ("9", "100")),
+            1.0 / 10.0
+        );
+        assert_eq!(overlap_fraction(numeric, ("3", "3"), ("3", "3")), 1.0);
+        assert_eq!(
+            overlap_fraction(BoundOrder::Text, ("\"a\"", "\"m\""), ("\"c\"", "\"z\"")),
+            1.0
+        );
+    }
+
+    #[test]
+    fn bound_order_is_total_over_mixed_and_nan_bounds() {
+        assert_eq!(BoundOrder::of(["1", "-2.5", "1e3"]), BoundOrder::Numeric);
+        assert_eq!(BoundOrder::of(["1", "abc"]), BoundOrder::Text);
+        // Mixed numbers and text compare as text: "10" < "9" < "a".
+        let order = BoundOrder::of(["10", "9", "a"]);
+        assert_eq!(order.compare("10", "9"), Ordering::Less);
+        assert_eq!(order.compare("9", "a"), Ordering::Less);
+
+        let order = BoundOrder::of(["NaN", "1", "2"]);
+        assert_eq!(order, BoundOrder::Numeric);
+        assert_eq!(order.compare("NaN", "1"), Ordering::Greater);
+        let mut bounds = vec!["2", "NaN", "1", "NaN", "-inf"];
+        bounds.sort_by(|a, b| order.compare(a, b));
+        assert_eq!(bounds, ["-inf", "1", "2", "NaN", "NaN"]);
+    }
+
+    #[test]
+    fn diagnose_counts_significant_overlaps_only() {
+        let groups = vec![
+            vec![
+                file("/d/a.parquet", Some(("0", "99"))),
+                file("/d/b.parquet", Some(("100", "199"))),
+            ],
+            vec![
+                // Rewritten copy of a.parquet's range, as a buggy compaction might leave it.
+                file("/d/c.parquet", Some(("10", "90"))),
+                // Shares a tenth of b.parquet's range: normal for appended data.
+                file("/d/d.parquet", Some(("190", "290"))),
+                file("/d/e.parquet", None),
+            ],
+        ];
+        let d = diagnose(&groups, Some(1));
+        assert_eq!(d.overlapping_pairs, 1);
+        assert_eq!(d.overlapping_files, 2);
+        assert!(d.duplicate_paths.is_empty());
+
+        assert_eq!(diagnose(&groups, None), FileDiagnostics::default());
+        assert!(diagnose(&groups, None).is_clean());
+    }
+
+    #[test]
+    fn diagnose_compares_files_within_a_partition() {
+        let in_day = |path: &str, day: &str, bounds: (&str, &str)| {
+            let mut df = file(path, Some(bounds));
+            df.partition_data.insert("day".into(), day.into());
+            df
+        };
+        let groups = vec![vec![
+            in_day("/d/a.parquet", "2024-01-01", ("0", "99")),
+            in_day("/d/b.parquet", "2024-01-02", ("0", "99")),
+            in_day("/d/c.parquet", "2024-01-02", ("50", "99")),
+            in_day("/d/d.parquet", "2024-01-03", ("0", "99")),
+        ]];
+        let d = diagnose(&groups, Some(1));
+        assert_eq!(d.overlapping_pairs, 1);
+        assert_eq!(d.overlapping_files, 2);
+    }
+
+    #[test]
+    fn diagnose_finds_paths_in_several_manifests() {
+        let mut delete = file("/d/del.parquet", Some(("0", "99")));
+        delete.content = "PositionDeletes".into();
+        let groups = vec![
+            vec![file("/d/a.parquet", None), delete.clone()],
+            vec![file("/d/a.parquet", None), file("/d/b.parquet", None)],
+            vec![file("/d/a.parquet", None), delete],
+        ];
+        let d = diagnose(&groups, Some(1));
+        assert_eq!(
+            d.duplicate_paths,
+            [
+                ("/d/a.parquet".to_string(), 3),
+                ("/d/del.parquet".to_string(), 2)
+            ]
+        );
+        // Delete files aren't compared by bounds.
+        assert_eq!(d.overlapping_pairs, 0);
+    }
+}
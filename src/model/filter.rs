@@ -1,14 +1,19 @@
-use anyhow::{bail, Result};
-use iceberg::expr::{Predicate, Reference};
+use std::ops::Range;
+
+use anyhow::{bail, Context, Result};
+use iceberg::expr::{Predicate, PredicateOperator, Reference};
+use iceberg::spec::{Datum, PrimitiveLiteral, PrimitiveType, Schema, Type};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 
 /// Parse a simplified filter expression into an iceberg Predicate.
 ///
 /// Supported syntax:
 /// - `column > value`, `column < value`, `column >= value`, `column <= value`
-/// - `column = value`, `column != value`
+/// - `column = value`, `column != value` (or `<>`)
 /// - `column = 'string value'`
 /// - `column IS NULL`, `column IS NOT NULL`
-/// - `column IN ('a', 'b', 'c')`
+/// - `column IN ('a', 'b', 'c')` (values separated by commas or semicolons)
 /// - Combinators: `expr AND expr`, `expr OR expr`
 ///
 /// Values without quotes are parsed as numbers; quoted values as strings.
@@ -20,6 +25,143 @@ pub fn parse_filter(input: &str) -> Result<Predicate> {
     parse_or_expr(input)
 }
 
+/// A condition on a virtual metadata column (`_file`, `_pos`), evaluated by icepeek
+/// while reading rather than pushed into the Iceberg scan.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataCondition {
+    /// `_file = 'x'`, `_file != 'x'` or `_file IN (...)`. A value matches a full path or a file name.
+    File { values: Vec<String>, negated: bool },
+    /// A `_pos` comparison, as the half-open range of matching positions.
+    Pos(Range<i64>),
+}
+
+impl MetadataCondition {
+    /// Whether rows of the file at `path` can satisfy this condition.
+    pub fn matches_file(&self, path: &str) -> bool {
+        match self {
+            MetadataCondition::File { values, negated } => {
+                let hit = values
+                    .iter()
+                    .any(|v| path == v || path.ends_with(&format!("/{}", v)));
+                hit != *negated
+            }
+            MetadataCondition::Pos(_) => true,
+        }
+    }
+}
+
+/// A filter split into the predicate pushed into the scan and conditions on metadata columns.
+#[derive(Debug, Clone, Default)]
+pub struct ScanFilter {
+    pub predicate: Option<Predicate>,
+    pub metadata: Vec<MetadataCondition>,
+}
+
+/// Parse a filter that may also reference the `_file` and `_pos` metadata columns.
+/// Metadata conditions can only be combined with the rest of the filter using AND.
+pub fn parse_scan_filter(input: &str) -> Result<ScanFilter> {
+    let input = input.trim();
+    if input.is_empty() {
+        bail!("empty filter expression");
+    }
+
+    let mentions_metadata = input
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|word| word == "_file" || word == "_pos");
+    if !mentions_metadata {
+        return Ok(ScanFilter {
+            predicate: Some(parse_filter(input)?),
+            metadata: vec![],
+        });
+    }
+    if split_combinator(input, " OR ").is_some() {
+        bail!("_file and _pos conditions can only be combined with AND");
+    }
+
+    let mut table_conditions = Vec::new();
+    let mut metadata = Vec::new();
+    let mut remaining = input;
+    loop {
+        let (conjunct, tail) = match split_combinator(remaining, " AND ") {
+            Some((left, right)) => (left, Some(right)),
+            None => (remaining, None),
+        };
+        match parse_metadata_condition(conjunct)? {
+            Some(condition) => metadata.push(condition),
+            None => table_conditions.push(conjunct),
+        }
+        let Some(tail) = tail else { break };
+        remaining = tail;
+    }
+
+    let predicate = if table_conditions.is_empty() {
+        None
+    } else {
+        Some(parse_and_expr(&table_conditions.join(" AND "))?)
+    };
+    Ok(ScanFilter {
+        predicate,
+        metadata,
+    })
+}
+
+/// Parse a single condition on `_file` or `_pos`; `Ok(None)` for any other column.
+fn parse_metadata_condition(input: &str) -> Result<Option<MetadataCondition>> {
+    let input = input.trim();
+    let col_end = input
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(input.len());
+    let rest = input[col_end..].trim();
+
+    match &input[..col_end] {
+        "_file" => {
+            let upper = rest.to_uppercase();
+            let (values, negated) = if upper.starts_with("IN ") || upper.starts_with("IN(") {
+                let list = rest[2..].trim();
+                if !(list.starts_with('(') && list.ends_with(')')) {
+                    bail!("invalid IN expression: {}", input);
+                }
+                (parse_list_values(&list[1..list.len() - 1])?, false)
+            } else if let Some(value) = rest.strip_prefix("!=").or(rest.strip_prefix("<>")) {
+                (vec![unquote(value).to_string()], true)
+            } else if let Some(value) = rest.strip_prefix('=') {
+                (vec![unquote(value).to_string()], false)
+            } else {
+                bail!("_file supports =, !=, <> and IN: {}", input);
+            };
+            Ok(Some(MetadataCondition::File { values, negated }))
+        }
+        "_pos" => {
+            let operators = [">=", "<=", ">", "<", "="];
+            let Some(op) = operators.iter().find(|op| rest.starts_with(**op)) else {
+                bail!("_pos supports =, <, <=, > and >=: {}", input);
+            };
+            let value: i64 = rest[op.len()..]
+                .trim()
+                .parse()
+                .map_err(|_| anyhow::anyhow!("_pos must be compared to an integer: {}", input))?;
+            let range = match *op {
+                ">=" => value..i64::MAX,
+                "<=" => i64::MIN..value.saturating_add(1),
+                ">" => value.saturating_add(1)..i64::MAX,
+                "<" => i64::MIN..value,
+                _ => value..value.saturating_add(1),
+            };
+            Ok(Some(MetadataCondition::Pos(range)))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn unquote(val: &str) -> &str {
+    let val = val.trim();
+    if val.starts_with('\'') && val.ends_with('\'') && val.len() >= 2 {
+        &val[1..val.len() - 1]
+    } else {
+        val
+    }
+}
+
 fn parse_or_expr(input: &str) -> Result<Predicate> {
     let Some((left, right)) = split_combinator(input, " OR ") else {
         return parse_and_expr(input);
@@ -86,8 +228,8 @@ fn parse_comparison(input: &str) -> Result<Predicate> {
         bail!("invalid IN expression: {}", input);
     }
 
-    // Comparison operators: >=, <=, !=, >, <, =
-    let operators = [">=", "<=", "!=", ">", "<", "="];
+    // Comparison operators, two-character ones first so `<>` isn't read as `<`
+    let operators = ["<>", ">=", "<=", "!=", ">", "<", "="];
     for op in &operators {
         let Some(pos) = input.find(op) else { continue };
 
@@ -98,7 +240,7 @@ fn parse_comparison(input: &str) -> Result<Predicate> {
         return match *op {
             ">=" => Ok(Reference::new(col).greater_than_or_equal_to(datum)),
             "<=" => Ok(Reference::new(col).less_than_or_equal_to(datum)),
-            "!=" => Ok(Reference::new(col).not_equal_to(datum)),
+            "!=" | "<>" => Ok(Reference::new(col).not_equal_to(datum)),
             ">" => Ok(Reference::new(col).greater_than(datum)),
             "<" => Ok(Reference::new(col).less_than(datum)),
             "=" => Ok(Reference::new(col).equal_to(datum)),
@@ -122,10 +264,13 @@ fn find_keyword_pos(upper: &str, keyword: &str) -> Option<usize> {
     None
 }
 
+/// Split an IN list on commas or semicolons outside quotes. Quoted values are kept
+/// verbatim (including `''`); empty unquoted entries are dropped.
 fn parse_list_values(input: &str) -> Result<Vec<String>> {
     let mut values = Vec::new();
     let mut current = String::new();
     let mut in_quote = false;
+    let mut quoted = false;
 
     for c in input.chars() {
         match c {
@@ -134,24 +279,27 @@ fn parse_list_values(input: &str) -> Result<Vec<String>> {
             }
             '\'' => {
                 in_quote = true;
+                quoted = true;
             }
-            ',' if !in_quote => {
-                let trimmed = current.trim().to_string();
-                if !trimmed.is_empty() {
-                    values.push(trimmed);
+            ',' | ';' if !in_quote => {
+                if quoted || !current.is_empty() {
+                    values.push(std::mem::take(&mut current));
                 }
-                current.clear();
+                quoted = false;
             }
             _ => {
+                // Whitespace outside quotes only separates tokens.
                 if in_quote || !c.is_whitespace() {
                     current.push(c);
                 }
             }
         }
     }
-    let trimmed = current.trim().to_string();
-    if !trimmed.is_empty() {
-        values.push(trimmed);
+    if in_quote {
+        bail!("unterminated quote in list: {}", input);
+    }
+    if quoted || !current.is_empty() {
+        values.push(current);
     }
     Ok(values)
 }
@@ -172,8 +320,15 @@ fn string_to_datum(val: &str) -> iceberg::spec::Datum {
         return iceberg::spec::Datum::long(i);
     }
 
-    // Try float
+    // Try float. Digits a double would round away, as in wide decimal values, are kept in a
+    // decimal literal; `type_decimal_literals` makes it a double again for other columns.
     if let Ok(f) = val.parse::<f64>() {
+        let exact = Decimal::from_str_exact(val)
+            .ok()
+            .filter(|d| Decimal::from_str_exact(&f.to_string()).ok() != Some(*d));
+        if let Some(datum) = exact.and_then(|d| Datum::decimal(d).ok()) {
+            return datum;
+        }
         return iceberg::spec::Datum::double(f);
     }
 
@@ -188,9 +343,118 @@ fn string_to_datum(val: &str) -> iceberg::spec::Datum {
     iceberg::spec::Datum::string(val)
 }
 
+/// Retype values compared against `decimal(p, s)` columns as decimals of exactly that type,
+/// so `price = 19.99` matches exactly instead of comparing the column to a double. Decimal
+/// literals compared against other columns become doubles; other values are left for iceberg
+/// to convert when the filter is bound.
+pub fn type_decimal_literals(predicate: &Predicate, schema: &Schema) -> Result<Predicate> {
+    Ok(match predicate {
+        Predicate::And(expr) => {
+            let [left, right] = expr.inputs();
+            type_decimal_literals(left, schema)?.and(type_decimal_literals(right, schema)?)
+        }
+        Predicate::Or(expr) => {
+            let [left, right] = expr.inputs();
+            type_decimal_literals(left, schema)?.or(type_decimal_literals(right, schema)?)
+        }
+        Predicate::Not(expr) => {
+            let [inner] = expr.inputs();
+            !type_decimal_literals(inner, schema)?
+        }
+        Predicate::Binary(expr) => {
+            let column = expr.term();
+            let decimal = decimal_column(schema, column.name());
+            let datum = retype_literal(expr.literal(), column.name(), decimal)?;
+            let column = column.clone();
+            match expr.op() {
+                PredicateOperator::LessThan => column.less_than(datum),
+                PredicateOperator::LessThanOrEq => column.less_than_or_equal_to(datum),
+                PredicateOperator::GreaterThan => column.greater_than(datum),
+                PredicateOperator::GreaterThanOrEq => column.greater_than_or_equal_to(datum),
+                PredicateOperator::Eq => column.equal_to(datum),
+                PredicateOperator::NotEq => column.not_equal_to(datum),
+                _ => predicate.clone(),
+            }
+        }
+        Predicate::Set(expr) => {
+            let column = expr.term();
+            let decimal = decimal_column(schema, column.name());
+            let datums = expr
+                .literals()
+                .iter()
+                .map(|d| retype_literal(d, column.name(), decimal))
+                .collect::<Result<Vec<_>>>()?;
+            match expr.op() {
+                PredicateOperator::In => column.clone().is_in(datums),
+                PredicateOperator::NotIn => column.clone().is_not_in(datums),
+                _ => predicate.clone(),
+            }
+        }
+        _ => predicate.clone(),
+    })
+}
+
+/// Precision and scale of `name` when it is a decimal column.
+fn decimal_column(schema: &Schema, name: &str) -> Option<(u32, u32)> {
+    match schema.field_by_name(name)?.field_type.as_ref() {
+        Type::Primitive(PrimitiveType::Decimal { precision, scale }) => Some((*precision, *scale)),
+        _ => None,
+    }
+}
+
+/// `datum` typed for a comparison with `column`, whose precision and scale are given when it
+/// is a decimal column.
+fn retype_literal(datum: &Datum, column: &str, decimal: Option<(u32, u32)>) -> Result<Datum> {
+    if let Some((precision, scale)) = decimal {
+        return decimal_datum(datum, column, precision, scale);
+    }
+    Ok(match (datum.literal(), datum.data_type()) {
+        (PrimitiveLiteral::Int128(v), PrimitiveType::Decimal { scale, .. }) => {
+            let value = Decimal::from_i128_with_scale(*v, *scale);
+            Datum::double(value.to_f64().unwrap_or(f64::NAN))
+        }
+        _ => datum.clone(),
+    })
+}
+
+/// `datum` as a `decimal(precision, scale)` value. Values with more decimal places than the
+/// column holds are rejected rather than rounded.
+fn decimal_datum(datum: &Datum, column: &str, precision: u32, scale: u32) -> Result<Datum> {
+    let text = match datum.literal() {
+        PrimitiveLiteral::Int(v) => v.to_string(),
+        PrimitiveLiteral::Long(v) => v.to_string(),
+        // Only literals whose digits survive as a double are parsed into one, so Display gives
+        // back the value that was typed.
+        PrimitiveLiteral::Double(v) => v.to_string(),
+        PrimitiveLiteral::Int128(v) => match datum.data_type() {
+            PrimitiveType::Decimal { scale, .. } => {
+                Decimal::from_i128_with_scale(*v, *scale).to_string()
+            }
+            _ => return Ok(datum.clone()),
+        },
+        PrimitiveLiteral::String(v) => v.clone(),
+        _ => return Ok(datum.clone()),
+    };
+    let column_type = format!("decimal({}, {})", precision, scale);
+    let mut value = Decimal::from_str_exact(text.trim()).with_context(|| {
+        format!(
+            "{} is {}, but `{}` is not a number",
+            column, column_type, text
+        )
+    })?;
+    let fits_scale = value.normalize().scale() <= scale;
+    value.rescale(scale);
+    if !fits_scale || value.mantissa().unsigned_abs().to_string().len() > precision as usize {
+        bail!("{} is {} and cannot hold {}", column, column_type, text);
+    }
+    Datum::decimal_with_precision(value, precision)
+        .with_context(|| format!("{} is {} and cannot hold {}", column, column_type, text))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use iceberg::expr::Bind;
 
     #[test]
     fn parse_simple_greater_than() {
@@ -240,6 +504,80 @@ mod tests {
         assert!(parse_filter("   ").is_err());
     }
 
+    #[test]
+    fn parse_sql_not_equal() {
+        let ne = parse_filter("dept <> 'HR'").unwrap();
+        let bang = parse_filter("dept != 'HR'").unwrap();
+        assert_eq!(format!("{:?}", ne), format!("{:?}", bang));
+    }
+
+    #[test]
+    fn file_condition_sql_not_equal() {
+        let filter = parse_scan_filter("_file <> 'a.parquet'").unwrap();
+        assert_eq!(
+            filter.metadata,
+            vec![MetadataCondition::File {
+                values: vec!["a.parquet".into()],
+                negated: true
+            }]
+        );
+    }
+
+    #[test]
+    fn list_values_comma_inside_quotes() {
+        assert_eq!(parse_list_values("'a,b', 'c'").unwrap(), vec!["a,b", "c"]);
+        assert_eq!(parse_list_values("'x;y'; 'z'").unwrap(), vec!["x;y", "z"]);
+    }
+
+    #[test]
+    fn list_values_semicolon_separator() {
+        assert_eq!(parse_list_values("1; 2; 3").unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(
+            parse_list_values("'a', 'b'; 'c'").unwrap(),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn list_values_consecutive_separators() {
+        assert_eq!(
+            parse_list_values("'a',, 'b' ;; 'c'").unwrap(),
+            vec!["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn list_values_surrounding_whitespace() {
+        assert_eq!(
+            parse_list_values("  'a'  ,  'b'  ").unwrap(),
+            vec!["a", "b"]
+        );
+        assert_eq!(parse_list_values(" 'a' , 'b' ,").unwrap(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn list_values_keep_spaces_inside_quotes() {
+        assert_eq!(
+            parse_list_values("'New York', ' LA '").unwrap(),
+            vec!["New York", " LA "]
+        );
+    }
+
+    #[test]
+    fn list_values_empty_quoted_value() {
+        assert_eq!(parse_list_values("'', 'a'").unwrap(), vec!["", "a"]);
+    }
+
+    #[test]
+    fn list_values_unterminated_quote_fails() {
+        assert!(parse_list_values("'a', 'b").is_err());
+    }
+
+    #[test]
+    fn parse_in_list_with_semicolons() {
+        assert!(parse_filter("city IN ('NYC'; 'LA, CA')").is_ok());
+    }
+
     #[test]
     fn parse_invalid_filter_fails() {
         assert!(parse_filter("nonsense gibberish").is_err());
@@ -261,4 +599,161 @@ mod tests {
             format!("{:?}", iceberg::spec::Datum::string("hello"))
         );
     }
+
+    fn price_schema() -> Schema {
+        use iceberg::spec::NestedField;
+
+        let decimal = PrimitiveType::Decimal {
+            precision: 10,
+            scale: 2,
+        };
+        Schema::builder()
+            .with_fields(vec![
+                NestedField::required(1, "price", Type::Primitive(decimal)).into(),
+                NestedField::required(2, "weight", Type::Primitive(PrimitiveType::Double)).into(),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    fn price(unscaled: i64) -> Datum {
+        Datum::decimal_with_precision(Decimal::new(unscaled, 2), 10).unwrap()
+    }
+
+    #[test]
+    fn decimal_literals_take_the_column_type() {
+        let schema = price_schema();
+        let typed = |filter: &str| {
+            type_decimal_literals(&parse_filter(filter).unwrap(), &schema).map(|p| p.to_string())
+        };
+
+        assert_eq!(
+            typed("price = 19.99").unwrap(),
+            Reference::new("price").equal_to(price(1999)).to_string()
+        );
+        assert_eq!(
+            typed("price >= 20 AND weight < 1.5").unwrap(),
+            Reference::new("price")
+                .greater_than_or_equal_to(price(2000))
+                .and(Reference::new("weight").less_than(Datum::double(1.5)))
+                .to_string()
+        );
+        assert_eq!(
+            typed("price = '0.10'").unwrap(),
+            Reference::new("price").equal_to(price(10)).to_string()
+        );
+        assert!(typed("price IN (19.99, 5)").is_ok());
+
+        let err = typed("price > 19.999").unwrap_err().to_string();
+        assert_eq!(err, "price is decimal(10, 2) and cannot hold 19.999");
+        assert!(typed("price = 123456789.5").is_err());
+        assert!(typed("price = 'cheap'").is_err());
+    }
+
+    #[test]
+    fn wide_decimal_literals_keep_every_digit() {
+        use iceberg::spec::NestedField;
+
+        let wide = PrimitiveType::Decimal {
+            precision: 28,
+            scale: 10,
+        };
+        let schema = std::sync::Arc::new(
+            Schema::builder()
+                .with_fields(vec![
+                    NestedField::required(1, "amount", Type::Primitive(wide)).into(),
+                    NestedField::required(2, "weight", Type::Primitive(PrimitiveType::Double))
+                        .into(),
+                ])
+                .build()
+                .unwrap(),
+        );
+        let filter = parse_filter("amount = 123456789012.0000000001").unwrap();
+        let expected = Datum::decimal_with_precision(
+            Decimal::from_str_exact("123456789012.0000000001").unwrap(),
+            28,
+        )
+        .unwrap();
+        assert_eq!(
+            type_decimal_literals(&filter, &schema).unwrap().to_string(),
+            Reference::new("amount").equal_to(expected).to_string()
+        );
+
+        // The same literal against a double column compares as a double.
+        let filter = parse_filter("weight > 0.10000000000000000001").unwrap();
+        let typed = type_decimal_literals(&filter, &schema).unwrap();
+        assert_eq!(
+            typed.to_string(),
+            Reference::new("weight")
+                .greater_than(Datum::double(0.1))
+                .to_string()
+        );
+        assert!(typed.bind(schema, true).is_ok());
+    }
+
+    #[test]
+    fn typed_decimal_filters_bind_to_the_schema() {
+        let schema = std::sync::Arc::new(price_schema());
+        let filter = parse_filter("price = 19.99 OR price IN (5, 0.5)").unwrap();
+        let typed = type_decimal_literals(&filter, &schema).unwrap();
+        assert!(typed.bind(schema, true).is_ok());
+    }
+
+    #[test]
+    fn scan_filter_without_metadata_columns() {
+        let filter = parse_scan_filter("age > 30 OR name = 'x'").unwrap();
+        assert!(filter.predicate.is_some());
+        assert!(filter.metadata.is_empty());
+    }
+
+    #[test]
+    fn scan_filter_splits_metadata_conditions() {
+        let filter = parse_scan_filter("age > 30 AND _file = 'a.parquet' AND _pos < 10").unwrap();
+        assert!(filter.predicate.is_some());
+        assert_eq!(
+            filter.metadata,
+            vec![
+                MetadataCondition::File {
+                    values: vec!["a.parquet".into()],
+                    negated: false
+                },
+                MetadataCondition::Pos(i64::MIN..10),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_filter_only_metadata() {
+        let filter = parse_scan_filter("_file IN ('a.parquet', 'b.parquet')").unwrap();
+        assert!(filter.predicate.is_none());
+        assert_eq!(filter.metadata.len(), 1);
+    }
+
+    #[test]
+    fn scan_filter_rejects_metadata_in_or() {
+        assert!(parse_scan_filter("_pos = 1 OR age > 3").is_err());
+    }
+
+    #[test]
+    fn scan_filter_rejects_non_integer_pos() {
+        assert!(parse_scan_filter("_pos > 'abc'").is_err());
+        assert!(parse_scan_filter("_pos != 3").is_err());
+    }
+
+    #[test]
+    fn file_condition_matches_path_or_name() {
+        let cond = MetadataCondition::File {
+            values: vec!["a.parquet".into()],
+            negated: false,
+        };
+        assert!(cond.matches_file("s3://bucket/t/data/a.parquet"));
+        assert!(cond.matches_file("a.parquet"));
+        assert!(!cond.matches_file("s3://bucket/t/data/ba.parquet"));
+
+        let negated = MetadataCondition::File {
+            values: vec!["a.parquet".into()],
+            negated: true,
+        };
+        assert!(!negated.matches_file("/data/a.parquet"));
+    }
 }
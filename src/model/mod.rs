@@ -1,2 +1,3 @@
+pub mod file_diagnostics;
 pub mod filter;
 pub mod table_info;
@@ -4,16 +4,51 @@ use std::collections::HashMap;
 #[derive(Debug, Clone)]
 pub struct TableMetadata {
     pub location: String,
+    /// Metadata file the table was loaded from.
+    pub metadata_location: Option<String>,
     pub current_schema: SchemaInfo,
     pub schemas: Vec<SchemaInfo>,
     pub snapshots: Vec<SnapshotInfo>,
+    /// Named branches and tags.
+    pub refs: Vec<RefInfo>,
     pub partition_specs: Vec<PartitionSpecInfo>,
+    pub default_spec_id: i32,
     pub sort_orders: Vec<SortOrderInfo>,
+    pub default_sort_order_id: i64,
+    /// Puffin statistics files, e.g. NDV sketches, one per snapshot at most.
+    pub statistics: Vec<StatisticsFileInfo>,
+    pub partition_statistics: Vec<PartitionStatisticsInfo>,
     pub properties: HashMap<String, String>,
     pub current_snapshot_id: Option<i64>,
     pub format_version: i32,
     pub table_uuid: String,
     pub last_updated_ms: i64,
+    /// Highest field id assigned so far; the next new column gets the id after it.
+    pub last_column_id: i32,
+    /// Highest partition field id assigned so far, across all specs.
+    pub last_partition_id: i32,
+}
+
+impl TableMetadata {
+    /// The partition spec new data is written with.
+    pub fn default_partition_spec(&self) -> Option<&PartitionSpecInfo> {
+        self.partition_specs
+            .iter()
+            .find(|s| s.spec_id == self.default_spec_id)
+    }
+
+    /// The sort order new data is written with, if the table is sorted.
+    pub fn default_sort_order(&self) -> Option<&SortOrderInfo> {
+        self.sort_orders
+            .iter()
+            .find(|o| o.order_id == self.default_sort_order_id && !o.fields.is_empty())
+    }
+
+    /// Whether snapshots and manifests carry sequence numbers. V1 tables have none and
+    /// report 0 for every one of them.
+    pub fn has_sequence_numbers(&self) -> bool {
+        self.format_version >= 2
+    }
 }
 
 /// Schema information.
@@ -23,6 +58,22 @@ pub struct SchemaInfo {
     pub fields: Vec<FieldInfo>,
 }
 
+impl SchemaInfo {
+    /// Look up a field (at any depth) by its field id.
+    pub fn find_field(&self, id: i32) -> Option<&FieldInfo> {
+        fn find(fields: &[FieldInfo], id: i32) -> Option<&FieldInfo> {
+            fields.iter().find_map(|f| {
+                if f.id == id {
+                    Some(f)
+                } else {
+                    find(&f.children, id)
+                }
+            })
+        }
+        find(&self.fields, id)
+    }
+}
+
 /// Information about a single field in a schema.
 #[derive(Debug, Clone)]
 pub struct FieldInfo {
@@ -31,9 +82,20 @@ pub struct FieldInfo {
     pub field_type: String,
     pub required: bool,
     pub doc: Option<String>,
+    /// Value readers fill in for rows written before the field existed (V3).
+    pub initial_default: Option<String>,
+    /// Value writers use when a row doesn't supply the field (V3).
+    pub write_default: Option<String>,
     pub children: Vec<FieldInfo>,
 }
 
+/// A branch or tag pointing at a snapshot.
+#[derive(Debug, Clone)]
+pub struct RefInfo {
+    pub name: String,
+    pub snapshot_id: i64,
+}
+
 /// Snapshot information.
 #[derive(Debug, Clone)]
 pub struct SnapshotInfo {
@@ -47,6 +109,73 @@ pub struct SnapshotInfo {
     pub schema_id: Option<i32>,
 }
 
+/// Schema id a snapshot was written with.
+///
+/// Snapshots from older (v1) writers may not record `schema-id`. For those, the schema is inferred
+/// from the nearest ancestor that records one, since the schema only changes through a metadata
+/// commit, and failing that from the earliest later snapshot that records one. Returns `None` when
+/// no snapshot records a schema, in which case callers fall back to the current schema.
+pub fn resolve_snapshot_schema_id(snapshots: &[SnapshotInfo], snapshot_id: i64) -> Option<i32> {
+    let find = |id: i64| snapshots.iter().find(|s| s.snapshot_id == id);
+    let snapshot = find(snapshot_id)?;
+    if snapshot.schema_id.is_some() {
+        return snapshot.schema_id;
+    }
+
+    let mut ancestor = snapshot.parent_snapshot_id.and_then(find);
+    let mut hops = 0;
+    while let Some(parent) = ancestor {
+        if parent.schema_id.is_some() {
+            return parent.schema_id;
+        }
+        hops += 1;
+        if hops > snapshots.len() {
+            break;
+        }
+        ancestor = parent.parent_snapshot_id.and_then(find);
+    }
+
+    snapshots
+        .iter()
+        .filter(|s| s.schema_id.is_some())
+        .filter(|s| {
+            (s.sequence_number, s.timestamp_ms) > (snapshot.sequence_number, snapshot.timestamp_ms)
+        })
+        .min_by_key(|s| (s.sequence_number, s.timestamp_ms))
+        .and_then(|s| s.schema_id)
+}
+
+/// A Puffin file of table-level statistics computed for one snapshot.
+#[derive(Debug, Clone)]
+pub struct StatisticsFileInfo {
+    pub snapshot_id: i64,
+    pub path: String,
+    pub blobs: Vec<StatisticsBlobInfo>,
+}
+
+/// One statistic inside a Puffin file, such as a theta sketch over a column.
+#[derive(Debug, Clone)]
+pub struct StatisticsBlobInfo {
+    /// Blob type, e.g. "apache-datasketches-theta-v1".
+    pub blob_type: String,
+    pub field_ids: Vec<i32>,
+    pub properties: HashMap<String, String>,
+}
+
+impl StatisticsBlobInfo {
+    /// Estimated number of distinct values, as recorded by NDV sketch writers.
+    pub fn ndv(&self) -> Option<u64> {
+        self.properties.get("ndv")?.trim().parse().ok()
+    }
+}
+
+/// A file of per-partition statistics computed for one snapshot.
+#[derive(Debug, Clone)]
+pub struct PartitionStatisticsInfo {
+    pub snapshot_id: i64,
+    pub path: String,
+}
+
 /// Manifest file information.
 #[derive(Debug, Clone)]
 pub struct ManifestInfo {
@@ -60,6 +189,8 @@ pub struct ManifestInfo {
     pub deleted_rows_count: Option<i64>,
     pub sequence_number: i64,
     pub partition_spec_id: i32,
+    /// Set when the manifest file itself could not be read.
+    pub load_error: Option<String>,
 }
 
 /// Data file information with column-level statistics.
@@ -67,12 +198,24 @@ pub struct ManifestInfo {
 pub struct DataFileInfo {
     pub file_path: String,
     pub file_format: String,
+    /// `Data`, `PositionDeletes` or `EqualityDeletes`.
+    pub content: String,
+    /// Field ids whose values identify deleted rows, for equality delete files.
+    pub equality_ids: Vec<i32>,
     pub record_count: i64,
     pub file_size_bytes: i64,
     pub null_value_counts: HashMap<i32, i64>,
     pub lower_bounds: HashMap<i32, String>,
     pub upper_bounds: HashMap<i32, String>,
     pub partition_data: HashMap<String, String>,
+    /// Manifest entry status: `Added` or `Existing` (deleted entries are not loaded).
+    pub entry_status: String,
+    /// Snapshot that added the file, from the manifest entry.
+    pub entry_snapshot_id: Option<i64>,
+    /// Data sequence number of the entry, used to decide which deletes apply to the file.
+    pub sequence_number: Option<i64>,
+    /// Sequence number of the snapshot that added the file.
+    pub file_sequence_number: Option<i64>,
 }
 
 /// Partition spec information.
@@ -110,6 +253,21 @@ pub struct SortFieldInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn blob_ndv_parses_property() {
+        let mut blob = StatisticsBlobInfo {
+            blob_type: "apache-datasketches-theta-v1".into(),
+            field_ids: vec![1],
+            properties: HashMap::from([("ndv".to_string(), "1200345".to_string())]),
+        };
+        assert_eq!(blob.ndv(), Some(1_200_345));
+
+        blob.properties.insert("ndv".into(), "n/a".into());
+        assert_eq!(blob.ndv(), None);
+        blob.properties.clear();
+        assert_eq!(blob.ndv(), None);
+    }
+
     #[test]
     fn field_info_can_have_children() {
         let field = FieldInfo {
@@ -118,12 +276,16 @@ mod tests {
             field_type: "struct".to_string(),
             required: false,
             doc: None,
+            initial_default: None,
+            write_default: None,
             children: vec![FieldInfo {
                 id: 2,
                 name: "street".to_string(),
                 field_type: "string".to_string(),
                 required: true,
                 doc: None,
+                initial_default: None,
+                write_default: None,
                 children: vec![],
             }],
         };
@@ -131,6 +293,37 @@ mod tests {
         assert_eq!(field.children[0].name, "street");
     }
 
+    #[test]
+    fn schema_find_field_searches_nested() {
+        let schema = SchemaInfo {
+            schema_id: 0,
+            fields: vec![FieldInfo {
+                id: 1,
+                name: "address".to_string(),
+                field_type: "struct".to_string(),
+                required: false,
+                doc: None,
+                initial_default: None,
+                write_default: None,
+                children: vec![FieldInfo {
+                    id: 2,
+                    name: "street".to_string(),
+                    field_type: "string".to_string(),
+                    required: false,
+                    doc: None,
+                    initial_default: None,
+                    write_default: None,
+                    children: vec![],
+                }],
+            }],
+        };
+        assert_eq!(
+            schema.find_field(2).map(|f| f.name.as_str()),
+            Some("street")
+        );
+        assert!(schema.find_field(3).is_none());
+    }
+
     #[test]
     fn manifest_info_data_manifest() {
         let m = ManifestInfo {
@@ -144,6 +337,7 @@ mod tests {
             deleted_rows_count: Some(100),
             sequence_number: 42,
             partition_spec_id: 0,
+            load_error: None,
         };
         assert_eq!(m.content_type, "data");
         assert_eq!(m.added_data_files_count, Some(5));
@@ -166,6 +360,7 @@ mod tests {
             deleted_rows_count: None,
             sequence_number: 0,
             partition_spec_id: 1,
+            load_error: None,
         };
         assert_eq!(m.content_type, "deletes");
         assert!(m.added_data_files_count.is_none());
@@ -187,4 +382,55 @@ mod tests {
         assert_eq!(snap.operation, "append");
         assert!(snap.parent_snapshot_id.is_none());
     }
+
+    fn snapshot(id: i64, parent: Option<i64>, seq: i64, schema_id: Option<i32>) -> SnapshotInfo {
+        SnapshotInfo {
+            snapshot_id: id,
+            parent_snapshot_id: parent,
+            sequence_number: seq,
+            timestamp_ms: seq * 1000,
+            operation: "append".into(),
+            summary: HashMap::new(),
+            manifest_list: String::new(),
+            schema_id,
+        }
+    }
+
+    #[test]
+    fn resolve_schema_id_prefers_recorded_id() {
+        let snaps = vec![
+            snapshot(1, None, 1, Some(0)),
+            snapshot(2, Some(1), 2, Some(3)),
+        ];
+        assert_eq!(resolve_snapshot_schema_id(&snaps, 2), Some(3));
+    }
+
+    #[test]
+    fn resolve_schema_id_walks_ancestors() {
+        let snaps = vec![
+            snapshot(1, None, 1, Some(0)),
+            snapshot(2, Some(1), 2, None),
+            snapshot(3, Some(2), 3, None),
+            snapshot(4, Some(3), 4, Some(1)),
+        ];
+        assert_eq!(resolve_snapshot_schema_id(&snaps, 3), Some(0));
+    }
+
+    #[test]
+    fn resolve_schema_id_falls_back_to_next_recorded_snapshot() {
+        let snaps = vec![
+            snapshot(1, None, 1, None),
+            snapshot(2, Some(1), 2, None),
+            snapshot(3, Some(2), 3, Some(2)),
+            snapshot(4, Some(3), 4, Some(5)),
+        ];
+        assert_eq!(resolve_snapshot_schema_id(&snaps, 1), Some(2));
+    }
+
+    #[test]
+    fn resolve_schema_id_none_without_any_recorded_schema() {
+        let snaps = vec![snapshot(1, None, 1, None), snapshot(2, Some(1), 2, None)];
+        assert_eq!(resolve_snapshot_schema_id(&snaps, 2), None);
+        assert_eq!(resolve_snapshot_schema_id(&snaps, 99), None);
+    }
 }
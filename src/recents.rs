@@ -0,0 +1,226 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::Command;
+
+/// Tables kept in the recent list; older ones are dropped.
+pub const MAX_RECENT: usize = 20;
+
+/// Where a recently opened table was loaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecentSource {
+    Path { path: String },
+    Catalog { uri: String, table: String },
+}
+
+/// A table opened in an earlier session, offered by the startup picker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentTable {
+    pub source: RecentSource,
+    /// Config file profile the table was opened with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// When the table was last opened, in milliseconds since the epoch.
+    pub opened_at_ms: i64,
+}
+
+impl RecentTable {
+    /// The table `command` opens. Local paths are made absolute so the entry works from any
+    /// directory.
+    pub fn from_command(command: &Command, profile: Option<String>, opened_at_ms: i64) -> Self {
+        let source = match command {
+            Command::Open { path, .. } => RecentSource::Path {
+                path: if path.contains("://") {
+                    path.clone()
+                } else {
+                    std::fs::canonicalize(path)
+                        .map(|p| p.display().to_string())
+                        .unwrap_or_else(|_| path.clone())
+                },
+            },
+            Command::Catalog { uri, table, .. } => RecentSource::Catalog {
+                uri: uri.clone(),
+                table: table.clone(),
+            },
+        };
+        Self {
+            source,
+            profile,
+            opened_at_ms,
+        }
+    }
+
+    /// Command-line arguments that reopen the table. `profile` overrides the one the table
+    /// was opened with.
+    pub fn args(&self, profile: Option<&str>) -> Vec<OsString> {
+        let mut args = vec!["icepeek".to_string()];
+        if let Some(profile) = profile.or(self.profile.as_deref()) {
+            args.extend(["--profile".to_string(), profile.to_string()]);
+        }
+        match &self.source {
+            RecentSource::Path { path } => args.extend(["open".to_string(), path.clone()]),
+            RecentSource::Catalog { uri, table } => args.extend([
+                "catalog".to_string(),
+                "--uri".to_string(),
+                uri.clone(),
+                "--table".to_string(),
+                table.clone(),
+            ]),
+        }
+        args.into_iter().map(OsString::from).collect()
+    }
+
+    /// The path, or `TABLE @ URI` for catalog tables.
+    pub fn label(&self) -> String {
+        match &self.source {
+            RecentSource::Path { path } => path.clone(),
+            RecentSource::Catalog { uri, table } => format!("{} @ {}", table, uri),
+        }
+    }
+
+    /// Whether the table is known to be gone. Only local paths are checked.
+    pub fn is_missing(&self) -> bool {
+        match &self.source {
+            RecentSource::Path { path } => !path.contains("://") && !Path::new(path).exists(),
+            RecentSource::Catalog { .. } => false,
+        }
+    }
+}
+
+/// Move `table` to the front of `recents`, replacing an earlier entry for the same table and
+/// dropping entries beyond `MAX_RECENT`.
+pub fn remember(recents: &mut Vec<RecentTable>, table: RecentTable) {
+    recents.retain(|r| r.source != table.source);
+    recents.insert(0, table);
+    recents.truncate(MAX_RECENT);
+}
+
+/// `icepeek/recent.json` under `$XDG_STATE_HOME` or `~/.local/state`.
+pub fn store_path() -> Option<PathBuf> {
+    let state_dir = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("state"))
+        })?;
+    Some(state_dir.join("icepeek").join("recent.json"))
+}
+
+/// Read the recent tables, newest first; a missing file is an empty list.
+pub fn load(path: &Path) -> Result<Vec<RecentTable>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .with_context(|| format!("invalid recent tables file {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e).with_context(|| format!("cannot read {}", path.display())),
+    }
+}
+
+pub fn save(path: &Path, recents: &[RecentTable]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(recents)?)
+        .with_context(|| format!("cannot write {}", path.display()))
+}
+
+/// Add `table` to the stored recent tables. An unreadable store is started over.
+pub fn record(table: RecentTable) -> Result<()> {
+    let Some(path) = store_path() else {
+        return Ok(());
+    };
+    let mut recents = load(&path).unwrap_or_default();
+    remember(&mut recents, table);
+    save(&path, &recents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_entry(path: &str, opened_at_ms: i64) -> RecentTable {
+        RecentTable {
+            source: RecentSource::Path {
+                path: path.to_string(),
+            },
+            profile: None,
+            opened_at_ms,
+        }
+    }
+
+    #[test]
+    fn remember_moves_reopened_table_to_front_and_caps_list() {
+        let mut recents: Vec<RecentTable> = (0..MAX_RECENT as i64)
+            .map(|i| path_entry(&format!("/t{}", i), i))
+            .collect();
+        remember(&mut recents, path_entry("/t5", 100));
+        assert_eq!(recents.len(), MAX_RECENT);
+        assert_eq!(recents[0], path_entry("/t5", 100));
+        assert_eq!(recents.iter().filter(|r| r.label() == "/t5").count(), 1);
+
+        remember(&mut recents, path_entry("/new", 101));
+        assert_eq!(recents.len(), MAX_RECENT);
+        assert_eq!(recents[0].label(), "/new");
+        assert_eq!(recents[MAX_RECENT - 1].label(), "/t18");
+    }
+
+    #[test]
+    fn args_reopen_table_with_its_profile() {
+        let table = RecentTable {
+            source: RecentSource::Catalog {
+                uri: "http://localhost:8181".into(),
+                table: "db.events".into(),
+            },
+            profile: Some("dev".into()),
+            opened_at_ms: 0,
+        };
+        let args = |profile| -> Vec<String> {
+            table
+                .args(profile)
+                .into_iter()
+                .map(|a| a.into_string().unwrap())
+                .collect()
+        };
+        assert_eq!(
+            args(None),
+            [
+                "icepeek",
+                "--profile",
+                "dev",
+                "catalog",
+                "--uri",
+                "http://localhost:8181",
+                "--table",
+                "db.events"
+            ]
+        );
+        assert_eq!(args(Some("prod"))[2], "prod");
+        assert_eq!(
+            path_entry("/data/t", 0).args(None),
+            ["icepeek", "open", "/data/t"].map(OsString::from)
+        );
+    }
+
+    #[test]
+    fn store_round_trips_and_flags_missing_paths() {
+        let dir = std::env::temp_dir().join(format!("icepeek-recents-{}", std::process::id()));
+        let path = dir.join("recent.json");
+        assert!(load(&path).unwrap().is_empty());
+
+        let recents = vec![
+            path_entry(&dir.display().to_string(), 2),
+            path_entry("/no/such/table", 1),
+            path_entry("s3://bucket/table", 0),
+        ];
+        save(&path, &recents).unwrap();
+        let loaded = load(&path).unwrap();
+        let missing: Vec<bool> = loaded.iter().map(RecentTable::is_missing).collect();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(loaded, recents);
+        assert_eq!(missing, [false, true, false]);
+    }
+}
@@ -1,5 +1,15 @@
+use crossterm::event::KeyCode;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
+/// Smallest terminal the normal layout is drawn in; below this a resize hint is shown instead.
+pub const MIN_TERMINAL_WIDTH: u16 = 40;
+pub const MIN_TERMINAL_HEIGHT: u16 = 10;
+
+/// Whether `area` is too small for the tab bar, panels and status bar to be usable.
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT
+}
+
 /// Top-level layout splits the terminal into: tab bar (top), content area (middle), status bar (bottom).
 pub struct AppLayout {
     pub tab_bar: Rect,
@@ -49,6 +59,21 @@ impl SplitLayout {
     }
 }
 
+/// How far `<` and `>` move a split, and the bounds that keep both panels usable.
+pub const SPLIT_STEP_PERCENT: u16 = 5;
+pub const MIN_SPLIT_PERCENT: u16 = 20;
+pub const MAX_SPLIT_PERCENT: u16 = 80;
+
+/// Left panel share after pressing `<` (narrow) or `>` (widen), or `None` for any other key.
+pub fn resize_split(left_percent: u16, key: KeyCode) -> Option<u16> {
+    let resized = match key {
+        KeyCode::Char('<') => left_percent.saturating_sub(SPLIT_STEP_PERCENT),
+        KeyCode::Char('>') => left_percent + SPLIT_STEP_PERCENT,
+        _ => return None,
+    };
+    Some(resized.clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT))
+}
+
 /// Filter bar + data content split (for data tab).
 pub struct DataTabLayout {
     pub filter_bar: Rect,
@@ -80,6 +105,14 @@ mod tests {
         Rect::new(0, 0, w, h)
     }
 
+    #[test]
+    fn too_small_below_minimum_in_either_dimension() {
+        assert!(!is_too_small(rect(MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT)));
+        assert!(is_too_small(rect(MIN_TERMINAL_WIDTH - 1, 50)));
+        assert!(is_too_small(rect(200, MIN_TERMINAL_HEIGHT - 1)));
+        assert!(is_too_small(rect(0, 0)));
+    }
+
     #[test]
     fn app_layout_splits_correctly() {
         let layout = AppLayout::new(rect(80, 24));
@@ -95,6 +128,21 @@ mod tests {
         assert_eq!(split.right.width, 60);
     }
 
+    #[test]
+    fn resize_split_steps_within_bounds() {
+        assert_eq!(resize_split(40, KeyCode::Char('>')), Some(45));
+        assert_eq!(resize_split(40, KeyCode::Char('<')), Some(35));
+        assert_eq!(
+            resize_split(MAX_SPLIT_PERCENT, KeyCode::Char('>')),
+            Some(MAX_SPLIT_PERCENT)
+        );
+        assert_eq!(
+            resize_split(MIN_SPLIT_PERCENT, KeyCode::Char('<')),
+            Some(MIN_SPLIT_PERCENT)
+        );
+        assert_eq!(resize_split(40, KeyCode::Char('j')), None);
+    }
+
     #[test]
     fn data_tab_layout_has_filter_and_table() {
         let layout = DataTabLayout::new(rect(80, 20));
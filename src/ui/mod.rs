@@ -1,9 +1,11 @@
 pub mod layout;
 pub mod theme;
+pub mod theme_preview;
 
 /// Which tab is currently active.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
 pub enum Tab {
+    #[default]
     Data,
     Schema,
     Snapshots,
@@ -40,17 +42,42 @@ impl Tab {
     pub fn index(&self) -> usize {
         Tab::ALL.iter().position(|t| t == self).unwrap()
     }
+
+    /// The tab to the right, wrapping around to the first.
+    pub fn next(&self) -> Tab {
+        Tab::ALL[(self.index() + 1) % Tab::ALL.len()]
+    }
+
+    /// The tab to the left, wrapping around to the last.
+    pub fn prev(&self) -> Tab {
+        Tab::ALL[(self.index() + Tab::ALL.len() - 1) % Tab::ALL.len()]
+    }
 }
 
-/// Which panel within a tab currently has focus.
+/// Which region of the screen receives keys.
+///
+/// `[`/`]` and `1`-`6` move between tabs. Within a tab, `Content` hands keys to the
+/// tab's component, which owns any split between its own panes (Tab / Shift+Tab).
+/// The filter bar and column selector take focus while they are open.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Focus {
-    Left,
-    Right,
+    Content,
     FilterBar,
+    #[cfg(feature = "sql")]
+    SqlPrompt,
     ColumnSelector,
 }
 
+/// Block title annotation naming the snapshot a panel reflects: the one being viewed while
+/// time-traveling, else the current one. Empty for a table without snapshots.
+pub fn snapshot_label(viewed: Option<i64>, current: Option<i64>) -> String {
+    match (viewed, current) {
+        (Some(id), _) => format!(" @ snapshot {}", id),
+        (None, Some(id)) => format!(" @ snapshot {} (current)", id),
+        (None, None) => String::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +91,14 @@ mod tests {
         assert_eq!(Tab::from_index(99), None);
     }
 
+    #[test]
+    fn tab_next_prev_wrap() {
+        assert_eq!(Tab::Data.next(), Tab::Schema);
+        assert_eq!(Tab::Snapshots.next(), Tab::Data);
+        assert_eq!(Tab::Data.prev(), Tab::Snapshots);
+        assert_eq!(Tab::Schema.prev(), Tab::Data);
+    }
+
     #[test]
     fn tab_labels() {
         assert_eq!(Tab::Data.label(), "1:Data");
@@ -71,4 +106,11 @@ mod tests {
         assert_eq!(Tab::Stats.label(), "5:Stats");
         assert_eq!(Tab::Snapshots.label(), "6:Snapshots");
     }
+
+    #[test]
+    fn snapshot_label_prefers_viewed_snapshot() {
+        assert_eq!(snapshot_label(Some(7), Some(9)), " @ snapshot 7");
+        assert_eq!(snapshot_label(None, Some(9)), " @ snapshot 9 (current)");
+        assert_eq!(snapshot_label(None, None), "");
+    }
 }
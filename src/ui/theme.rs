@@ -4,6 +4,9 @@ use ratatui::style::{Color, Modifier, Style};
 pub struct Theme;
 
 impl Theme {
+    /// Default background of alternate data rows.
+    pub const STRIPE_COLOR: Color = Color::Rgb(25, 25, 30);
+
     // Tab bar
     pub fn tab_active() -> Style {
         Style::default()
@@ -36,7 +39,18 @@ impl Theme {
     }
 
     pub fn table_row_alt() -> Style {
-        Style::default().fg(Color::White).bg(Color::Rgb(25, 25, 30))
+        Style::default().fg(Color::White).bg(Self::STRIPE_COLOR)
+    }
+
+    pub fn null_value() -> Style {
+        Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC)
+    }
+
+    /// Cells containing the text typed into the Data tab's find prompt.
+    pub fn find_match() -> Style {
+        Style::default().fg(Color::Black).bg(Color::Yellow)
     }
 
     // Borders and panels
@@ -59,6 +73,16 @@ impl Theme {
             .add_modifier(Modifier::BOLD)
     }
 
+    pub fn status_warning() -> Style {
+        Style::default()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn status_info() -> Style {
+        Style::default().fg(Color::LightGreen)
+    }
+
     pub fn status_error() -> Style {
         Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
     }
@@ -118,9 +142,36 @@ impl Theme {
             .add_modifier(Modifier::BOLD)
     }
 
+    // Net change badges in the snapshot list
+    pub fn change_growth() -> Style {
+        Style::default().fg(Color::Green)
+    }
+
+    pub fn change_shrink() -> Style {
+        Style::default().fg(Color::Red)
+    }
+
+    pub fn change_none() -> Style {
+        Style::default().fg(Color::DarkGray)
+    }
+
+    // Age of the current snapshot
+    pub fn age_fresh() -> Style {
+        Style::default().fg(Color::Green)
+    }
+
+    pub fn age_recent() -> Style {
+        Style::default().fg(Color::Yellow)
+    }
+
+    pub fn age_stale() -> Style {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    }
+
     pub fn status_time_travel() -> Style {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(Color::Black)
+            .bg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
     }
 }
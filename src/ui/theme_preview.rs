@@ -0,0 +1,147 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, Tabs};
+use ratatui::Frame;
+
+use super::layout::{AppLayout, SplitLayout};
+use super::theme::Theme;
+use super::Tab;
+
+/// Draw a static sample of every themed element over a synthetic layout.
+pub fn render(frame: &mut Frame) {
+    let layout = AppLayout::new(frame.area());
+
+    let tab_titles: Vec<Line> = Tab::ALL
+        .iter()
+        .map(|t| {
+            if *t == Tab::Data {
+                Line::styled(t.label(), Theme::tab_active())
+            } else {
+                Line::styled(t.label(), Theme::tab_inactive())
+            }
+        })
+        .collect();
+    let tabs = Tabs::new(tab_titles)
+        .select(0)
+        .divider(" │ ")
+        .style(Theme::tab_bar_bg());
+    frame.render_widget(tabs, layout.tab_bar);
+
+    let split = SplitLayout::new(layout.content, 50);
+    render_table_sample(frame, split.left);
+    render_detail_sample(frame, split.right);
+
+    let status = Line::from(vec![
+        Span::styled(" Loading... ", Theme::status_loading()),
+        Span::styled(" TIME TRAVEL ", Theme::status_time_travel()),
+        Span::styled(" warning ", Theme::status_warning()),
+        Span::styled(" info ", Theme::status_info()),
+        Span::styled(" error ", Theme::status_error()),
+        Span::styled(" press any key to exit", Theme::status_key_hint()),
+    ]);
+    frame.render_widget(
+        Paragraph::new(status).style(Theme::status_bar()),
+        layout.status_bar,
+    );
+}
+
+fn render_table_sample(frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3)])
+        .split(area);
+
+    let filter = Line::from(vec![
+        Span::styled("Filter: ", Theme::filter_active()),
+        Span::styled("id > 10", Theme::filter_active()),
+        Span::styled("  (inactive)", Theme::filter_inactive()),
+    ]);
+    frame.render_widget(Paragraph::new(filter), chunks[0]);
+
+    let header = Row::new(["id", "name", "score"]).style(Theme::table_header());
+    let rows = vec![
+        Row::new(["1", "alice", "9.5"]).style(Theme::table_row_normal()),
+        Row::new(["2", "bob", "7.25"]).style(Theme::table_row_alt()),
+        Row::new([
+            Cell::from("3"),
+            Cell::from("carol"),
+            Cell::from("NULL").style(Theme::null_value()),
+        ])
+        .style(Theme::table_row_normal()),
+        Row::new(["4", "dave", "8.0"]).style(Theme::table_row_selected()),
+    ];
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Length(8),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Focused ")
+            .border_style(Theme::border_focused()),
+    );
+    frame.render_widget(table, chunks[1]);
+}
+
+fn render_detail_sample(frame: &mut Frame, area: Rect) {
+    let lines = vec![
+        Line::styled("─── Title ───", Theme::title()),
+        Line::from(vec![
+            Span::styled("address", Theme::field_name()),
+            Span::styled("?", Theme::field_id()),
+            Span::raw(": "),
+            Span::styled("struct", Theme::field_type()),
+        ]),
+        Line::from(vec![
+            Span::styled("Label: ", Theme::label()),
+            Span::styled("value", Theme::value()),
+        ]),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled("  q  ", Theme::help_key()),
+            Span::styled("Help key description", Theme::help_description()),
+        ]),
+    ];
+    let detail = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Unfocused ")
+            .border_style(Theme::border_unfocused()),
+    );
+    frame.render_widget(detail, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn preview_renders_every_section() {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(render).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect();
+        for expected in [
+            "1:Data",
+            "Focused",
+            "Unfocused",
+            "alice",
+            "NULL",
+            "TIME TRAVEL",
+        ] {
+            assert!(text.contains(expected), "missing {expected}");
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt::Display;
+
+/// Insert `,` between groups of three digits in the integer part of a plain decimal number,
+/// e.g. "-1234567.25" becomes "-1,234,567.25". Anything else, such as "1e10" or "NaN", is
+/// returned unchanged.
+pub fn group_digits(text: &str) -> String {
+    let (sign, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text),
+    };
+    let (int_part, fraction) = match unsigned.split_once('.') {
+        Some((int_part, fraction)) => (int_part, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if int_part.is_empty() || !is_digits(int_part) || !fraction.is_none_or(is_digits) {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len() + int_part.len() / 3);
+    out.push_str(sign);
+    for (i, digit) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        out.push('.');
+        out.push_str(fraction);
+    }
+    out
+}
+
+/// A count rendered raw or with digit grouping.
+pub fn format_count(n: impl Display, grouped: bool) -> String {
+    let raw = n.to_string();
+    if grouped {
+        group_digits(&raw)
+    } else {
+        raw
+    }
+}
+
+pub const BYTES_PER_KB: i64 = 1024;
+pub const BYTES_PER_MB: i64 = BYTES_PER_KB * 1024;
+pub const BYTES_PER_GB: i64 = BYTES_PER_MB * 1024;
+
+/// A byte count in the largest binary unit below it, up to GB, e.g. "1.5 KB".
+pub fn format_size(bytes: i64) -> String {
+    if bytes < BYTES_PER_KB {
+        format!("{} B", bytes)
+    } else if bytes < BYTES_PER_MB {
+        format!("{:.1} KB", bytes as f64 / BYTES_PER_KB as f64)
+    } else if bytes < BYTES_PER_GB {
+        format!("{:.1} MB", bytes as f64 / BYTES_PER_MB as f64)
+    } else {
+        format!("{:.1} GB", bytes as f64 / BYTES_PER_GB as f64)
+    }
+}
+
+/// A Unix timestamp in milliseconds as UTC wall-clock time, or the raw value when it's out
+/// of range.
+pub fn format_timestamp_ms(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| format!("{}ms", ms))
+}
+
+/// Parse a point in time into epoch milliseconds. Times without an offset are UTC.
+pub fn parse_timestamp(s: &str) -> Result<i64, String> {
+    let s = s.trim();
+    if let Ok(ms) = s.parse::<i64>() {
+        return Ok(ms);
+    }
+    if let Ok(t) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(t.timestamp_millis());
+    }
+    if let Ok(t) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+        return Ok(t.and_utc().timestamp_millis());
+    }
+    if let Ok(d) = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(d
+            .and_time(chrono::NaiveTime::MIN)
+            .and_utc()
+            .timestamp_millis());
+    }
+    Err(format!(
+        "invalid timestamp `{}`: expected RFC 3339, \"YYYY-MM-DD[ HH:MM:SS]\" or epoch milliseconds",
+        s
+    ))
+}
+
+/// Parse the end of a time range like `parse_timestamp`, except that a date alone means the
+/// last millisecond of that day, so `--until 2025-01-31` takes in all of January 31.
+pub fn parse_timestamp_until(s: &str) -> Result<i64, String> {
+    match chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d") {
+        Ok(d) => Ok(d
+            .and_hms_milli_opt(23, 59, 59, 999)
+            .expect("valid time of day")
+            .and_utc()
+            .timestamp_millis()),
+        Err(_) => parse_timestamp(s),
+    }
+}
+
+/// Coarse age such as "5 minutes" or "3 days".
+pub fn format_relative(age_ms: i64) -> String {
+    const MINUTE: i64 = 60_000;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+    let (n, unit) = match age_ms.max(0) {
+        ms if ms < MINUTE => return "less than a minute".into(),
+        ms if ms < HOUR => (ms / MINUTE, "minute"),
+        ms if ms < DAY => (ms / HOUR, "hour"),
+        ms => (ms / DAY, "day"),
+    };
+    format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_only_until_takes_in_the_whole_day() {
+        assert_eq!(parse_timestamp_until("2025-01-02"), Ok(1_735_862_399_999));
+        assert_eq!(
+            parse_timestamp_until("2025-01-02 12:00:00"),
+            parse_timestamp("2025-01-02 12:00:00")
+        );
+        assert!(parse_timestamp_until("tomorrow").is_err());
+    }
+
+    #[test]
+    fn groups_integer_part() {
+        assert_eq!(group_digits("0"), "0");
+        assert_eq!(group_digits("999"), "999");
+        assert_eq!(group_digits("1000"), "1,000");
+        assert_eq!(group_digits("1234567"), "1,234,567");
+        assert_eq!(group_digits("-1234567.125"), "-1,234,567.125");
+    }
+
+    #[test]
+    fn leaves_non_plain_numbers_alone() {
+        for text in ["", "-", "abc", "1e10", "NaN", "12.3.4", "1,000", ".5"] {
+            assert_eq!(group_digits(text), text);
+        }
+    }
+
+    #[test]
+    fn format_count_respects_flag() {
+        assert_eq!(format_count(50_000_u64, false), "50000");
+        assert_eq!(format_count(50_000_u64, true), "50,000");
+    }
+
+    #[test]
+    fn format_size_works() {
+        assert_eq!(format_size(500), "500 B");
+        assert_eq!(format_size(1500), "1.5 KB");
+        assert_eq!(format_size(1_500_000), "1.4 MB");
+        assert_eq!(format_size(1_500_000_000), "1.4 GB");
+    }
+
+    #[test]
+    fn format_size_unit_boundaries() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(BYTES_PER_KB - 1), "1023 B");
+        assert_eq!(format_size(BYTES_PER_KB), "1.0 KB");
+        assert_eq!(format_size(BYTES_PER_MB), "1.0 MB");
+        assert_eq!(format_size(BYTES_PER_GB), "1.0 GB");
+        assert_eq!(format_size(2048 * BYTES_PER_GB), "2048.0 GB");
+    }
+
+    #[test]
+    fn format_timestamp_valid() {
+        assert_eq!(
+            format_timestamp_ms(1700000000000),
+            "2023-11-14 22:13:20 UTC"
+        );
+        assert_eq!(format_timestamp_ms(i64::MAX), format!("{}ms", i64::MAX));
+    }
+
+    #[test]
+    fn format_relative_units() {
+        assert_eq!(format_relative(30_000), "less than a minute");
+        assert_eq!(format_relative(60_000), "1 minute");
+        assert_eq!(format_relative(2 * 3_600_000 + 1), "2 hours");
+        assert_eq!(format_relative(86_400_000), "1 day");
+        assert_eq!(format_relative(-5), "less than a minute");
+    }
+}